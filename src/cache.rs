@@ -0,0 +1,65 @@
+//! The cache of incremental runs, kept in the output directory.
+//!
+//! It records a hash of every input file and generated page along with the
+//! version and settings that produced them. A run whose inputs and settings
+//! match the cache has nothing to do; otherwise pages whose content did not
+//! change are left untouched, so their modification times stay as they were.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the cache file in the output directory.
+pub const CACHE_FILE: &str = ".sdkdocgen-cache.json";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Cache {
+    /// Version of sdkdocgen that wrote the cache.
+    pub version: String,
+    /// Hash of the settings the pages were generated with.
+    pub settings: String,
+    /// Hash of the content of each input file, by path.
+    pub inputs: BTreeMap<String, String>,
+    /// Hash of the content of each generated page, by path.
+    pub pages: BTreeMap<String, String>,
+}
+
+impl Cache {
+    /// Reads the cache in `output_path`. A missing or unreadable cache is
+    /// `None`, as if nothing had been generated yet.
+    pub fn load(output_path: &Path) -> Option<Cache> {
+        let source = fs::read_to_string(output_path.join(CACHE_FILE)).ok()?;
+        match serde_json::from_str(&source) {
+            Ok(cache) => Some(cache),
+            Err(err) => {
+                tracing::warn!("ignoring invalid {}: {}", CACHE_FILE, err);
+                None
+            }
+        }
+    }
+
+    /// Writes the cache to `output_path`.
+    pub fn save(&self, output_path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("a cache always serializes");
+        fs::create_dir_all(output_path)?;
+        fs::write(output_path.join(CACHE_FILE), json + "\n")
+    }
+
+    /// Whether a page with `content` is already at `path` as recorded.
+    pub fn has_page(&self, path: &str, content: &str) -> bool {
+        self.pages.get(path) == Some(&content_hash(content.as_bytes()))
+    }
+}
+
+/// A stable 64-bit FNV-1a hash of `content`, in hexadecimal.
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
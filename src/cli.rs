@@ -0,0 +1,550 @@
+//! Reference pages for command-line interfaces defined with Click or
+//! argparse.
+//!
+//! A Click command is documented by what its user types and sees, not by
+//! the signature of the function implementing it. Commands are recognized
+//! by their decorators (`@click.command()`, `@click.group()`, `@cli.command()`)
+//! and parameters by `@click.option(...)` and `@click.argument(...)`;
+//! argparse parsers by `ArgumentParser(...)`, `add_subparsers()`,
+//! `add_parser(...)` and `add_argument(...)` calls on the variables they
+//! are assigned to.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use rustpython_parser::{ast, Parse, ParseError};
+
+use crate::docstring::clean_docstring;
+use crate::render::table_cell;
+use crate::source::{is_true, keyword, string, SourceText};
+use crate::types::{extract_type, infer_type};
+
+/// A command or command group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliCommand {
+    /// Name the command is invoked by.
+    pub name: String,
+    pub help: Option<String>,
+    /// Python function implementing a Click command.
+    pub function: Option<String>,
+    /// One-based line where the command is defined.
+    pub line: usize,
+    /// Positional arguments in declaration order.
+    pub arguments: Vec<CliParameter>,
+    /// Options in declaration order.
+    pub options: Vec<CliParameter>,
+    /// Subcommands in definition order.
+    pub subcommands: Vec<CliCommand>,
+}
+
+/// An option or positional argument of a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliParameter {
+    /// Option flags such as `--name` and `-n`, or an argument's name.
+    pub flags: Vec<String>,
+    pub type_name: String,
+    /// Default value as written in the source.
+    pub default: Option<String>,
+    pub required: bool,
+    pub help: Option<String>,
+}
+
+impl CliCommand {
+    /// Names of the Click command functions in this command tree.
+    pub fn functions(&self) -> BTreeSet<&str> {
+        let mut functions: BTreeSet<&str> = self.function.as_deref().into_iter().collect();
+        for subcommand in &self.subcommands {
+            functions.extend(subcommand.functions());
+        }
+        functions
+    }
+}
+
+/// Finds the command-line interfaces defined in `source`. Returns the
+/// top-level commands, each with its subcommands nested inside.
+pub fn extract_cli(source: &str, path: &Path) -> Result<Vec<CliCommand>, ParseError> {
+    let suite = ast::Suite::parse(source, &path.to_string_lossy())?;
+    let mut extractor = Extractor {
+        source: SourceText::new(source),
+        program: path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().replace('_', "-"))
+            .unwrap_or_default(),
+        click_modules: BTreeSet::new(),
+        click_names: BTreeMap::new(),
+        argparse_modules: BTreeSet::new(),
+        argparse_names: BTreeSet::new(),
+        nodes: Vec::new(),
+        groups: BTreeMap::new(),
+        parsers: BTreeMap::new(),
+        subparsers: BTreeMap::new(),
+    };
+    extractor.stmts(&suite);
+    Ok(extractor.tree(None))
+}
+
+/// A command while the tree is being built.
+struct Node {
+    command: CliCommand,
+    parent: Option<usize>,
+}
+
+struct Extractor<'a> {
+    source: SourceText<'a>,
+    /// Name of an argparse program without `prog=`: the file stem.
+    program: String,
+    /// Names `click` is imported as.
+    click_modules: BTreeSet<String>,
+    /// Names imported from `click`, mapped to the imported name.
+    click_names: BTreeMap<String, String>,
+    argparse_modules: BTreeSet<String>,
+    /// Names `argparse.ArgumentParser` is imported as.
+    argparse_names: BTreeSet<String>,
+    nodes: Vec<Node>,
+    /// Click command functions by Python name.
+    groups: BTreeMap<String, usize>,
+    /// Variables holding an argparse parser or argument group.
+    parsers: BTreeMap<String, usize>,
+    /// Variables holding the result of `add_subparsers()`, mapped to the
+    /// parser they belong to.
+    subparsers: BTreeMap<String, usize>,
+}
+
+impl Extractor<'_> {
+    fn add(&mut self, command: CliCommand, parent: Option<usize>) -> usize {
+        self.nodes.push(Node { command, parent });
+        self.nodes.len() - 1
+    }
+
+    fn tree(&self, parent: Option<usize>) -> Vec<CliCommand> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.parent == parent)
+            .map(|(index, node)| CliCommand {
+                subcommands: self.tree(Some(index)),
+                ..node.command.clone()
+            })
+            .collect()
+    }
+
+    fn stmts(&mut self, body: &[ast::Stmt]) {
+        for stmt in body {
+            match stmt {
+                ast::Stmt::Import(import) => {
+                    for alias in &import.names {
+                        let local_name = alias.asname.as_ref().unwrap_or(&alias.name).to_string();
+                        match alias.name.as_str() {
+                            "click" => self.click_modules.insert(local_name),
+                            "argparse" => self.argparse_modules.insert(local_name),
+                            _ => false,
+                        };
+                    }
+                }
+                ast::Stmt::ImportFrom(import) => {
+                    let module = import.module.as_ref().map(|module| module.as_str());
+                    for alias in &import.names {
+                        let local_name = alias.asname.as_ref().unwrap_or(&alias.name).to_string();
+                        match module {
+                            Some("click") => {
+                                self.click_names.insert(local_name, alias.name.to_string());
+                            }
+                            Some("argparse") if alias.name.as_str() == "ArgumentParser" => {
+                                self.argparse_names.insert(local_name);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                ast::Stmt::FunctionDef(func_def) => {
+                    self.click_command(func_def);
+                    self.stmts(&func_def.body);
+                }
+                ast::Stmt::Assign(assign) => {
+                    if let ast::Expr::Call(call) = &*assign.value {
+                        let target = match assign.targets.as_slice() {
+                            [ast::Expr::Name(name)] => Some(name.id.as_str()),
+                            _ => None,
+                        };
+                        self.call(call, target);
+                    }
+                }
+                ast::Stmt::Expr(expr) => {
+                    if let ast::Expr::Call(call) = &*expr.value {
+                        self.call(call, None);
+                    }
+                }
+                ast::Stmt::If(stmt) => {
+                    self.stmts(&stmt.body);
+                    self.stmts(&stmt.orelse);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// What a Click decorator is: `command`, `group`, `option` or
+    /// `argument`, and the group function for `@cli.command()`.
+    fn click_decorator(&self, decorator: &ast::Expr) -> Option<(String, Option<usize>)> {
+        let callee = match decorator {
+            ast::Expr::Call(call) => extract_type(&call.func),
+            decorator => extract_type(decorator),
+        };
+        if let Some(name) = self.click_names.get(&callee) {
+            return Some((name.clone(), None));
+        }
+        let (base, name) = callee.rsplit_once('.')?;
+        if self.click_modules.contains(base) {
+            return Some((name.to_string(), None));
+        }
+        let group = *self.groups.get(base)?;
+        matches!(name, "command" | "group").then(|| (name.to_string(), Some(group)))
+    }
+
+    fn click_command(&mut self, func_def: &ast::StmtFunctionDef) {
+        let mut command = None;
+        let mut options = Vec::new();
+        let mut arguments = Vec::new();
+        for decorator in &func_def.decorator_list {
+            let Some((kind, group)) = self.click_decorator(decorator) else {
+                continue;
+            };
+            let call = match decorator {
+                ast::Expr::Call(call) => Some(call),
+                _ => None,
+            };
+            match (kind.as_str(), call) {
+                ("command" | "group", _) => command = Some((call, group)),
+                ("option", Some(call)) => options.extend(self.click_option(call)),
+                ("argument", Some(call)) => arguments.extend(self.click_argument(call)),
+                _ => {}
+            }
+        }
+        let Some((call, group)) = command else {
+            return;
+        };
+
+        let name = call
+            .and_then(|call| keyword(call, "name").or(call.args.first()).and_then(string))
+            .map_or_else(|| func_def.name.replace('_', "-"), str::to_string);
+        let help = call
+            .and_then(|call| keyword(call, "help"))
+            .and_then(string)
+            .map(clean_docstring)
+            .or_else(|| docstring(&func_def.body));
+        let index = self.add(
+            CliCommand {
+                name,
+                help,
+                function: Some(func_def.name.to_string()),
+                line: self.source.line(func_def),
+                arguments,
+                options,
+                subcommands: Vec::new(),
+            },
+            group,
+        );
+        self.groups.insert(func_def.name.to_string(), index);
+    }
+
+    fn click_option(&self, call: &ast::ExprCall) -> Option<CliParameter> {
+        if keyword(call, "hidden").is_some_and(is_true) {
+            return None;
+        }
+        let flags: Vec<String> = call
+            .args
+            .iter()
+            .filter_map(string)
+            .filter(|flag| flag.starts_with('-'))
+            .map(str::to_string)
+            .collect();
+        if flags.is_empty() {
+            return None;
+        }
+        let is_flag = keyword(call, "is_flag").is_some_and(is_true)
+            || flags.iter().any(|flag| flag.contains('/'));
+        let type_name = if is_flag {
+            "bool".to_string()
+        } else if keyword(call, "count").is_some_and(is_true) {
+            "int".to_string()
+        } else {
+            self.type_name(call)
+        };
+        let default = keyword(call, "default")
+            .map(|default| self.source.one_line(default))
+            .or_else(|| is_flag.then(|| "False".to_string()));
+        Some(CliParameter {
+            flags,
+            type_name,
+            default,
+            required: keyword(call, "required").is_some_and(is_true),
+            help: keyword(call, "help").and_then(string).map(str::to_string),
+        })
+    }
+
+    fn click_argument(&self, call: &ast::ExprCall) -> Option<CliParameter> {
+        let name = call.args.first().and_then(string)?;
+        let variadic =
+            keyword(call, "nargs").is_some_and(|nargs| self.source.one_line(nargs) == "-1");
+        let default = keyword(call, "default").map(|default| self.source.one_line(default));
+        let required = match keyword(call, "required") {
+            Some(required) => is_true(required),
+            None => default.is_none() && !variadic,
+        };
+        Some(CliParameter {
+            flags: vec![name.to_uppercase()],
+            type_name: self.type_name(call),
+            default,
+            required,
+            help: None,
+        })
+    }
+
+    /// The `type=` of a parameter, or the type of its default, or `str`.
+    fn type_name(&self, call: &ast::ExprCall) -> String {
+        match keyword(call, "type") {
+            Some(type_name) => self.source.one_line(type_name),
+            None => keyword(call, "default")
+                .and_then(infer_type)
+                .unwrap_or("str")
+                .to_string(),
+        }
+    }
+
+    /// Handles a call whose result is assigned to `target`, if any.
+    fn call(&mut self, call: &ast::ExprCall, target: Option<&str>) {
+        let callee = extract_type(&call.func);
+        let (receiver, method) = callee.rsplit_once('.').unwrap_or(("", &callee));
+
+        let is_parser = self.argparse_names.contains(&callee)
+            || (method == "ArgumentParser" && self.argparse_modules.contains(receiver));
+        if is_parser {
+            let name = keyword(call, "prog")
+                .and_then(string)
+                .map_or_else(|| self.program.clone(), str::to_string);
+            let help = keyword(call, "description")
+                .and_then(string)
+                .map(clean_docstring);
+            let index = self.add(self.parser_command(name, help, call), None);
+            self.parsers
+                .extend(target.map(|target| (target.to_string(), index)));
+            return;
+        }
+
+        if let Some(&parser) = self.parsers.get(receiver) {
+            match method {
+                "add_subparsers" => {
+                    self.subparsers
+                        .extend(target.map(|target| (target.to_string(), parser)));
+                }
+                "add_argument_group" | "add_mutually_exclusive_group" => {
+                    self.parsers
+                        .extend(target.map(|target| (target.to_string(), parser)));
+                }
+                "add_argument" => {
+                    if let Some((is_option, parameter)) = self.argparse_argument(call) {
+                        let command = &mut self.nodes[parser].command;
+                        if is_option {
+                            command.options.push(parameter);
+                        } else {
+                            command.arguments.push(parameter);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if let Some(&parent) = self.subparsers.get(receiver) {
+            if method == "add_parser" {
+                let Some(name) = call.args.first().and_then(string) else {
+                    return;
+                };
+                let help = keyword(call, "help")
+                    .or_else(|| keyword(call, "description"))
+                    .and_then(string)
+                    .map(clean_docstring);
+                let command = self.parser_command(name.to_string(), help, call);
+                let index = self.add(command, Some(parent));
+                self.parsers
+                    .extend(target.map(|target| (target.to_string(), index)));
+            }
+        } else if let Some(&group) = self.groups.get(receiver) {
+            // `cli.add_command(sub)` or `cli.add_command(sub, name="other")`
+            if method != "add_command" {
+                return;
+            }
+            let Some(ast::Expr::Name(sub)) = call.args.first() else {
+                return;
+            };
+            if let Some(&index) = self.groups.get(sub.id.as_str()) {
+                self.nodes[index].parent = Some(group);
+                if let Some(name) = keyword(call, "name").or(call.args.get(1)).and_then(string) {
+                    self.nodes[index].command.name = name.to_string();
+                }
+            }
+        }
+    }
+
+    fn parser_command(
+        &self,
+        name: String,
+        help: Option<String>,
+        call: &ast::ExprCall,
+    ) -> CliCommand {
+        CliCommand {
+            name,
+            help,
+            function: None,
+            line: self.source.line(call),
+            arguments: Vec::new(),
+            options: Vec::new(),
+            subcommands: Vec::new(),
+        }
+    }
+
+    /// Reads `add_argument(...)`. Returns whether it is an option, or
+    /// `None` for arguments hidden with `help=argparse.SUPPRESS`.
+    fn argparse_argument(&self, call: &ast::ExprCall) -> Option<(bool, CliParameter)> {
+        let help = keyword(call, "help");
+        if help.is_some_and(|help| extract_type(help).ends_with("SUPPRESS")) {
+            return None;
+        }
+        let flags: Vec<String> = call
+            .args
+            .iter()
+            .filter_map(string)
+            .map(str::to_string)
+            .collect();
+        let is_option = flags.first()?.starts_with('-');
+
+        let action = keyword(call, "action").and_then(string);
+        let (type_name, implied_default) = match action {
+            Some("store_true") => ("bool".to_string(), Some("False")),
+            Some("store_false") => ("bool".to_string(), Some("True")),
+            Some("count") => ("int".to_string(), None),
+            _ => (self.type_name(call), None),
+        };
+        let default = keyword(call, "default")
+            .map(|default| self.source.one_line(default))
+            .or(implied_default.map(str::to_string));
+        let required = if is_option {
+            keyword(call, "required").is_some_and(is_true)
+        } else {
+            let nargs = keyword(call, "nargs").and_then(string);
+            !matches!(nargs, Some("?" | "*"))
+        };
+        Some((
+            is_option,
+            CliParameter {
+                flags,
+                type_name,
+                default,
+                required,
+                help: help.and_then(string).map(str::to_string),
+            },
+        ))
+    }
+}
+
+/// A command's docstring as Click shows it: cleaned, and cut at a form feed.
+fn docstring(body: &[ast::Stmt]) -> Option<String> {
+    let ast::Stmt::Expr(expr) = body.first()? else {
+        return None;
+    };
+    let docstring = string(&expr.value)?;
+    let shown = docstring.split('\x0c').next().unwrap_or(docstring);
+    Some(clean_docstring(shown).trim_end().to_string())
+}
+
+/// Renders a command and its subcommands as one MDX page.
+pub fn render_cli_page(command: &CliCommand) -> String {
+    let mut page = String::new();
+    page.push_str("---\n");
+    page.push_str(&format!("title: {}\n", command.name));
+    page.push_str("---\n\n");
+    render_command(&mut page, command, &[]);
+    page
+}
+
+fn render_command(page: &mut String, command: &CliCommand, parents: &[&str]) {
+    let mut path = parents.to_vec();
+    path.push(&command.name);
+
+    let kind = if command.subcommands.is_empty() {
+        "command"
+    } else {
+        "group"
+    };
+    page.push_str(&format!("<a id=\"{}\"></a>\n", anchor(&path)));
+    page.push_str(&format!(
+        "{} `{}` `{}`\n\n",
+        "#".repeat((path.len() + 1).min(4)),
+        path.join(" "),
+        kind
+    ));
+    if let Some(help) = &command.help {
+        page.push_str(&format!("{}\n\n", help.trim()));
+    }
+
+    if !command.arguments.is_empty() {
+        page.push_str("**Arguments:**\n\n");
+        page.push_str(&parameters_table("Name", &command.arguments));
+    }
+    if !command.options.is_empty() {
+        page.push_str("**Options:**\n\n");
+        page.push_str(&parameters_table("Flag", &command.options));
+    }
+    if !command.subcommands.is_empty() {
+        page.push_str("**Commands:**\n\n| Command | Description |\n| --- | --- |\n");
+        for subcommand in &command.subcommands {
+            let mut sub_path = path.clone();
+            sub_path.push(&subcommand.name);
+            let summary = subcommand
+                .help
+                .as_deref()
+                .and_then(|help| help.lines().next())
+                .unwrap_or("");
+            page.push_str(&format!(
+                "| [`{}`](#{}) | {} |\n",
+                subcommand.name,
+                anchor(&sub_path),
+                table_cell(summary)
+            ));
+        }
+        page.push('\n');
+    }
+
+    for subcommand in &command.subcommands {
+        render_command(page, subcommand, &path);
+    }
+}
+
+fn anchor(path: &[&str]) -> String {
+    format!("cli.{}", path.join("."))
+}
+
+fn parameters_table(first_column: &str, parameters: &[CliParameter]) -> String {
+    let mut table = format!(
+        "| {} | Type | Default | Required | Description |\n| --- | --- | --- | --- | --- |\n",
+        first_column
+    );
+    for parameter in parameters {
+        let flags: Vec<String> = parameter
+            .flags
+            .iter()
+            .map(|flag| format!("`{}`", flag))
+            .collect();
+        table.push_str(&format!(
+            "| {} | `{}` | {} | {} | {} |\n",
+            flags.join(", "),
+            table_cell(&parameter.type_name),
+            parameter.default.as_deref().map_or_else(
+                || "-".to_string(),
+                |default| format!("`{}`", table_cell(default))
+            ),
+            if parameter.required { "yes" } else { "no" },
+            table_cell(parameter.help.as_deref().unwrap_or(""))
+        ));
+    }
+    table.push('\n');
+    table
+}
@@ -0,0 +1,298 @@
+//! The TOML configuration file, passed with `--config` or found as
+//! `sdkdocgen.toml` in the current directory.
+//!
+//! Keys are the long names of the command-line options, with `_` in place
+//! of `-`; options given on the command line take precedence:
+//!
+//! ```toml
+//! file = ["src/zenml"]
+//! output_path = "docs/sdk"
+//! exclude = ["**/tests/**"]
+//! dunders = "all"
+//! usage_snippets = false
+//!
+//! [frontmatter]
+//! icon = "code"
+//!
+//! [modules."zenml.client"]
+//! title = "The ZenML client"
+//!
+//! [modules."zenml.client".frontmatter]
+//! sidebarTitle = "Client"
+//!
+//! [markers]
+//! internal = "exclude"
+//! experimental = { badge = "experimental", note = "May change without notice." }
+//! beta = { badge = "beta" }
+//! ```
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coverage::CoverageFormat;
+use crate::diagnostics::DiagnosticsFormat;
+use crate::format::Format;
+use crate::lint::DocstringRequirement;
+use crate::model::Badge;
+use crate::nav::NavFormat;
+use crate::options::{
+    AliasStyle, Dunders, MarkerAction, NamePattern, NestedFunctions, OptionalStyle, RenderOptions,
+};
+use crate::stubs::StubPreference;
+use crate::template::OutputTemplate;
+
+/// Settings read from a configuration file. Every key is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Python files, package directories or glob patterns to document.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub file: Vec<PathBuf>,
+    pub output_path: Option<PathBuf>,
+    pub flat: Option<bool>,
+    pub index_name: Option<String>,
+    pub package_index: Option<bool>,
+    pub nav_format: Option<NavFormat>,
+    pub output_template: Option<OutputTemplate>,
+    /// Glob patterns of files to leave out.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    pub follow_symlinks: Option<bool>,
+    pub include_notebook_markdown: Option<bool>,
+    pub locale: Option<PathBuf>,
+    #[serde(alias = "package_name")]
+    pub package_prefix: Option<String>,
+    pub special_tag: Option<bool>,
+    pub prettify_titles: Option<bool>,
+    pub doc_version: Option<String>,
+    pub format: Option<Format>,
+    pub include_private: Option<bool>,
+    pub dunders: Option<Dunders>,
+    pub ignore_all: Option<bool>,
+    /// Decorators whose functions and methods are left out.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skip_decorator: Vec<String>,
+    /// Base classes making a class a pydantic model, besides `BaseModel`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pydantic_base: Vec<String>,
+    pub show_validators: Option<bool>,
+    pub skip_nested_config: Option<bool>,
+    pub nested_functions: Option<NestedFunctions>,
+    pub merge_init: Option<bool>,
+    /// Decorators marking deprecated definitions, besides `deprecated`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deprecated_decorator: Vec<String>,
+    pub hide_deprecated: Option<bool>,
+    pub deprecated_last: Option<bool>,
+    pub signature_from_docstring: Option<bool>,
+    pub all_module_attributes: Option<bool>,
+    pub only: Option<NamePattern>,
+    pub infer_raises: Option<bool>,
+    pub prefer: Option<StubPreference>,
+    pub stubs_dir: Option<PathBuf>,
+    pub examples_from: Option<PathBuf>,
+    pub expand_aliases: Option<AliasStyle>,
+    pub inheritance_diagrams: Option<bool>,
+    pub referenced_by: Option<bool>,
+    pub no_infer_types: Option<bool>,
+    pub normalize_unions: Option<bool>,
+    pub collapse_annotated: Option<bool>,
+    /// Whether entries start with an import-and-call snippet.
+    pub usage_snippets: Option<bool>,
+    pub diagnostics_format: Option<DiagnosticsFormat>,
+    pub strict: Option<bool>,
+    pub require_docstrings: Option<DocstringRequirement>,
+    pub coverage: Option<bool>,
+    pub coverage_format: Option<CoverageFormat>,
+    pub cli: Option<bool>,
+    pub routes: Option<bool>,
+    pub check_links: Option<bool>,
+    pub timestamp: Option<bool>,
+    pub timing: Option<bool>,
+    pub jobs: Option<NonZeroUsize>,
+    pub cache: Option<bool>,
+    /// Actions for decorators and docstring tags, added to or replacing
+    /// the default markers.
+    pub markers: BTreeMap<String, MarkerConfig>,
+    /// Extra frontmatter keys of every page.
+    pub frontmatter: BTreeMap<String, String>,
+    /// Settings for single modules, keyed by dotted module path.
+    pub modules: BTreeMap<String, ModuleConfig>,
+}
+
+/// Settings for one module, under `[modules."zenml.client"]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModuleConfig {
+    /// Title of the module's page.
+    pub title: Option<String>,
+    /// Extra frontmatter keys of the module's page, taking precedence over
+    /// the ones for every page.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub frontmatter: BTreeMap<String, String>,
+}
+
+/// A marker as written in the configuration file: `"exclude"`, or a table
+/// with the badge label and an optional note.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MarkerConfig {
+    Action(MarkerKeyword),
+    Badge {
+        badge: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkerKeyword {
+    Exclude,
+}
+
+impl From<&MarkerConfig> for MarkerAction {
+    fn from(marker: &MarkerConfig) -> Self {
+        match marker {
+            MarkerConfig::Action(MarkerKeyword::Exclude) => MarkerAction::Exclude,
+            MarkerConfig::Badge { badge, note } => MarkerAction::Badge(Badge {
+                label: badge.clone(),
+                note: note.clone(),
+            }),
+        }
+    }
+}
+
+impl From<&MarkerAction> for MarkerConfig {
+    fn from(action: &MarkerAction) -> Self {
+        match action {
+            MarkerAction::Exclude => MarkerConfig::Action(MarkerKeyword::Exclude),
+            MarkerAction::Badge(badge) => MarkerConfig::Badge {
+                badge: badge.label.clone(),
+                note: badge.note.clone(),
+            },
+        }
+    }
+}
+
+impl Config {
+    /// Parses the contents of a configuration file.
+    pub fn from_toml(source: &str) -> Result<Config, toml::de::Error> {
+        toml::from_str(source)
+    }
+
+    /// Renders the configuration as a file `from_toml` reads back.
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("a configuration always serializes")
+    }
+
+    /// Applies the settings controlling parsing and rendering on top of
+    /// `options`. The others are for the command-line tool to act on.
+    pub fn apply(&self, options: &mut RenderOptions) {
+        if let Some(package_prefix) = &self.package_prefix {
+            options.module_prefix = package_prefix.clone();
+        }
+        if let Some(special_tag) = self.special_tag {
+            options.special_tag = special_tag;
+        }
+        if let Some(doc_version) = &self.doc_version {
+            options.doc_version = Some(doc_version.clone());
+        }
+        if let Some(format) = self.format {
+            options.format = format;
+        }
+        if let Some(include_private) = self.include_private {
+            options.include_private = include_private;
+        }
+        if let Some(dunders) = self.dunders {
+            options.dunders = dunders;
+        }
+        if let Some(ignore_all) = self.ignore_all {
+            options.respect_all = !ignore_all;
+        }
+        options
+            .skip_decorators
+            .extend(self.skip_decorator.iter().cloned());
+        options
+            .pydantic_bases
+            .extend(self.pydantic_base.iter().cloned());
+        if let Some(show_validators) = self.show_validators {
+            options.show_validators = show_validators;
+        }
+        if let Some(skip_nested_config) = self.skip_nested_config {
+            options.skip_nested_config = skip_nested_config;
+        }
+        if let Some(nested_functions) = self.nested_functions {
+            options.nested_functions = nested_functions;
+        }
+        if let Some(merge_init) = self.merge_init {
+            options.merge_init = merge_init;
+        }
+        options
+            .deprecated_decorators
+            .extend(self.deprecated_decorator.iter().cloned());
+        if let Some(hide_deprecated) = self.hide_deprecated {
+            options.hide_deprecated = hide_deprecated;
+        }
+        if let Some(deprecated_last) = self.deprecated_last {
+            options.deprecated_last = deprecated_last;
+        }
+        if let Some(signature_from_docstring) = self.signature_from_docstring {
+            options.signature_from_docstring = signature_from_docstring;
+        }
+        if let Some(all_module_attributes) = self.all_module_attributes {
+            options.all_module_attributes = all_module_attributes;
+        }
+        if let Some(only) = &self.only {
+            options.only = Some(only.clone());
+        }
+        if let Some(infer_raises) = self.infer_raises {
+            options.infer_raises = infer_raises;
+        }
+        if let Some(expand_aliases) = self.expand_aliases {
+            options.alias_style = expand_aliases;
+        }
+        if let Some(inheritance_diagrams) = self.inheritance_diagrams {
+            options.inheritance_diagrams = inheritance_diagrams;
+        }
+        if let Some(referenced_by) = self.referenced_by {
+            options.referenced_by = referenced_by;
+        }
+        if let Some(no_infer_types) = self.no_infer_types {
+            options.infer_types = !no_infer_types;
+        }
+        if let Some(normalize_unions) = self.normalize_unions {
+            options.types.optional_style = if normalize_unions {
+                OptionalStyle::PipeUnions
+            } else {
+                OptionalStyle::Preserve
+            };
+        }
+        if let Some(collapse_annotated) = self.collapse_annotated {
+            options.types.collapse_annotated = collapse_annotated;
+        }
+        if let Some(usage_snippets) = self.usage_snippets {
+            options.usage_snippets = usage_snippets;
+        }
+        for (name, marker) in &self.markers {
+            options.markers.insert(name.clone(), marker.into());
+        }
+        options.frontmatter.extend(self.frontmatter.clone());
+        if let Some(prettify_titles) = self.prettify_titles {
+            options.prettify_titles = prettify_titles;
+        }
+        for (module, settings) in &self.modules {
+            if let Some(title) = &settings.title {
+                options.module_titles.insert(module.clone(), title.clone());
+            }
+            options
+                .module_frontmatter
+                .entry(module.clone())
+                .or_default()
+                .extend(settings.frontmatter.clone());
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Documentation coverage computed from the model.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::{FunctionDoc, Item, ModuleDoc};
+use crate::options::RenderOptions;
+
+/// How a coverage report is printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageFormat {
+    /// A table with one row per module.
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolKind {
+    Module,
+    Class,
+    Function,
+    Method,
+    Parameter,
+    Return,
+}
+
+impl SymbolKind {
+    pub const ALL: [SymbolKind; 6] = [
+        SymbolKind::Module,
+        SymbolKind::Class,
+        SymbolKind::Function,
+        SymbolKind::Method,
+        SymbolKind::Parameter,
+        SymbolKind::Return,
+    ];
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SymbolKind::Module => "module",
+            SymbolKind::Class => "class",
+            SymbolKind::Function => "function",
+            SymbolKind::Method => "method",
+            SymbolKind::Parameter => "parameter",
+            SymbolKind::Return => "return",
+        })
+    }
+}
+
+/// Whether one symbol is documented.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolCoverage {
+    pub kind: SymbolKind,
+    /// Path within the module, e.g. `Stack.describe` or `Stack.describe(verbose)`.
+    pub symbol: String,
+    /// One-based line of the enclosing definition.
+    pub line: usize,
+    pub documented: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageCounts {
+    pub documented: usize,
+    pub total: usize,
+}
+
+impl CoverageCounts {
+    /// Percentage of documented symbols; 100 when there is nothing to document.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl std::ops::AddAssign for CoverageCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.documented += other.documented;
+        self.total += other.total;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    /// Dotted name of the module the report describes.
+    pub module: String,
+    /// Every counted symbol, in source order.
+    pub symbols: Vec<SymbolCoverage>,
+}
+
+impl CoverageReport {
+    pub fn counts(&self, kind: SymbolKind) -> CoverageCounts {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.kind == kind)
+            .fold(CoverageCounts::default(), |mut counts, symbol| {
+                counts.total += 1;
+                counts.documented += usize::from(symbol.documented);
+                counts
+            })
+    }
+
+    pub fn total(&self) -> CoverageCounts {
+        CoverageCounts {
+            documented: self.symbols.iter().filter(|s| s.documented).count(),
+            total: self.symbols.len(),
+        }
+    }
+
+    pub fn undocumented(&self) -> impl Iterator<Item = &SymbolCoverage> {
+        self.symbols.iter().filter(|symbol| !symbol.documented)
+    }
+}
+
+/// Counts documented and undocumented symbols in `module`.
+///
+/// Parameters count as documented when the docstring's `Args:` section
+/// describes them; `self` and `cls` are not counted. Return values are only
+/// counted for functions annotated with something other than `None`.
+/// Symbols that `options` would not render, such as private members, are
+/// left out.
+pub fn coverage(module: &ModuleDoc, options: &RenderOptions) -> CoverageReport {
+    let mut symbols = vec![SymbolCoverage {
+        kind: SymbolKind::Module,
+        symbol: module.qualified_name.clone(),
+        line: 1,
+        documented: module.docstring.is_some(),
+    }];
+
+    for item in module
+        .items
+        .iter()
+        .filter(|item| options.documents_export(module, item.name()))
+    {
+        match item {
+            Item::Class(class) => {
+                symbols.push(SymbolCoverage {
+                    kind: SymbolKind::Class,
+                    symbol: class.name.clone(),
+                    line: class.line,
+                    documented: class.docstring.is_some(),
+                });
+                for method in class
+                    .methods
+                    .iter()
+                    .filter(|method| options.documents_method(&method.name))
+                {
+                    let path = format!("{}.{}", class.name, method.name);
+                    function_coverage(&mut symbols, method, &path, SymbolKind::Method);
+                }
+            }
+            Item::Function(function) => {
+                function_coverage(&mut symbols, function, &function.name, SymbolKind::Function)
+            }
+        }
+    }
+
+    CoverageReport {
+        module: module.qualified_name.clone(),
+        symbols,
+    }
+}
+
+fn function_coverage(
+    symbols: &mut Vec<SymbolCoverage>,
+    function: &FunctionDoc,
+    path: &str,
+    kind: SymbolKind,
+) {
+    symbols.push(SymbolCoverage {
+        kind,
+        symbol: path.to_string(),
+        line: function.line,
+        documented: function.docstring.is_some(),
+    });
+
+    for parameter in function.arguments(kind == SymbolKind::Method) {
+        symbols.push(SymbolCoverage {
+            kind: SymbolKind::Parameter,
+            symbol: format!("{}({})", path, parameter.name),
+            line: function.line,
+            documented: parameter.description.is_some(),
+        });
+    }
+
+    if function.returns.as_deref().is_some_and(|ret| !is_none(ret)) {
+        symbols.push(SymbolCoverage {
+            kind: SymbolKind::Return,
+            symbol: format!("{}()", path),
+            line: function.line,
+            documented: function.parsed_docstring.returns.is_some(),
+        });
+    }
+}
+
+fn is_none(annotation: &str) -> bool {
+    annotation == "None"
+}
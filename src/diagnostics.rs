@@ -0,0 +1,99 @@
+//! Diagnostics collected while documenting a module.
+
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::paths::normalize_path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// How diagnostics are printed at the end of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticsFormat {
+    /// One line per diagnostic, grouped by file.
+    #[default]
+    Text,
+    /// A JSON array.
+    Json,
+}
+
+/// What kind of problem a [`Diagnostic`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Category {
+    /// An AST node the tool does not know how to render.
+    UnhandledNode,
+    /// A docstring that disagrees with the signature it documents.
+    DocstringMismatch,
+    /// A reference to a name that could not be resolved.
+    UnresolvedReference,
+    /// Two symbols or output files competing for the same name.
+    Collision,
+    /// A `.pyi` stub that disagrees with the implementation it describes.
+    StubMismatch,
+    /// A link in a generated page to a page or anchor that does not exist.
+    BrokenLink,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Category::UnhandledNode => "unhandled-node",
+            Category::DocstringMismatch => "docstring-mismatch",
+            Category::UnresolvedReference => "unresolved-reference",
+            Category::Collision => "collision",
+            Category::StubMismatch => "stub-mismatch",
+            Category::BrokenLink => "broken-link",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub category: Category,
+    /// File the diagnostic refers to.
+    #[serde(serialize_with = "serialize_path")]
+    pub file: PathBuf,
+    /// One-based line number, or 0 when the diagnostic has no location.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {}:{}: {}",
+            self.severity,
+            self.category,
+            normalize_path(&self.file),
+            self.line,
+            self.message
+        )
+    }
+}
+
+pub(crate) fn serialize_path<S: Serializer>(
+    path: &std::path::Path,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&normalize_path(path))
+}
@@ -0,0 +1,282 @@
+//! API changes between two versions of a set of modules.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::docstring::VersionMarker;
+use crate::model::{ClassDoc, FunctionDoc, Item, ModuleDoc, Parameter};
+use crate::options::RenderOptions;
+use crate::project::DefinitionKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+    Deprecated,
+}
+
+/// One difference in the public API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+    /// Full dotted path of the symbol, e.g. `zenml.client.Client.delete`.
+    pub symbol: String,
+    pub symbol_kind: DefinitionKind,
+    /// What changed, for [`ChangeKind::Changed`] and
+    /// [`ChangeKind::Deprecated`].
+    pub detail: Option<String>,
+    /// Version markers from the symbol's docstring: the new version's, or
+    /// the old one's for a removed symbol.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<VersionMarker>,
+}
+
+/// A documented definition.
+enum Definition<'a> {
+    Module(&'a ModuleDoc),
+    Class(&'a ClassDoc),
+    Function(&'a FunctionDoc, DefinitionKind),
+}
+
+impl Definition<'_> {
+    fn kind(&self) -> DefinitionKind {
+        match self {
+            Definition::Module(_) => DefinitionKind::Module,
+            Definition::Class(_) => DefinitionKind::Class,
+            Definition::Function(_, kind) => *kind,
+        }
+    }
+
+    fn versions(&self) -> &[VersionMarker] {
+        match self {
+            Definition::Module(_) => &[],
+            Definition::Class(class) => &class.versions,
+            Definition::Function(function, _) => &function.versions,
+        }
+    }
+
+    fn is_deprecated(&self) -> bool {
+        let docstring = match self {
+            Definition::Module(module) => &module.docstring,
+            Definition::Class(class) if class.deprecated.is_some() => return true,
+            Definition::Class(class) => &class.docstring,
+            Definition::Function(function, _) if function.deprecated.is_some() => return true,
+            Definition::Function(function, _) => &function.docstring,
+        };
+        docstring
+            .as_deref()
+            .is_some_and(|docstring| docstring.contains(".. deprecated::"))
+    }
+}
+
+/// Indexes the definitions `options` documents by dotted path. Modules are
+/// keyed by import path, so a file that moved without changing its import
+/// path is still the same module.
+fn definitions<'a>(
+    modules: &'a [ModuleDoc],
+    options: &RenderOptions,
+) -> BTreeMap<String, Definition<'a>> {
+    let mut definitions = BTreeMap::new();
+    for module in modules {
+        let module_path = module.import_path().to_string();
+        for item in module
+            .items
+            .iter()
+            .filter(|item| options.documents_export(module, item.name()))
+        {
+            match item {
+                Item::Class(class) => {
+                    let class_path = format!("{}.{}", module_path, class.name);
+                    for method in class
+                        .methods
+                        .iter()
+                        .filter(|method| options.documents_method(&method.name))
+                    {
+                        definitions.insert(
+                            format!("{}.{}", class_path, method.name),
+                            Definition::Function(method, DefinitionKind::Method),
+                        );
+                    }
+                    definitions.insert(class_path, Definition::Class(class));
+                }
+                Item::Function(function) => {
+                    definitions.insert(
+                        format!("{}.{}", module_path, function.name),
+                        Definition::Function(function, DefinitionKind::Function),
+                    );
+                }
+            }
+        }
+        definitions.insert(module_path, Definition::Module(module));
+    }
+    definitions
+}
+
+/// Compares the public API of two versions of a package.
+///
+/// Symbols inside an added or removed module or class are not listed
+/// separately. Changes are ordered by symbol path.
+pub fn diff_api(old: &[ModuleDoc], new: &[ModuleDoc], options: &RenderOptions) -> Vec<ApiChange> {
+    let old = definitions(old, options);
+    let new = definitions(new, options);
+    let paths: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    let mut changes = Vec::new();
+    for path in paths {
+        let change = |kind, definition: &Definition, detail| ApiChange {
+            kind,
+            symbol: path.clone(),
+            symbol_kind: definition.kind(),
+            detail,
+            versions: definition.versions().to_vec(),
+        };
+        let parent = path.rsplit_once('.').map(|(parent, _)| parent);
+        match (old.get(path), new.get(path)) {
+            (None, Some(definition)) => {
+                if !parent
+                    .is_some_and(|parent| new.contains_key(parent) && !old.contains_key(parent))
+                {
+                    changes.push(change(ChangeKind::Added, definition, None));
+                }
+            }
+            (Some(definition), None) => {
+                if !parent
+                    .is_some_and(|parent| old.contains_key(parent) && !new.contains_key(parent))
+                {
+                    changes.push(change(ChangeKind::Removed, definition, None));
+                }
+            }
+            (Some(before), Some(after)) => {
+                if before.kind() != after.kind() {
+                    let detail = format!("changed from {} to {}", before.kind(), after.kind());
+                    changes.push(change(ChangeKind::Changed, after, Some(detail)));
+                } else if let (Definition::Function(old, _), Definition::Function(new, _)) =
+                    (before, after)
+                {
+                    for detail in signature_changes(old, new) {
+                        changes.push(change(ChangeKind::Changed, after, Some(detail)));
+                    }
+                }
+                if !before.is_deprecated() && after.is_deprecated() {
+                    changes.push(change(
+                        ChangeKind::Deprecated,
+                        after,
+                        Some("deprecated".to_string()),
+                    ));
+                }
+            }
+            (None, None) => unreachable!("path comes from one of the maps"),
+        }
+    }
+    changes
+}
+
+fn signature_changes(before: &FunctionDoc, after: &FunctionDoc) -> Vec<String> {
+    fn find<'a>(parameters: &'a [Parameter], name: &str) -> Option<&'a Parameter> {
+        parameters.iter().find(|p| p.name == name)
+    }
+
+    let mut details = Vec::new();
+
+    for parameter in &before.parameters {
+        if find(&after.parameters, &parameter.name).is_none() {
+            details.push(format!("parameter `{}` removed", parameter.name));
+        }
+    }
+    for parameter in &after.parameters {
+        let Some(previous) = find(&before.parameters, &parameter.name) else {
+            let required = if parameter.default.is_none() {
+                "required "
+            } else {
+                ""
+            };
+            details.push(format!("{}parameter `{}` added", required, parameter.name));
+            continue;
+        };
+        if previous.annotation != parameter.annotation {
+            details.push(format!(
+                "type of `{}` changed from {} to {}",
+                parameter.name,
+                describe(previous.annotation.as_deref()),
+                describe(parameter.annotation.as_deref())
+            ));
+        }
+        let before_default = previous.default.as_deref().map(comparable_default);
+        let after_default = parameter.default.as_deref().map(comparable_default);
+        match (before_default, after_default) {
+            (Some(_), None) => {
+                details.push(format!("parameter `{}` became required", parameter.name))
+            }
+            (None, Some(_)) => {
+                details.push(format!("parameter `{}` became optional", parameter.name))
+            }
+            (Some(before), Some(after)) if before != after => details.push(format!(
+                "default of `{}` changed from `{}` to `{}`",
+                parameter.name, before, after
+            )),
+            _ => {}
+        }
+    }
+
+    if before.returns != after.returns {
+        details.push(format!(
+            "return type changed from {} to {}",
+            describe(before.returns.as_deref()),
+            describe(after.returns.as_deref())
+        ));
+    }
+    details
+}
+
+fn describe(annotation: Option<&str>) -> String {
+    match annotation {
+        Some(annotation) => format!("`{}`", annotation),
+        None => "unannotated".to_string(),
+    }
+}
+
+/// Defaults are source text; collapse whitespace so that reformatting a
+/// signature does not change them.
+fn comparable_default(default: &str) -> String {
+    default.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Renders changes as a Markdown section for a changelog.
+pub fn render_changelog(changes: &[ApiChange]) -> String {
+    let mut markdown = String::from("## API changes\n\n");
+    if changes.is_empty() {
+        markdown.push_str("No changes to the public API.\n");
+        return markdown;
+    }
+
+    for (kind, heading) in [
+        (ChangeKind::Added, "Added"),
+        (ChangeKind::Removed, "Removed"),
+        (ChangeKind::Changed, "Changed"),
+        (ChangeKind::Deprecated, "Deprecated"),
+    ] {
+        let mut section = changes
+            .iter()
+            .filter(|change| change.kind == kind)
+            .peekable();
+        if section.peek().is_none() {
+            continue;
+        }
+        markdown.push_str(&format!("### {}\n\n", heading));
+        for change in section {
+            match (&change.detail, kind) {
+                (Some(detail), ChangeKind::Changed) => {
+                    markdown.push_str(&format!("- `{}`: {}\n", change.symbol, detail))
+                }
+                _ => {
+                    markdown.push_str(&format!("- `{}` ({})\n", change.symbol, change.symbol_kind))
+                }
+            }
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
@@ -0,0 +1,439 @@
+//! Parsing of Google-style docstrings into their sections.
+
+use serde::Serialize;
+
+/// A docstring split into its summary, free-form description and sections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Docstring {
+    /// The first paragraph.
+    pub summary: String,
+    /// Free text after the summary that is not part of any section.
+    pub description: String,
+    /// Entries of the `Args:` section.
+    pub params: Vec<DocstringEntry>,
+    /// The `Returns:` (or `Yields:`) section.
+    pub returns: Option<DocstringReturns>,
+    /// Entries of the `Raises:` section, keyed by exception name.
+    pub raises: Vec<DocstringEntry>,
+    /// Every other recognized section, such as `Examples:` or `Note:`.
+    pub sections: Vec<DocstringSection>,
+}
+
+/// A `name (type): description` entry from an `Args:` or `Raises:` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocstringEntry {
+    pub name: String,
+    pub type_name: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocstringReturns {
+    pub type_name: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocstringSection {
+    pub title: String,
+    /// Section content, dedented.
+    pub content: String,
+}
+
+/// A `.. versionadded::` or `.. versionchanged::` marker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionMarker {
+    pub kind: VersionKind,
+    pub version: String,
+    /// Text after the version, such as `renamed from X`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionKind {
+    Added,
+    Changed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Params,
+    Returns,
+    Raises,
+    Other,
+}
+
+/// Every title recognized by [`section_kind`].
+const SECTION_TITLES: &[&str] = &[
+    "Args",
+    "Arguments",
+    "Parameters",
+    "Params",
+    "Keyword Args",
+    "Keyword Arguments",
+    "Other Parameters",
+    "Returns",
+    "Return",
+    "Yields",
+    "Yield",
+    "Raises",
+    "Raise",
+    "Attributes",
+    "Example",
+    "Examples",
+    "Note",
+    "Notes",
+    "References",
+    "See Also",
+    "Todo",
+    "Warning",
+    "Warnings",
+    "Warns",
+];
+
+/// Maps a section title to its kind, or `None` if it is not a known title.
+fn section_kind(title: &str) -> Option<SectionKind> {
+    match title {
+        "Args" | "Arguments" | "Parameters" | "Params" | "Keyword Args" | "Keyword Arguments"
+        | "Other Parameters" => Some(SectionKind::Params),
+        "Returns" | "Return" | "Yields" | "Yield" => Some(SectionKind::Returns),
+        "Raises" | "Raise" => Some(SectionKind::Raises),
+        "Attributes" | "Example" | "Examples" | "Note" | "Notes" | "References" | "See Also"
+        | "Todo" | "Warning" | "Warnings" | "Warns" => Some(SectionKind::Other),
+        _ => None,
+    }
+}
+
+/// Returns the section title if `line` is a section header such as `Args:`.
+pub(crate) fn section_header(line: &str) -> Option<&str> {
+    let title = line.trim_end().strip_suffix(':')?;
+    section_kind(title).map(|_| title)
+}
+
+/// Finds lines that look like a section header but are not recognized as
+/// one: a known title without its colon or in the wrong case, or an `Args:`,
+/// `Returns:` or `Raises:` header with text after the colon. Returns
+/// one-based line numbers within the cleaned docstring with a message.
+pub(crate) fn malformed_section_headers(text: &str) -> Vec<(usize, String)> {
+    let cleaned = clean_docstring(text);
+    let lines: Vec<&str> = cleaned.lines().collect();
+    let mut problems = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        if indentation(line) > 0 || section_header(line).is_some() {
+            continue;
+        }
+        let line = line.trim_end();
+        let followed_by_block = lines
+            .get(index + 1)
+            .is_some_and(|next| !next.trim().is_empty() && indentation(next) > 0);
+
+        if let Some((title, rest)) = line.split_once(':') {
+            let entries_inline = !rest.trim().is_empty()
+                && matches!(
+                    section_kind(title),
+                    Some(SectionKind::Params | SectionKind::Returns | SectionKind::Raises)
+                );
+            if entries_inline {
+                problems.push((
+                    index + 1,
+                    format!(
+                        "text after `{}:` is ignored; start the section on the next line",
+                        title
+                    ),
+                ));
+                continue;
+            }
+        }
+
+        let (title, has_colon) = match line.strip_suffix(':') {
+            Some(title) => (title, true),
+            None => (line, false),
+        };
+        if !has_colon && !followed_by_block {
+            continue;
+        }
+        if let Some(known) = SECTION_TITLES
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(title))
+        {
+            let message = if *known == title {
+                format!("section header `{}` is missing a trailing colon", title)
+            } else {
+                format!("section header `{}` should be written `{}:`", line, known)
+            };
+            problems.push((index + 1, message));
+        }
+    }
+    problems
+}
+
+/// Normalizes docstring indentation like Python's `inspect.cleandoc`.
+pub fn clean_docstring(text: &str) -> String {
+    let expanded = text.replace('\t', "        ");
+    let lines: Vec<&str> = expanded.lines().collect();
+    let margin = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or(0);
+
+    let mut cleaned: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                line.trim_start()
+            } else if line.trim().is_empty() {
+                ""
+            } else {
+                line.get(margin..).unwrap_or_else(|| line.trim_start())
+            }
+        })
+        .collect();
+    while cleaned.first().is_some_and(|line| line.trim().is_empty()) {
+        cleaned.remove(0);
+    }
+    while cleaned.last().is_some_and(|line| line.trim().is_empty()) {
+        cleaned.pop();
+    }
+    cleaned
+        .iter()
+        .map(|line| line.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Removes the common indentation of `lines`.
+fn dedent(lines: &[&str]) -> Vec<String> {
+    let margin = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| indentation(line))
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                line.get(margin..)
+                    .unwrap_or_else(|| line.trim_start())
+                    .to_string()
+            }
+        })
+        .collect()
+}
+
+/// Takes the version markers out of a raw docstring: reST directives such
+/// as `.. versionchanged:: 0.45.0: renamed from X`, whose note may continue
+/// on more indented lines, and Google-style `Added in version: 0.40.0`
+/// lines. Returns the markers in version order and the docstring without
+/// them or the blank lines they leave at its end; a docstring without
+/// markers is returned unchanged.
+pub fn extract_version_markers(text: &str) -> (Vec<VersionMarker>, String) {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut markers = Vec::new();
+    let mut kept = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        index += 1;
+        let Some((kind, rest)) = version_marker(line.trim()) else {
+            kept.push(line);
+            continue;
+        };
+        let rest = rest.trim();
+        let version_end = rest
+            .find(|c: char| c.is_whitespace() || c == ':' || c == ',')
+            .unwrap_or(rest.len());
+        let (version, note) = rest.split_at(version_end);
+        let mut note = note.trim_start_matches([':', ',']).trim().to_string();
+        while let Some(next) = lines
+            .get(index)
+            .filter(|next| !next.trim().is_empty() && indentation(next) > indentation(line))
+        {
+            if !note.is_empty() {
+                note.push(' ');
+            }
+            note.push_str(next.trim());
+            index += 1;
+        }
+        markers.push(VersionMarker {
+            kind,
+            version: version.to_string(),
+            note: Some(note).filter(|note| !note.is_empty()),
+        });
+    }
+
+    if markers.is_empty() {
+        return (markers, text.to_string());
+    }
+    while kept.last().is_some_and(|line| line.trim().is_empty()) {
+        kept.pop();
+    }
+    markers.sort_by_cached_key(|marker| version_key(&marker.version));
+    (markers, kept.join("\n"))
+}
+
+fn version_marker(line: &str) -> Option<(VersionKind, &str)> {
+    [
+        (".. versionadded::", VersionKind::Added),
+        (".. versionchanged::", VersionKind::Changed),
+        ("Added in version:", VersionKind::Added),
+        ("Changed in version:", VersionKind::Changed),
+    ]
+    .into_iter()
+    .find_map(|(prefix, kind)| line.strip_prefix(prefix).map(|rest| (kind, rest)))
+}
+
+/// Orders versions by their numeric components, so `0.9` sorts before
+/// `0.10`.
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Parses a raw docstring.
+pub fn parse_docstring(text: &str) -> Docstring {
+    let _span = tracing::trace_span!("docstring").entered();
+
+    let cleaned = clean_docstring(text);
+    let mut docstring = Docstring::default();
+    let mut free_text: Vec<&str> = Vec::new();
+    let mut section: Option<(&str, Vec<&str>)> = None;
+
+    for line in cleaned.lines() {
+        if let Some(title) = section_header(line).filter(|_| indentation(line) == 0) {
+            if let Some((title, content)) = section.take() {
+                add_section(&mut docstring, title, &content);
+            }
+            section = Some((title, Vec::new()));
+            continue;
+        }
+        match &mut section {
+            Some((_, content)) if line.trim().is_empty() || indentation(line) > 0 => {
+                content.push(line)
+            }
+            Some(_) => {
+                let (title, content) = section.take().unwrap();
+                add_section(&mut docstring, title, &content);
+                free_text.push(line);
+            }
+            None => free_text.push(line),
+        }
+    }
+    if let Some((title, content)) = section.take() {
+        add_section(&mut docstring, title, &content);
+    }
+
+    let text = free_text.join("\n");
+    let text = text.trim();
+    let (summary, description) = text.split_once("\n\n").unwrap_or((text, ""));
+    docstring.summary = summary.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+    docstring.description = description.trim().to_string();
+    docstring
+}
+
+fn add_section(docstring: &mut Docstring, title: &str, content: &[&str]) {
+    let lines = dedent(content);
+    match section_kind(title).unwrap_or(SectionKind::Other) {
+        SectionKind::Params => docstring.params.extend(parse_entries(&lines)),
+        SectionKind::Raises => docstring.raises.extend(parse_entries(&lines)),
+        SectionKind::Returns => docstring.returns = Some(parse_returns(&lines)),
+        SectionKind::Other => docstring.sections.push(DocstringSection {
+            title: title.to_string(),
+            content: lines.join("\n").trim().to_string(),
+        }),
+    }
+}
+
+/// Parses `name (type): description` entries; indented lines continue the
+/// previous entry.
+fn parse_entries(lines: &[String]) -> Vec<DocstringEntry> {
+    let mut entries: Vec<DocstringEntry> = Vec::new();
+    for line in lines {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indentation(line) == 0 {
+            if let Some(entry) = parse_entry(line) {
+                entries.push(entry);
+                continue;
+            }
+        }
+        if let Some(entry) = entries.last_mut() {
+            if !entry.description.is_empty() {
+                entry.description.push(' ');
+            }
+            entry.description.push_str(line.trim());
+        }
+    }
+    entries
+}
+
+fn parse_entry(line: &str) -> Option<DocstringEntry> {
+    let (head, description) = line.split_once(':')?;
+    let head = head.trim();
+    let (name, type_name) = match head.split_once('(') {
+        Some((name, rest)) => (
+            name.trim(),
+            Some(rest.strip_suffix(')')?.trim().to_string()),
+        ),
+        None => (head, None),
+    };
+    let bare = name.trim_start_matches('*');
+    let valid = !bare.is_empty()
+        && bare
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        && !bare.starts_with(|c: char| c.is_ascii_digit());
+    valid.then(|| DocstringEntry {
+        name: name.to_string(),
+        type_name,
+        description: description.trim().to_string(),
+    })
+}
+
+fn parse_returns(lines: &[String]) -> DocstringReturns {
+    let text = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    match text.split_once(": ") {
+        Some((type_name, description)) if looks_like_type(type_name) => DocstringReturns {
+            type_name: Some(type_name.to_string()),
+            description: description.trim().to_string(),
+        },
+        _ => DocstringReturns {
+            type_name: None,
+            description: text,
+        },
+    }
+}
+
+/// Whether the text before a colon reads as a type such as `Dict[str, int]`
+/// rather than prose.
+fn looks_like_type(text: &str) -> bool {
+    let compact = text.replace(", ", ",").replace(" | ", "|");
+    !compact.is_empty()
+        && compact
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_.[],|".contains(c))
+}
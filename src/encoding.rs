@@ -0,0 +1,159 @@
+//! Decoding of Python source files that are not plain UTF-8: a leading
+//! byte order mark, a PEP 263 coding declaration, or invalid bytes.
+
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::paths::normalize_path;
+
+/// Reads the Python source file at `path`, decoded with [`decode_source`].
+pub fn read_source(path: &Path) -> io::Result<String> {
+    Ok(decode_source(&fs::read(path)?, path))
+}
+
+/// Decodes the bytes of a Python source file. A UTF-8 byte order mark is
+/// dropped, and a declaration such as `# -*- coding: latin-1 -*-` on one
+/// of the first two lines picks the encoding. Bytes that are not valid in
+/// it are replaced with U+FFFD and an unsupported encoding is read as
+/// UTF-8, with a warning naming `path` either way.
+pub fn decode_source(bytes: &[u8], path: &Path) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let declared = coding_declaration(bytes);
+    let encoding = match declared.as_deref().map(Encoding::named) {
+        Some(Some(encoding)) => encoding,
+        Some(None) => {
+            tracing::warn!(
+                "{}: unsupported encoding `{}`, reading it as UTF-8",
+                normalize_path(path),
+                declared.unwrap_or_default()
+            );
+            Encoding::Utf8
+        }
+        None => Encoding::Utf8,
+    };
+    let (source, invalid) = encoding.decode(bytes);
+    if invalid {
+        tracing::warn!(
+            "{}: bytes that are not valid {} replaced with U+FFFD",
+            normalize_path(path),
+            encoding.name()
+        );
+    }
+    source
+}
+
+/// The encoding named by a PEP 263 declaration on the first line, or on
+/// the second when the first is a comment or blank.
+pub fn coding_declaration(bytes: &[u8]) -> Option<String> {
+    let mut lines = bytes.split(|byte| *byte == b'\n');
+    let first = lines.next()?;
+    if let Some(encoding) = declared_on(first) {
+        return Some(encoding);
+    }
+    let first = trim_start(first);
+    if !first.is_empty() && first[0] != b'#' && first != b"\r" {
+        return None;
+    }
+    declared_on(lines.next()?)
+}
+
+/// The encoding named by `coding:` or `coding=` in a comment line.
+fn declared_on(line: &[u8]) -> Option<String> {
+    let line = trim_start(line);
+    if line.first() != Some(&b'#') {
+        return None;
+    }
+    let line = String::from_utf8_lossy(line);
+    let start = line.find("coding")? + "coding".len();
+    let rest = line[start..].strip_prefix([':', '='])?;
+    let name: String = rest
+        .trim_start_matches([' ', '\t'])
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || "-_.".contains(*c))
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+fn trim_start(line: &[u8]) -> &[u8] {
+    let start = line
+        .iter()
+        .position(|byte| !matches!(byte, b' ' | b'\t' | b'\x0C'))
+        .unwrap_or(line.len());
+    &line[start..]
+}
+
+/// The source encodings understood besides UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Utf8,
+    Latin1,
+    Ascii,
+    Windows1252,
+}
+
+impl Encoding {
+    /// The encoding called `name` in a declaration, ignoring case and
+    /// spelling variants as Python does.
+    fn named(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        match name.as_str() {
+            "utf-8" | "utf8" | "utf-8-sig" => Some(Encoding::Utf8),
+            "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" | "l1" => Some(Encoding::Latin1),
+            "ascii" | "us-ascii" => Some(Encoding::Ascii),
+            "cp1252" | "windows-1252" => Some(Encoding::Windows1252),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Latin1 => "Latin-1",
+            Encoding::Ascii => "ASCII",
+            Encoding::Windows1252 => "Windows-1252",
+        }
+    }
+
+    /// The decoded text, and whether any bytes were invalid and replaced
+    /// with U+FFFD.
+    fn decode(self, bytes: &[u8]) -> (String, bool) {
+        let decoded: String = match self {
+            Encoding::Utf8 => {
+                return match String::from_utf8_lossy(bytes) {
+                    Cow::Borrowed(source) => (source.to_string(), false),
+                    Cow::Owned(source) => (source, true),
+                }
+            }
+            Encoding::Latin1 => bytes.iter().map(|&byte| char::from(byte)).collect(),
+            Encoding::Ascii => bytes
+                .iter()
+                .map(|&byte| match byte {
+                    0..=0x7F => char::from(byte),
+                    _ => char::REPLACEMENT_CHARACTER,
+                })
+                .collect(),
+            Encoding::Windows1252 => bytes.iter().map(|&byte| windows_1252(byte)).collect(),
+        };
+        // None of these encodings has U+FFFD of its own.
+        let invalid = decoded.contains(char::REPLACEMENT_CHARACTER);
+        (decoded, invalid)
+    }
+}
+
+/// The character of a Windows-1252 byte. It differs from Latin-1 only in
+/// 0x80 to 0x9F, five of which are unassigned.
+fn windows_1252(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{FFFD}',
+        '\u{017D}', '\u{FFFD}', '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}',
+        '\u{2022}', '\u{2013}', '\u{2014}', '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}',
+        '\u{0153}', '\u{FFFD}', '\u{017E}', '\u{0178}',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[usize::from(byte - 0x80)],
+        _ => char::from(byte),
+    }
+}
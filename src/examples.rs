@@ -0,0 +1,456 @@
+//! Usage examples taken from test files.
+//!
+//! A short test calling a documented function is often the best example of
+//! how to use it. Calls are matched through the test module's imports, so
+//! `store.get(...)` only counts for `zenml.store.Store.get` when `store` was
+//! built from `Store` imported out of `zenml.store`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustpython_parser::ast::Ranged;
+use rustpython_parser::{ast, Parse, ParseError};
+
+use crate::encoding::read_source;
+use crate::model::{FunctionDoc, Item, ModuleDoc};
+use crate::paths::normalize_path;
+
+/// A test function used as an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// Source of the test, dedented and without its decorators.
+    pub code: String,
+    /// Test file the example was taken from.
+    pub file: PathBuf,
+}
+
+/// The shortest test exercising each symbol, keyed by dotted path.
+#[derive(Debug, Clone, Default)]
+pub struct Examples {
+    by_symbol: BTreeMap<String, Example>,
+}
+
+impl Examples {
+    /// Collects examples from every `test_*.py` and `*_test.py` file under
+    /// `dir`. Files that cannot be read or parsed are skipped with a warning.
+    pub fn collect(dir: &Path) -> Examples {
+        let _span = tracing::debug_span!("examples", dir = %normalize_path(dir)).entered();
+
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                tracing::warn!("failed to read {}", dir.display());
+                continue;
+            };
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    pending.push(path);
+                } else if is_test_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+
+        let mut examples = Examples::default();
+        for file in files {
+            let result = read_source(&file)
+                .map_err(|err| err.to_string())
+                .and_then(|source| {
+                    examples
+                        .add_test_module(&source, &file)
+                        .map_err(|err| err.to_string())
+                });
+            if let Err(err) = result {
+                tracing::warn!("failed to read examples from {}: {}", file.display(), err);
+            }
+        }
+        examples
+    }
+
+    /// Adds the tests of one test module. Only module-level `test*`
+    /// functions without parameters are self-contained enough to be shown;
+    /// anything relying on fixtures is skipped. On ties the test seen first
+    /// wins.
+    pub fn add_test_module(&mut self, source: &str, path: &Path) -> Result<(), ParseError> {
+        let suite = ast::Suite::parse(source, &path.to_string_lossy())?;
+
+        let mut module_calls = Calls::default();
+        for stmt in &suite {
+            module_calls.bind(stmt);
+        }
+        for stmt in &suite {
+            let ast::Stmt::FunctionDef(func_def) = stmt else {
+                continue;
+            };
+            if !func_def.name.starts_with("test") || !takes_no_arguments(&func_def.args) {
+                continue;
+            }
+
+            let mut calls = Calls {
+                bindings: module_calls.bindings.clone(),
+                called: BTreeSet::new(),
+            };
+            calls.stmts(&func_def.body);
+
+            let code = dedented_source(source, func_def);
+            let length = code.lines().count();
+            for symbol in calls.called {
+                let shorter = self
+                    .by_symbol
+                    .get(&symbol)
+                    .is_none_or(|existing| length < existing.code.lines().count());
+                if shorter {
+                    self.by_symbol.insert(
+                        symbol,
+                        Example {
+                            code: code.clone(),
+                            file: path.to_path_buf(),
+                        },
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the example for a dotted symbol path such as
+    /// `zenml.client.Client.delete`.
+    pub fn get(&self, symbol: &str) -> Option<&Example> {
+        self.by_symbol.get(symbol)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_symbol.is_empty()
+    }
+}
+
+/// Attaches examples to the functions and methods of `module`, including
+/// re-exported ones, whose docstring has no `Example:` or `Examples:`
+/// section of its own.
+pub fn attach_examples(module: &mut ModuleDoc, examples: &Examples) {
+    let module_path = module.import_path().to_string();
+    for item in &mut module.items {
+        let paths = [format!("{}.{}", module_path, item.name())];
+        attach_to_item(item, &paths, examples);
+    }
+    for reexport in &mut module.reexports {
+        // Tests may import the definition from either module.
+        let paths = [
+            format!("{}.{}", module_path, reexport.item.name()),
+            format!("{}.{}", reexport.module, reexport.original_name),
+        ];
+        attach_to_item(&mut reexport.item, &paths, examples);
+    }
+}
+
+/// `paths` are the dotted paths the item can be imported by.
+fn attach_to_item(item: &mut Item, paths: &[String], examples: &Examples) {
+    match item {
+        Item::Class(class) => {
+            for method in &mut class.methods {
+                let paths: Vec<String> = paths
+                    .iter()
+                    .map(|path| format!("{}.{}", path, method.name))
+                    .collect();
+                attach_to_function(method, &paths, examples);
+            }
+        }
+        Item::Function(function) => attach_to_function(function, paths, examples),
+    }
+}
+
+fn attach_to_function(function: &mut FunctionDoc, paths: &[String], examples: &Examples) {
+    let documented = function
+        .parsed_docstring
+        .sections
+        .iter()
+        .any(|section| section.title == "Example" || section.title == "Examples");
+    if documented {
+        return;
+    }
+    function.example = paths
+        .iter()
+        .find_map(|path| examples.get(path))
+        .map(|example| example.code.clone());
+}
+
+fn is_test_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "py")
+        && path.file_stem().is_some_and(|stem| {
+            let stem = stem.to_string_lossy();
+            stem.starts_with("test_") || stem.ends_with("_test")
+        })
+}
+
+fn takes_no_arguments(args: &ast::Arguments) -> bool {
+    args.posonlyargs.is_empty()
+        && args.args.is_empty()
+        && args.kwonlyargs.is_empty()
+        && args.vararg.is_none()
+        && args.kwarg.is_none()
+}
+
+/// Returns the source of a function from its `def` line, which leaves its
+/// decorators out, dedented by the indentation of that line.
+fn dedented_source(source: &str, func_def: &ast::StmtFunctionDef) -> String {
+    let start = usize::from(func_def.start());
+    let end = usize::from(func_def.end());
+    let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+    let indent = start - line_start;
+
+    let mut code = String::new();
+    for line in source[line_start..end].lines() {
+        let strip = line
+            .char_indices()
+            .take_while(|(index, c)| *index < indent && c.is_whitespace())
+            .count();
+        code.push_str(line[strip..].trim_end());
+        code.push('\n');
+    }
+    code
+}
+
+/// Names bound to documented symbols in a test, and the symbols it calls.
+#[derive(Default)]
+struct Calls {
+    /// Local name to the dotted path it refers to. Instances are bound to
+    /// the path of their class.
+    bindings: BTreeMap<String, String>,
+    called: BTreeSet<String>,
+}
+
+impl Calls {
+    /// Records the names bound by an absolute import. Relative imports
+    /// cannot be resolved against the documented package and are ignored.
+    /// A local `def` or `class` shadows whatever its name referred to.
+    fn bind(&mut self, stmt: &ast::Stmt) {
+        match stmt {
+            ast::Stmt::FunctionDef(ast::StmtFunctionDef { name, .. })
+            | ast::Stmt::AsyncFunctionDef(ast::StmtAsyncFunctionDef { name, .. })
+            | ast::Stmt::ClassDef(ast::StmtClassDef { name, .. }) => {
+                self.bindings.remove(name.as_str());
+            }
+            ast::Stmt::Import(import) => {
+                for alias in &import.names {
+                    let (local_name, path) = match &alias.asname {
+                        Some(asname) => (asname.to_string(), alias.name.to_string()),
+                        None => {
+                            let top = alias.name.split('.').next().unwrap_or_default();
+                            (top.to_string(), top.to_string())
+                        }
+                    };
+                    self.bindings.insert(local_name, path);
+                }
+            }
+            ast::Stmt::ImportFrom(import) => {
+                let level = import.level.as_ref().map_or(0, |level| level.to_usize());
+                let Some(module) = import.module.as_ref().filter(|_| level == 0) else {
+                    return;
+                };
+                for alias in &import.names {
+                    let local_name = alias.asname.as_ref().unwrap_or(&alias.name);
+                    self.bindings
+                        .insert(local_name.to_string(), format!("{}.{}", module, alias.name));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves a name, attribute chain or call result to a dotted path. A
+    /// call resolves to its callee, so `Store()` stands for a `Store`.
+    fn resolve(&self, expr: &ast::Expr) -> Option<String> {
+        match expr {
+            ast::Expr::Name(name) => self.bindings.get(name.id.as_str()).cloned(),
+            ast::Expr::Attribute(attribute) => self
+                .resolve(&attribute.value)
+                .map(|path| format!("{}.{}", path, attribute.attr)),
+            ast::Expr::Call(call) => self.resolve(&call.func),
+            _ => None,
+        }
+    }
+
+    /// Binds `target = value`; rebinding a name to anything unresolvable
+    /// forgets what it referred to.
+    fn assign(&mut self, target: &ast::Expr, value: Option<&ast::Expr>) {
+        let ast::Expr::Name(name) = target else {
+            return;
+        };
+        match value.and_then(|value| self.resolve(value)) {
+            Some(path) => self.bindings.insert(name.id.to_string(), path),
+            None => self.bindings.remove(name.id.as_str()),
+        };
+    }
+
+    fn stmts(&mut self, body: &[ast::Stmt]) {
+        for stmt in body {
+            self.stmt(stmt);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt) {
+        match stmt {
+            ast::Stmt::Import(_)
+            | ast::Stmt::ImportFrom(_)
+            | ast::Stmt::FunctionDef(_)
+            | ast::Stmt::AsyncFunctionDef(_)
+            | ast::Stmt::ClassDef(_) => self.bind(stmt),
+            ast::Stmt::Expr(stmt) => self.expr(&stmt.value),
+            ast::Stmt::Assign(stmt) => {
+                self.expr(&stmt.value);
+                for target in &stmt.targets {
+                    self.assign(target, Some(&stmt.value));
+                }
+            }
+            ast::Stmt::AnnAssign(stmt) => {
+                if let Some(value) = &stmt.value {
+                    self.expr(value);
+                }
+                self.assign(&stmt.target, stmt.value.as_deref());
+            }
+            ast::Stmt::AugAssign(stmt) => self.expr(&stmt.value),
+            ast::Stmt::Return(stmt) => {
+                if let Some(value) = &stmt.value {
+                    self.expr(value);
+                }
+            }
+            ast::Stmt::Assert(stmt) => {
+                self.expr(&stmt.test);
+                if let Some(msg) = &stmt.msg {
+                    self.expr(msg);
+                }
+            }
+            ast::Stmt::Raise(stmt) => {
+                if let Some(exc) = &stmt.exc {
+                    self.expr(exc);
+                }
+            }
+            ast::Stmt::If(stmt) => {
+                self.expr(&stmt.test);
+                self.stmts(&stmt.body);
+                self.stmts(&stmt.orelse);
+            }
+            ast::Stmt::While(stmt) => {
+                self.expr(&stmt.test);
+                self.stmts(&stmt.body);
+                self.stmts(&stmt.orelse);
+            }
+            ast::Stmt::For(stmt) => {
+                self.expr(&stmt.iter);
+                self.stmts(&stmt.body);
+                self.stmts(&stmt.orelse);
+            }
+            ast::Stmt::With(stmt) => {
+                for item in &stmt.items {
+                    self.expr(&item.context_expr);
+                    if let Some(target) = &item.optional_vars {
+                        self.assign(target, Some(&item.context_expr));
+                    }
+                }
+                self.stmts(&stmt.body);
+            }
+            ast::Stmt::Try(ast::StmtTry {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                ..
+            })
+            | ast::Stmt::TryStar(ast::StmtTryStar {
+                body,
+                handlers,
+                orelse,
+                finalbody,
+                ..
+            }) => {
+                self.stmts(body);
+                for handler in handlers {
+                    let ast::ExceptHandler::ExceptHandler(handler) = handler;
+                    self.stmts(&handler.body);
+                }
+                self.stmts(orelse);
+                self.stmts(finalbody);
+            }
+            ast::Stmt::Match(stmt) => {
+                self.expr(&stmt.subject);
+                for case in &stmt.cases {
+                    self.stmts(&case.body);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn exprs<'e>(&mut self, exprs: impl IntoIterator<Item = &'e ast::Expr>) {
+        for expr in exprs {
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &ast::Expr) {
+        match expr {
+            ast::Expr::Call(call) => {
+                if let Some(path) = self.resolve(&call.func) {
+                    self.called.insert(path);
+                }
+                self.expr(&call.func);
+                self.exprs(&call.args);
+                self.exprs(call.keywords.iter().map(|keyword| &keyword.value));
+            }
+            ast::Expr::Attribute(expr) => self.expr(&expr.value),
+            ast::Expr::Subscript(expr) => {
+                self.expr(&expr.value);
+                self.expr(&expr.slice);
+            }
+            ast::Expr::BoolOp(expr) => self.exprs(&expr.values),
+            ast::Expr::NamedExpr(expr) => {
+                self.expr(&expr.value);
+                self.assign(&expr.target, Some(&expr.value));
+            }
+            ast::Expr::BinOp(expr) => {
+                self.expr(&expr.left);
+                self.expr(&expr.right);
+            }
+            ast::Expr::UnaryOp(expr) => self.expr(&expr.operand),
+            ast::Expr::Compare(expr) => {
+                self.expr(&expr.left);
+                self.exprs(&expr.comparators);
+            }
+            ast::Expr::IfExp(expr) => {
+                self.expr(&expr.test);
+                self.expr(&expr.body);
+                self.expr(&expr.orelse);
+            }
+            ast::Expr::Await(expr) => self.expr(&expr.value),
+            ast::Expr::Starred(expr) => self.expr(&expr.value),
+            ast::Expr::List(expr) => self.exprs(&expr.elts),
+            ast::Expr::Tuple(expr) => self.exprs(&expr.elts),
+            ast::Expr::Set(expr) => self.exprs(&expr.elts),
+            ast::Expr::Dict(expr) => {
+                self.exprs(expr.keys.iter().flatten());
+                self.exprs(&expr.values);
+            }
+            ast::Expr::JoinedStr(expr) => self.exprs(&expr.values),
+            ast::Expr::FormattedValue(expr) => self.expr(&expr.value),
+            ast::Expr::ListComp(ast::ExprListComp {
+                elt, generators, ..
+            })
+            | ast::Expr::SetComp(ast::ExprSetComp {
+                elt, generators, ..
+            })
+            | ast::Expr::GeneratorExp(ast::ExprGeneratorExp {
+                elt, generators, ..
+            }) => {
+                for generator in generators {
+                    self.expr(&generator.iter);
+                    self.exprs(&generator.ifs);
+                }
+                self.expr(elt);
+            }
+            _ => {}
+        }
+    }
+}
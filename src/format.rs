@@ -0,0 +1,77 @@
+//! Output formats: the markup written around the documented content, for
+//! the docs generator the pages are built with.
+
+use serde::{Deserialize, Serialize};
+
+/// The flavour of Markdown pages are written in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// MDX using Mintlify components such as `<Accordion>`.
+    #[default]
+    Mdx,
+    /// Markdown without components, for MkDocs and similar generators.
+    /// Collapsible blocks are HTML `<details>` elements.
+    Markdown,
+}
+
+impl Format {
+    /// Extension of the generated files, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Mdx => "mdx",
+            Format::Markdown => "md",
+        }
+    }
+
+    /// A block collapsed under `title`. Code spans in the title are kept.
+    pub(crate) fn collapsible(self, title: &str, body: &str) -> String {
+        match self {
+            Format::Mdx => format!(
+                "<Accordion\n  title=\"{}\"\n>\n{}</Accordion>\n\n",
+                title, body
+            ),
+            Format::Markdown => format!(
+                "<details>\n<summary>{}</summary>\n\n{}\n</details>\n\n",
+                html_code_spans(title),
+                body
+            ),
+        }
+    }
+
+    /// A note admonition.
+    pub(crate) fn note(self, text: &str) -> String {
+        match self {
+            Format::Mdx => format!("<Note>\n{}\n</Note>\n\n", text),
+            Format::Markdown => {
+                let quoted: Vec<String> = text.lines().map(|line| format!("> {}", line)).collect();
+                format!("> **Note**\n>\n{}\n\n", quoted.join("\n"))
+            }
+        }
+    }
+
+    /// A warning admonition.
+    pub(crate) fn warning(self, text: &str) -> String {
+        match self {
+            Format::Mdx => format!("<Warning>\n{}\n</Warning>\n\n", text),
+            Format::Markdown => {
+                let quoted: Vec<String> = text.lines().map(|line| format!("> {}", line)).collect();
+                format!("> **Warning**\n>\n{}\n\n", quoted.join("\n"))
+            }
+        }
+    }
+}
+
+/// Rewrites the `code` spans of Markdown text as `<code>` elements, which
+/// HTML blocks such as `<summary>` need.
+fn html_code_spans(text: &str) -> String {
+    let mut html = String::new();
+    for (index, part) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", part));
+        } else {
+            html.push_str(part);
+        }
+    }
+    html
+}
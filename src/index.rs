@@ -0,0 +1,163 @@
+//! Landing pages of package directories, listing the modules and
+//! sub-packages in each with links to their pages.
+//!
+//! A directory whose `__init__.py` has a page gets the listing at the end
+//! of that page; any other directory gets a page of its own.
+
+use std::collections::BTreeSet;
+
+use crate::docstring::clean_docstring;
+use crate::model::ModuleDoc;
+use crate::options::RenderOptions;
+use crate::project::{relative_page, Project};
+use crate::render::{table_cell, yaml_scalar};
+
+/// The directories of the module pages of `project` and all their parents,
+/// with `/` separators. The output directory itself is `""`.
+pub fn package_directories(project: &Project) -> BTreeSet<String> {
+    let mut directories = BTreeSet::from([String::new()]);
+    for module in project.modules() {
+        let mut page = module.page.as_str();
+        while let Some((directory, _)) = page.rsplit_once('/') {
+            directories.insert(directory.to_string());
+            page = directory;
+        }
+    }
+    directories
+}
+
+/// Page of the landing page of `directory`, without the file extension.
+pub fn index_page(directory: &str, index_name: &str) -> String {
+    if directory.is_empty() {
+        index_name.to_string()
+    } else {
+        format!("{}/{}", directory, index_name)
+    }
+}
+
+/// Tables of the modules directly in `directory` and of its sub-packages,
+/// each with the first sentence of its docstring, linked from the landing
+/// page. Empty when there is nothing to list.
+pub fn package_listing(
+    project: &Project,
+    directory: &str,
+    index_name: &str,
+    options: &RenderOptions,
+) -> String {
+    let strings = &options.strings;
+    let from = index_page(directory, index_name);
+    let header = format!(
+        "| {} | {} |\n| --- | --- |\n",
+        strings.name, strings.description
+    );
+
+    let mut modules = String::new();
+    for module in project.modules() {
+        if parent(&module.page) != directory || module.page == from {
+            continue;
+        }
+        modules.push_str(&format!(
+            "| [`{}`]({}) | {} |\n",
+            module.name,
+            relative_page(&from, &module.page),
+            first_sentence(module.docstring.as_deref())
+        ));
+    }
+
+    let mut packages = String::new();
+    for subdirectory in package_directories(project) {
+        if subdirectory.is_empty() || parent(&subdirectory) != directory {
+            continue;
+        }
+        let page = index_page(&subdirectory, index_name);
+        let docstring = project
+            .modules()
+            .iter()
+            .find(|module| module.page == page)
+            .and_then(|module| module.docstring.as_deref());
+        packages.push_str(&format!(
+            "| [`{}`]({}) | {} |\n",
+            name(&subdirectory),
+            relative_page(&from, &page),
+            first_sentence(docstring)
+        ));
+    }
+
+    let mut listing = String::new();
+    for (heading, rows) in [
+        (&strings.modules, modules),
+        (&strings.subpackages, packages),
+    ] {
+        if !rows.is_empty() {
+            listing.push_str(&format!("**{}:**\n\n{}{}\n", heading, header, rows));
+        }
+    }
+    listing
+}
+
+/// A landing page of its own for `directory`, which has no `__init__.py`
+/// page to carry the listing.
+pub fn render_package_index(
+    project: &Project,
+    directory: &str,
+    index_name: &str,
+    options: &RenderOptions,
+) -> String {
+    let package = package_name(project, directory);
+    let id = match options.page_id {
+        true => format!("id: {}\n", yaml_scalar(index_name)),
+        false => String::new(),
+    };
+    format!(
+        "---\ntitle: {}\n{}---\n\n## `{}`\n\n{}",
+        yaml_scalar(&package),
+        id,
+        package,
+        package_listing(project, directory, index_name, options)
+    )
+}
+
+/// Dotted name of the package in `directory`, from the qualified name of a
+/// module under it, e.g. `zenml.cli` from `zenml.cli.stack` on the page
+/// `cli/stack`. The directory's name when no module tells.
+fn package_name(project: &Project, directory: &str) -> String {
+    let prefix = format!("{}/", directory);
+    project
+        .modules()
+        .iter()
+        .find_map(|module: &ModuleDoc| {
+            let below = if directory.is_empty() {
+                module.page.as_str()
+            } else {
+                module.page.strip_prefix(&prefix)?
+            };
+            let mut segments: Vec<&str> = module.qualified_name.split('.').collect();
+            let depth = below.split('/').count();
+            if depth >= segments.len() {
+                return None;
+            }
+            segments.truncate(segments.len() - depth);
+            Some(segments.join("."))
+        })
+        .unwrap_or_else(|| name(directory).to_string())
+}
+
+/// The directory a page or directory is in; `""` for the output directory.
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+/// The last segment of a page or directory.
+fn name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The first sentence of a docstring's summary paragraph, on one line.
+fn first_sentence(docstring: Option<&str>) -> String {
+    let cleaned = docstring.map(clean_docstring).unwrap_or_default();
+    let summary = table_cell(cleaned.split("\n\n").next().unwrap_or(""));
+    match summary.find(". ") {
+        Some(end) => summary[..=end].to_string(),
+        None => summary,
+    }
+}
@@ -0,0 +1,89 @@
+//! Generate MDX API reference pages from Python source files.
+//!
+//! Documentation is produced in two stages: [`parse_module`] extracts a
+//! [`ModuleDoc`] from Python source, and [`render_module`] turns it into a
+//! page. Both are driven by the same [`RenderOptions`].
+//!
+//! Output is deterministic: the same source and options always produce the
+//! same bytes. The model keeps source order or uses ordered collections,
+//! paths are rendered with `/` separators, and timestamps are only written
+//! when [`RenderOptions::timestamp`] is set.
+
+pub mod cache;
+pub mod cli;
+pub mod config;
+pub mod coverage;
+pub mod diagnostics;
+pub mod diff;
+pub mod docstring;
+pub mod encoding;
+pub mod examples;
+pub mod format;
+pub mod index;
+pub mod links;
+pub mod lint;
+pub mod locale;
+pub mod model;
+pub mod nav;
+pub mod notebook;
+pub mod options;
+mod parse;
+pub mod paths;
+pub mod project;
+pub mod reexports;
+mod render;
+pub mod routes;
+mod source;
+pub mod stale;
+pub mod stubs;
+pub mod template;
+pub mod timing;
+mod types;
+pub mod version;
+
+pub use cache::{content_hash, Cache, CACHE_FILE};
+pub use cli::{extract_cli, render_cli_page, CliCommand, CliParameter};
+pub use config::{Config, MarkerConfig, ModuleConfig};
+pub use coverage::{
+    coverage, CoverageCounts, CoverageFormat, CoverageReport, SymbolCoverage, SymbolKind,
+};
+pub use diagnostics::{Category, Diagnostic, DiagnosticsFormat, Severity};
+pub use diff::{diff_api, render_changelog, ApiChange, ChangeKind};
+pub use encoding::{coding_declaration, decode_source, read_source};
+pub use examples::{attach_examples, Example, Examples};
+pub use format::Format;
+pub use index::{index_page, package_directories, package_listing, render_package_index};
+pub use links::check_links;
+pub use lint::{lint, missing_docstrings, DocstringRequirement, Finding, Rule};
+pub use locale::Strings;
+pub use model::{
+    decorator_name, method_kind, AttributeDoc, Badge, ClassDoc, DataClassDoc, DataClassKind,
+    EnumMember, FieldDoc, FunctionDoc, Import, Item, MethodKind, ModuleDoc, Parameter,
+    ParameterKind, PropertyDoc, Prose, Reexport, TypeAlias, TypeParameter, TypedDictDoc,
+    TypedDictKey,
+};
+pub use nav::{render_nav, NavFormat, NavPage};
+pub use notebook::{is_notebook, Notebook};
+pub use options::{
+    is_private, AliasStyle, Dunders, MarkerAction, NamePattern, NestedFunctions, OptionalStyle,
+    RenderOptions, RenderOptionsBuilder, TypeRenderOptions,
+};
+pub use parse::parse_module;
+pub use paths::{
+    discover_python_files, dotted_module_name, expand_glob, glob_base, glob_matches, is_glob,
+    normalize_path, qualified_name,
+};
+pub use project::{DefinitionKind, Project, Symbol};
+pub use reexports::inline_private_reexports;
+pub use render::render_module;
+pub use routes::{
+    extract_routes, render_routes_page, Endpoint, EndpointParameter, ParameterLocation,
+};
+pub use stale::{stale_pages, unified_diff, StalePage, Staleness};
+pub use stubs::{
+    merge_adjacent_stub, merge_stub, merge_stub_signatures, stub_in_dir, StubPreference,
+};
+pub use template::OutputTemplate;
+pub use timing::{TimingLayer, Timings};
+pub use types::render_annotation;
+pub use version::pyproject_version;
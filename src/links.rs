@@ -0,0 +1,167 @@
+//! Checking the links between generated pages.
+//!
+//! Every `id="..."` attribute defines an anchor, and every Markdown link
+//! whose target is relative must point at a page and anchor that exist.
+//! External links (`https://...`, `mailto:`) and site-rooted links
+//! (`/integrations/...`) lead outside the generated output and are not
+//! checked.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::diagnostics::{Category, Diagnostic, Severity};
+use crate::paths::normalize_path;
+
+/// A Markdown link found in a page.
+struct Link<'a> {
+    line: usize,
+    text: &'a str,
+    target: &'a str,
+}
+
+/// Checks the relative links in `pages`, given as output file and content.
+/// Links to pages that were not just generated are checked against the file
+/// on disk, if there is one. Returns a broken-link diagnostic for every link
+/// whose page or anchor does not exist, in page and line order.
+pub fn check_links(pages: &[(PathBuf, String)]) -> Vec<Diagnostic> {
+    let _span = tracing::info_span!("check-links").entered();
+
+    let mut anchors: BTreeMap<PathBuf, Option<BTreeSet<String>>> = pages
+        .iter()
+        .map(|(path, content)| (lexically_normal(path), Some(anchors_in(content))))
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    for (path, content) in pages {
+        for link in links_in(content) {
+            if is_external(link.target) {
+                continue;
+            }
+            let (page, anchor) = link.target.split_once('#').unwrap_or((link.target, ""));
+            let target_path = if page.is_empty() {
+                lexically_normal(path)
+            } else {
+                let mut target = path.parent().unwrap_or(Path::new("")).join(page);
+                if target.extension().is_none() {
+                    target.set_extension(path.extension().unwrap_or_default());
+                }
+                lexically_normal(&target)
+            };
+            let target_anchors = anchors.entry(target_path.clone()).or_insert_with(|| {
+                fs::read_to_string(&target_path)
+                    .ok()
+                    .map(|content| anchors_in(&content))
+            });
+
+            let problem = match target_anchors {
+                None => format!("page `{}` does not exist", normalize_path(&target_path)),
+                Some(defined) if !anchor.is_empty() && !defined.contains(anchor) => {
+                    format!("anchor `#{}` does not exist", anchor)
+                }
+                Some(_) => continue,
+            };
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                category: Category::BrokenLink,
+                file: path.clone(),
+                line: link.line,
+                message: format!(
+                    "link [{}]({}) is broken: {}",
+                    link.text, link.target, problem
+                ),
+            });
+        }
+    }
+    diagnostics
+}
+
+fn is_external(target: &str) -> bool {
+    target.is_empty()
+        || target.starts_with('/')
+        || target.contains("://")
+        || target.starts_with("mailto:")
+}
+
+/// Resolves `.` and `..` components without touching the file system.
+fn lexically_normal(path: &Path) -> PathBuf {
+    let mut normal = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normal.file_name().is_some() => {
+                normal.pop();
+            }
+            component => normal.push(component),
+        }
+    }
+    normal
+}
+
+/// Collects the values of `id="..."` attributes.
+fn anchors_in(content: &str) -> BTreeSet<String> {
+    let mut anchors = BTreeSet::new();
+    let mut rest = content;
+    while let Some(start) = rest.find(" id=\"") {
+        rest = &rest[start + " id=\"".len()..];
+        if let Some(end) = rest.find('"') {
+            anchors.insert(rest[..end].to_string());
+            rest = &rest[end..];
+        }
+    }
+    anchors
+}
+
+/// Finds the `[text](target)` links outside fenced code blocks.
+fn links_in(content: &str) -> Vec<Link<'_>> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut offset = 0;
+        while let Some(found) = line[offset..].find("](") {
+            let close = offset + found;
+            offset = close + 2;
+            let Some(open) = opening_bracket(&line[..close]) else {
+                continue;
+            };
+            let Some(end) = line[offset..].find(')') else {
+                break;
+            };
+            // Drop a link title: `[text](target "title")`.
+            let target = line[offset..offset + end]
+                .split_whitespace()
+                .next()
+                .unwrap_or("");
+            links.push(Link {
+                line: index + 1,
+                text: &line[open + 1..close],
+                target,
+            });
+            offset += end + 1;
+        }
+    }
+    links
+}
+
+/// Returns the position of the `[` matching a `]` at the end of `before`,
+/// allowing balanced brackets in the link text, e.g. `[List[int]](...)`.
+fn opening_bracket(before: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, c) in before.char_indices().rev() {
+        match c {
+            ']' => depth += 1,
+            '[' if depth == 0 => return Some(index),
+            '[' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
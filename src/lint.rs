@@ -0,0 +1,282 @@
+//! Docstring quality checks over the model.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::serialize_path;
+use crate::docstring::malformed_section_headers;
+use crate::model::{FunctionDoc, Item, ModuleDoc};
+use crate::options::RenderOptions;
+use crate::paths::normalize_path;
+
+/// A lint rule, identified on the command line by its code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    /// A public module, class, function or method without a docstring.
+    MissingDocstring,
+    /// An `Args:` entry naming a parameter the signature does not have.
+    UnknownParameter,
+    /// A parameter left out of an existing `Args:` section.
+    UndocumentedParameter,
+    /// A non-`None` return annotation without a `Returns:` section.
+    MissingReturns,
+    /// A `Raises:` entry for an exception the body never raises.
+    UnraisedException,
+    /// A line that looks like a section header but is not recognized.
+    MalformedSection,
+    /// `Args:` entries listed in a different order than the signature.
+    ParameterOrder,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 7] = [
+        Rule::MissingDocstring,
+        Rule::UnknownParameter,
+        Rule::UndocumentedParameter,
+        Rule::MissingReturns,
+        Rule::UnraisedException,
+        Rule::MalformedSection,
+        Rule::ParameterOrder,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Rule::MissingDocstring => "DOC001",
+            Rule::UnknownParameter => "DOC002",
+            Rule::UndocumentedParameter => "DOC003",
+            Rule::MissingReturns => "DOC004",
+            Rule::UnraisedException => "DOC005",
+            Rule::MalformedSection => "DOC006",
+            Rule::ParameterOrder => "DOC007",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.code().eq_ignore_ascii_case(code))
+            .ok_or_else(|| format!("unknown lint rule `{}`", code))
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+/// One problem found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub rule: Rule,
+    #[serde(serialize_with = "serialize_path")]
+    pub file: PathBuf,
+    /// One-based line of the definition the finding is about.
+    pub line: usize,
+    /// Path within the module, e.g. `Stack.describe`; the module's dotted
+    /// name for module-level findings.
+    pub symbol: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} {}: {}",
+            normalize_path(&self.file),
+            self.line,
+            self.rule,
+            self.symbol,
+            self.message
+        )
+    }
+}
+
+/// Runs every rule over the symbols of `module` that `options` documents.
+/// Findings are in source order.
+pub fn lint(module: &ModuleDoc, options: &RenderOptions) -> Vec<Finding> {
+    let mut linter = Linter {
+        module,
+        findings: Vec::new(),
+    };
+
+    match &module.docstring {
+        Some(docstring) => linter.check_sections(&module.qualified_name, 1, docstring),
+        None => linter.missing_docstring(&module.qualified_name, 1, "module"),
+    }
+    for item in module
+        .items
+        .iter()
+        .filter(|item| options.documents_export(module, item.name()))
+    {
+        match item {
+            Item::Class(class) => {
+                match &class.docstring {
+                    Some(docstring) => linter.check_sections(&class.name, class.line, docstring),
+                    None => linter.missing_docstring(&class.name, class.line, "class"),
+                }
+                for method in class
+                    .methods
+                    .iter()
+                    .filter(|method| options.documents_method(&method.name))
+                {
+                    let path = format!("{}.{}", class.name, method.name);
+                    linter.check_function(method, &path, true);
+                }
+            }
+            Item::Function(function) => linter.check_function(function, &function.name, false),
+        }
+    }
+    linter.findings
+}
+
+/// The documented classes, functions and methods of `module` without a
+/// docstring, as [`Rule::MissingDocstring`] findings in source order. The
+/// module's own docstring is not required.
+pub fn missing_docstrings(module: &ModuleDoc, options: &RenderOptions) -> Vec<Finding> {
+    lint(module, options)
+        .into_iter()
+        .filter(|finding| {
+            finding.rule == Rule::MissingDocstring && finding.symbol != module.qualified_name
+        })
+        .collect()
+}
+
+/// Whether a documented symbol without a docstring fails the run or is
+/// only reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocstringRequirement {
+    Error,
+    Warn,
+}
+
+struct Linter<'a> {
+    module: &'a ModuleDoc,
+    findings: Vec<Finding>,
+}
+
+impl Linter<'_> {
+    fn report(&mut self, rule: Rule, symbol: &str, line: usize, message: String) {
+        self.findings.push(Finding {
+            rule,
+            file: self.module.path.clone(),
+            line,
+            symbol: symbol.to_string(),
+            message,
+        });
+    }
+
+    fn missing_docstring(&mut self, symbol: &str, line: usize, kind: &str) {
+        self.report(
+            Rule::MissingDocstring,
+            symbol,
+            line,
+            format!("public {} has no docstring", kind),
+        );
+    }
+
+    fn check_sections(&mut self, symbol: &str, line: usize, docstring: &str) {
+        for (_, message) in malformed_section_headers(docstring) {
+            self.report(Rule::MalformedSection, symbol, line, message);
+        }
+    }
+
+    fn check_function(&mut self, function: &FunctionDoc, path: &str, is_method: bool) {
+        let Some(docstring) = &function.docstring else {
+            let kind = if is_method { "method" } else { "function" };
+            self.missing_docstring(path, function.line, kind);
+            return;
+        };
+        self.check_sections(path, function.line, docstring);
+        let parsed = &function.parsed_docstring;
+
+        // `*args` and `**kwargs` entries may be written with or without
+        // their stars.
+        for entry in &parsed.params {
+            let name = entry.name.trim_start_matches('*');
+            if !function.parameters.iter().any(|p| p.name == name) {
+                self.report(
+                    Rule::UnknownParameter,
+                    path,
+                    function.line,
+                    format!(
+                        "`Args:` documents `{}`, which is not a parameter",
+                        entry.name
+                    ),
+                );
+            }
+        }
+        if !parsed.params.is_empty() {
+            for parameter in function.arguments(is_method) {
+                if parameter.description.is_none() {
+                    self.report(
+                        Rule::UndocumentedParameter,
+                        path,
+                        function.line,
+                        format!("parameter `{}` is missing from `Args:`", parameter.name),
+                    );
+                }
+            }
+        }
+
+        if let Some((documented, expected)) = function.misordered_parameter() {
+            self.report(
+                Rule::ParameterOrder,
+                path,
+                function.line,
+                format!(
+                    "`Args:` lists `{}` where the signature has `{}`",
+                    documented, expected
+                ),
+            );
+        }
+
+        if let Some(returns) = function.returns.as_deref() {
+            if returns != "None" && parsed.returns.is_none() {
+                self.report(
+                    Rule::MissingReturns,
+                    path,
+                    function.line,
+                    format!("returns `{}` but has no `Returns:` section", returns),
+                );
+            }
+        }
+
+        for entry in &parsed.raises {
+            let raised = function
+                .raises
+                .iter()
+                .any(|raised| last_segment(raised) == last_segment(&entry.name));
+            if !raised {
+                self.report(
+                    Rule::UnraisedException,
+                    path,
+                    function.line,
+                    format!(
+                        "`Raises:` documents `{}`, which is never raised",
+                        entry.name
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
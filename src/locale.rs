@@ -0,0 +1,204 @@
+//! User-facing labels of generated pages, replaceable with a locale file
+//! passed with `--locale`.
+//!
+//! Keys left out of the file keep their English text. Placeholders in
+//! braces are filled in when rendering:
+//!
+//! ```toml
+//! parameters = "引数"
+//! returns = "戻り値"
+//! required = "必須"
+//! source_code = "{path} のソースコード"
+//! ```
+
+use serde::Deserialize;
+
+/// Every label and sentence the renderer writes around the documented
+/// content. Markup such as `**`, backticks and the colon after a section
+/// heading is added by the renderer.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Strings {
+    /// Tag after the module heading, with `special_tag`.
+    pub special: String,
+    /// Symbol kinds in the summary table and entry headings.
+    pub class: String,
+    /// Label of a class deriving from `Protocol`.
+    pub protocol: String,
+    /// Label of an abstract base class.
+    pub abstract_class: String,
+    pub function: String,
+    pub method: String,
+    pub property: String,
+    pub cached_property: String,
+    pub type_alias: String,
+    pub classmethod: String,
+    pub staticmethod: String,
+    pub abstractmethod: String,
+    /// Label of functions and methods defined with `async def`.
+    pub asynchronous: String,
+    pub frozen: String,
+    pub kw_only: String,
+    /// Tag of an attribute annotated with `ClassVar`.
+    pub class_attribute: String,
+    /// Label of the base classes under a class heading.
+    pub bases: String,
+    /// Table headers.
+    pub name: String,
+    pub kind: String,
+    #[serde(rename = "type")]
+    pub type_name: String,
+    pub description: String,
+    pub default: String,
+    pub value: String,
+    /// Header of the column telling whether a `TypedDict` key is required.
+    pub status: String,
+    pub imported_from: String,
+    /// Default cell of a parameter or field without a default.
+    pub required: String,
+    /// Status cell of a `TypedDict` key that may be left out.
+    pub optional: String,
+    /// Section headings.
+    pub parameters: String,
+    pub fields: String,
+    /// Heading of the members table of an enum.
+    pub enum_members: String,
+    /// Heading of the keys table of a `TypedDict`.
+    pub keys: String,
+    pub attributes: String,
+    pub returns: String,
+    pub raises: String,
+    pub example: String,
+    pub referenced_by: String,
+    pub reexports: String,
+    pub module_attributes: String,
+    pub modules: String,
+    pub subpackages: String,
+    /// `{class}`: the class whose members are listed.
+    pub members: String,
+    /// `{path}`: the source file.
+    pub source_code: String,
+    /// `{module}`: the module a re-exported symbol is defined in.
+    pub defined_in: String,
+    /// `{module}` and `{name}`: where a renamed re-export is defined.
+    pub defined_in_as: String,
+    /// `{version}`: the version from a version marker.
+    pub added_in: String,
+    pub changed_in: String,
+    pub read_only: String,
+    pub writable: String,
+    /// Label of the type a property's setter accepts.
+    pub setter_type: String,
+    /// Label of the type variables of a generic class or function.
+    pub type_parameters: String,
+    /// Label of the list of functions defined inside a function.
+    pub inner_functions: String,
+    /// Label of the `@overload` signatures of a function.
+    pub overloads: String,
+    pub no_return_value: String,
+    /// Note under the heading of a protocol.
+    pub protocol_note: String,
+    /// Label of the callout on a deprecated definition.
+    pub deprecated: String,
+    /// Label of the list of a class's slots and metaclass.
+    pub implementation_notes: String,
+    pub slots: String,
+    pub metaclass: String,
+    /// Note under the heading of an abstract class.
+    pub abstract_note: String,
+    /// `{members}`: the abstract methods and properties of a class.
+    pub must_implement: String,
+    /// Description of an exception found in the body, not the docstring.
+    pub detected_from_source: String,
+    /// `{count}`: how many referring symbols are not listed.
+    pub and_more: String,
+}
+
+impl Default for Strings {
+    fn default() -> Self {
+        let s = str::to_string;
+        Self {
+            special: s("special"),
+            class: s("class"),
+            protocol: s("protocol"),
+            abstract_class: s("abstract"),
+            function: s("function"),
+            method: s("method"),
+            property: s("property"),
+            cached_property: s("cached property"),
+            type_alias: s("type alias"),
+            classmethod: s("classmethod"),
+            staticmethod: s("staticmethod"),
+            abstractmethod: s("abstractmethod"),
+            asynchronous: s("async"),
+            frozen: s("frozen"),
+            kw_only: s("kw_only"),
+            class_attribute: s("class attribute"),
+            bases: s("Bases"),
+            name: s("Name"),
+            kind: s("Kind"),
+            type_name: s("Type"),
+            description: s("Description"),
+            default: s("Default"),
+            value: s("Value"),
+            status: s("Status"),
+            imported_from: s("Imported from"),
+            required: s("required"),
+            optional: s("optional"),
+            parameters: s("Parameters"),
+            fields: s("Fields"),
+            enum_members: s("Members"),
+            keys: s("Keys"),
+            attributes: s("Attributes"),
+            returns: s("Returns"),
+            raises: s("Raises"),
+            example: s("Example"),
+            referenced_by: s("Referenced by"),
+            reexports: s("Re-exported names"),
+            module_attributes: s("Module attributes"),
+            modules: s("Modules"),
+            subpackages: s("Sub-packages"),
+            members: s("{class} members"),
+            source_code: s("Source code in {path}"),
+            defined_in: s("Defined in {module}."),
+            defined_in_as: s("Defined in {module} as {name}."),
+            added_in: s("Added in {version}"),
+            changed_in: s("Changed in {version}"),
+            read_only: s("Read-only."),
+            writable: s("Writable."),
+            setter_type: s("Setter type"),
+            type_parameters: s("Type parameters"),
+            inner_functions: s("Inner functions"),
+            overloads: s("Overloads"),
+            no_return_value: s("This function doesn't return a value."),
+            protocol_note: s(
+                "This is a protocol: implementers must provide the methods and attributes documented here.",
+            ),
+            deprecated: s("Deprecated"),
+            implementation_notes: s("Implementation notes"),
+            slots: s("Slots"),
+            metaclass: s("Metaclass"),
+            abstract_note: s("This class is abstract and cannot be instantiated directly."),
+            must_implement: s("Subclasses must implement: {members}."),
+            detected_from_source: s("Detected from source."),
+            and_more: s("and {count} more"),
+        }
+    }
+}
+
+impl Strings {
+    /// Parses a locale file. Unknown keys are an error, so that typos do
+    /// not silently leave a label untranslated.
+    pub fn from_toml(source: &str) -> Result<Strings, toml::de::Error> {
+        toml::from_str(source)
+    }
+}
+
+/// Fills the `{name}` placeholders of a template.
+pub(crate) fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+}
@@ -1,509 +1,2410 @@
-use clap::Parser;
-use rustpython_parser::{ast, Parse};
+use clap::{Parser, Subcommand, ValueEnum};
+use rustpython_parser::source_code::LineIndex;
+use rustpython_parser::ParseError;
+use sdkdocgen::{
+    attach_examples, check_links, content_hash, coverage, decode_source, diff_api,
+    discover_python_files, dotted_module_name, expand_glob, extract_cli, extract_routes, glob_base,
+    glob_matches, index_page, inline_private_reexports, is_glob, is_notebook, lint,
+    merge_adjacent_stub, merge_stub_signatures, missing_docstrings, normalize_path,
+    package_directories, package_listing, parse_module, pyproject_version, qualified_name,
+    read_source, render_changelog, render_cli_page, render_module, render_nav,
+    render_package_index, render_routes_page, stale_pages, stub_in_dir, AliasStyle, Cache,
+    CliCommand, Config, CoverageCounts, CoverageFormat, CoverageReport, Diagnostic,
+    DiagnosticsFormat, DocstringRequirement, Dunders, Endpoint, Examples, Format, Item,
+    ModuleConfig, ModuleDoc, NamePattern, NavFormat, NavPage, NestedFunctions, Notebook,
+    OptionalStyle, OutputTemplate, Project, RenderOptions, Rule, Severity, Strings, StubPreference,
+    SymbolKind, TimingLayer, Timings, CACHE_FILE,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal, Read};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::prelude::*;
 
 #[derive(Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    after_help = EXIT_CODES,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    args: Args,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Report docstring coverage without generating pages
+    Coverage(CoverageArgs),
+    /// Check docstring quality without generating pages
+    Lint(LintArgs),
+    /// Summarize API changes between two versions of a source tree
+    Diff(DiffArgs),
+}
+
+/// Arguments for generating pages. Any of them may be set in the
+/// configuration file instead; those given here take precedence.
+#[derive(Clone, clap::Args)]
 struct Args {
-    /// Path to the Python file
+    /// Path to the Python file, `.pyi` stub or `.ipynb` notebook, a
+    /// directory whose `.py` files and stubs without a `.py` file are all
+    /// documented, or a glob pattern such as `src/zenml/**/*.py`. May be
+    /// repeated
     #[arg(short, long)]
-    file: String,
+    file: Vec<PathBuf>,
+
+    /// Read the Python source from standard input instead of --file
+    #[arg(long, conflicts_with = "file")]
+    stdin: bool,
 
     /// Output directory for the Markdown file
     #[arg(short, long)]
-    output_path: PathBuf,
+    output_path: Option<PathBuf>,
+
+    /// Write every page directly under --output-path, named after its
+    /// module, instead of mirroring the package layout. Two modules with
+    /// the same name are an error
+    #[arg(long)]
+    flat: bool,
+
+    /// Page name for a package's `__init__.py` [default: index]
+    #[arg(long, value_name = "NAME")]
+    index_name: Option<String>,
+
+    /// List the modules and sub-packages of each package directory on its
+    /// --index-name page, written for directories without an `__init__.py`
+    #[arg(long)]
+    package_index: bool,
+
+    /// Also write a navigation file listing the pages by package for a
+    /// static site generator: `nav.yml` for MkDocs, `sidebar.json` for
+    /// Docusaurus, which also writes each page's `id` to its frontmatter,
+    /// or `SUMMARY.md` for mdBook, which needs --format markdown
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    nav_format: Option<NavFormatArg>,
+
+    /// Name of each module's page in the output directory, with the
+    /// placeholders {page}, {stem}, {kebab}, {module_path} and {ext}, e.g.
+    /// `{module_path}.api.{ext}`. Must end in `.{ext}` [default:
+    /// {page}.{ext}]
+    #[arg(long, value_name = "TEMPLATE")]
+    output_template: Option<OutputTemplate>,
+
+    /// Print the generated pages instead of writing them to --output-path.
+    /// With more than one page, each starts with a `==> path <==` line
+    #[arg(long, conflicts_with = "output_path")]
+    stdout: bool,
+
+    /// Compare the generated pages with the files under --output-path
+    /// instead of writing them, and fail listing the files that differ, are
+    /// missing or were not generated
+    #[arg(long, conflicts_with = "stdout")]
+    check: bool,
+
+    /// Keep running and regenerate the pages whenever an input file
+    /// changes, until interrupted with Ctrl-C. The page of an input file
+    /// that is deleted is removed
+    #[arg(long, conflicts_with_all = ["stdin", "stdout", "check", "cache"])]
+    watch: bool,
+
+    /// Parse the input files and list the page each would be written to and
+    /// what it would document, without writing anything
+    #[arg(long, conflicts_with_all = ["stdin", "stdout", "check", "watch"])]
+    dry_run: bool,
+
+    /// Keep a cache of input and page hashes in the output directory: a run
+    /// with unchanged inputs and settings does nothing, and pages whose
+    /// content did not change are not written again
+    #[arg(long)]
+    cache: bool,
+
+    /// With --cache, regenerate and write every page even if the cache
+    /// says it is up to date
+    #[arg(long)]
+    force: bool,
+
+    /// Page title in place of the module name, e.g. for source read with
+    /// --stdin
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Title pages after their module in Title Case, e.g. `Base
+    /// Orchestrator` for `base_orchestrator.py`, unless the configuration
+    /// file titles them
+    #[arg(long)]
+    prettify_titles: bool,
+
+    /// Glob pattern of files to leave out, matched against their path
+    /// relative to the directory or pattern given with --file, e.g.
+    /// `**/tests/**`. May be repeated
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Follow symlinks in directories given with --file. Each directory and
+    /// file is read once, however many links lead to it
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Show the Markdown cells of `.ipynb` notebooks between the documented
+    /// definitions
+    #[arg(long)]
+    include_notebook_markdown: bool,
+
+    /// TOML configuration file setting any of these options, and marker
+    /// decorators and tags [default: sdkdocgen.toml if present]
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Print the configuration merged from the file and the command line,
+    /// then exit
+    #[arg(long)]
+    print_config: bool,
+
+    /// TOML file translating the labels of generated pages, e.g. `ja.toml`;
+    /// labels it leaves out stay in English
+    #[arg(long, value_name = "FILE")]
+    locale: Option<PathBuf>,
+
+    /// Dotted package path prepended to module names in headings, links
+    /// and import snippets, e.g. `zenml`; none by default
+    #[arg(long, value_name = "PREFIX", alias = "package-name")]
+    package_prefix: Option<String>,
+
+    /// Write a `special` tag after each module heading
+    #[arg(long)]
+    special_tag: bool,
+
+    /// Markup of the generated pages: MDX for Mintlify, or Markdown
+    /// without components written to `.md` files [default: mdx]
+    #[arg(long, value_enum)]
+    format: Option<FormatArg>,
+
+    /// Document `_private` classes, functions and methods
+    #[arg(long, overrides_with = "skip_private")]
+    include_private: bool,
+
+    /// Leave `_private` classes, functions and methods out, undoing an
+    /// earlier --include-private [default]
+    #[arg(long, overrides_with = "include_private")]
+    skip_private: bool,
+
+    /// Which dunder methods to document [default: init-only]
+    #[arg(long, value_enum, value_name = "MODE")]
+    dunders: Option<DundersArg>,
+
+    /// Document every public class, function and type alias of a module,
+    /// not only those listed in its `__all__`
+    #[arg(long)]
+    ignore_all: bool,
+
+    /// Leave out functions and methods carrying this decorator, matched on
+    /// the last segment of its name: `command` matches both `@command` and
+    /// `@click.command()`. May be repeated
+    #[arg(long, value_name = "NAME")]
+    skip_decorator: Vec<String>,
+
+    /// Treat subclasses of this class as pydantic models, like those of
+    /// `BaseModel`, matched on the last segment of its name. May be
+    /// repeated
+    #[arg(long, value_name = "NAME")]
+    pydantic_base: Vec<String>,
+
+    /// Document the validators of pydantic models, such as methods
+    /// decorated with `@field_validator`
+    #[arg(long)]
+    show_validators: bool,
+
+    /// Leave out the `Config` class nested in pydantic models
+    #[arg(long)]
+    skip_nested_config: bool,
+
+    /// How functions defined inside functions and methods are shown:
+    /// only in the source, listed, documented, or omitted even from the
+    /// source [default: hide]
+    #[arg(long, value_enum, value_name = "MODE")]
+    nested_functions: Option<NestedFunctionsArg>,
+
+    /// Document the parameters and docstring of `__init__` under the class
+    /// heading instead of in a section of their own
+    #[arg(long)]
+    merge_init: bool,
+
+    /// Treat this decorator as marking definitions deprecated, like
+    /// `@deprecated`, matched on the last segment of its name. May be
+    /// repeated
+    #[arg(long, value_name = "NAME")]
+    deprecated_decorator: Vec<String>,
+
+    /// Leave deprecated classes, functions and methods out
+    #[arg(long)]
+    hide_deprecated: bool,
+
+    /// Document deprecated classes and functions after the others on their
+    /// page
+    #[arg(long)]
+    deprecated_last: bool,
+
+    /// Take a function's parameters and return type from a signature such
+    /// as `run(name: str) -> Run` on the first line of its docstring, for
+    /// functions whose own signature a decorator hides
+    #[arg(long)]
+    signature_from_docstring: bool,
+
+    /// Document every public module-level variable, not only upper-case
+    /// constants and names listed in `__all__`
+    #[arg(long)]
+    all_module_attributes: bool,
+
+    /// Document only the module-level classes and functions whose name
+    /// matches this regular expression, e.g. `Client|StackComponent`, and
+    /// write no page for modules where nothing matches
+    #[arg(long, value_name = "REGEX")]
+    only: Option<NamePattern>,
+
+    /// List exceptions raised in the body of functions whose docstring has
+    /// no `Raises:` section
+    #[arg(long)]
+    infer_raises: bool,
+
+    /// Which signature wins when a `.pyi` stub disagrees with the
+    /// implementation [default: stub]
+    #[arg(long, value_enum)]
+    prefer: Option<Prefer>,
+
+    /// Directory of `.pyi` stubs mirroring the package layout, whose
+    /// annotations fill in those of the documented modules; definitions
+    /// only in a stub are left out. Takes the place of a stub next to the
+    /// file
+    #[arg(long, value_name = "DIR")]
+    stubs_dir: Option<PathBuf>,
+
+    /// Embed the shortest test calling each function or method found under
+    /// this directory as its example, unless its docstring has one
+    #[arg(long, value_name = "DIR")]
+    examples_from: Option<PathBuf>,
+
+    /// Show types naming a type alias of the module as the aliased type
+    /// (`expand`) or as both (`both`), instead of linking to the alias
+    #[arg(
+        long,
+        value_enum,
+        value_name = "MODE",
+        num_args = 0..=1,
+        default_missing_value = "expand"
+    )]
+    expand_aliases: Option<ExpandAliases>,
+
+    /// Draw a Mermaid class diagram of each class's documented ancestors
+    /// and direct subclasses
+    #[arg(long)]
+    inheritance_diagrams: bool,
+
+    /// List the symbols naming each class and function in an annotation,
+    /// a base class list or a decorator
+    #[arg(long)]
+    referenced_by: bool,
+
+    /// Show `Any` instead of a type inferred from the default value for
+    /// unannotated parameters
+    #[arg(long)]
+    no_infer_types: bool,
+
+    /// Write `Optional[X]` as `X | None` and `Union[A, B]` as `A | B`
+    #[arg(long)]
+    normalize_unions: bool,
+
+    /// Show `Annotated[T, ...]` as just `T` in tables; the source keeps the
+    /// metadata
+    #[arg(long)]
+    collapse_annotated: bool,
+
+    /// How to print diagnostics collected during the run [default: text]
+    #[arg(long, value_enum)]
+    diagnostics_format: Option<DiagnosticsFormatArg>,
+
+    /// Exit with a non-zero status if any diagnostics were reported
+    #[arg(long)]
+    strict: bool,
+
+    /// Report every documented class, function and method without a
+    /// docstring, and exit with a non-zero status unless the level is
+    /// `warn`
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "error"
+    )]
+    require_docstrings: Option<DocstringRequirementArg>,
+
+    /// Print the documentation coverage of every module after generating
+    /// the pages
+    #[arg(long)]
+    coverage: bool,
+
+    /// How to print the --coverage report [default: text]
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    coverage_format: Option<OutputFormat>,
+
+    /// Write a CLI reference page under `cli/` for each Click or argparse
+    /// command, and leave the functions implementing Click commands out of
+    /// the API page
+    #[arg(long)]
+    cli: bool,
+
+    /// Write an endpoint reference page under `routes/` for the FastAPI or
+    /// Flask routes in the file, and leave their handler functions out of
+    /// the API page
+    #[arg(long)]
+    routes: bool,
+
+    /// Report links to pages or anchors missing from the generated output;
+    /// external links are not checked
+    #[arg(long)]
+    check_links: bool,
+
+    /// Release written to the frontmatter as `version` [default: the
+    /// version in the nearest pyproject.toml, or the `__version__` of an
+    /// input module]
+    #[arg(long, value_name = "VERSION")]
+    doc_version: Option<String>,
+
+    /// Extra frontmatter key of every page, replacing a built-in key such as
+    /// `title` of the same name. May be repeated
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_frontmatter)]
+    frontmatter: Vec<(String, String)>,
+
+    /// Record the generation time in the frontmatter (honors SOURCE_DATE_EPOCH)
+    #[arg(long)]
+    timestamp: bool,
+
+    /// Print a per-phase and per-file timing table at the end of the run
+    #[arg(long)]
+    timing: bool,
+
+    /// Files to parse and render at once [default: the number of CPUs]
+    #[arg(short, long, value_name = "N")]
+    jobs: Option<NonZeroUsize>,
+
+    #[command(flatten)]
+    log: LogArgs,
 }
 
-fn is_classmethod(decorators: &[ast::Expr]) -> bool {
-    decorators.iter().any(|d| {
-        if let ast::Expr::Name(ast::ExprName { id, .. }) = d {
-            id == "classmethod"
-        } else {
-            false
+#[derive(clap::Args)]
+struct CoverageArgs {
+    /// Path to a Python file, a directory whose `.py` files are all
+    /// counted, or a glob pattern. May be repeated
+    #[arg(short, long, required = true)]
+    file: Vec<PathBuf>,
+
+    /// Glob pattern of files to leave out, matched against their path
+    /// relative to the directory or pattern given with --file
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Follow symlinks in directories given with --file
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Count `_private` classes, functions and methods
+    #[arg(long)]
+    include_private: bool,
+
+    /// How to print the report
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Exit with a non-zero status if total coverage is below this percentage
+    #[arg(long, alias = "coverage-fail-under", value_name = "PERCENT")]
+    fail_under: Option<f64>,
+
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[derive(clap::Args)]
+struct LintArgs {
+    /// Path to a Python file, a directory whose `.py` files are all
+    /// checked, or a glob pattern. May be repeated
+    #[arg(short, long, required = true)]
+    file: Vec<PathBuf>,
+
+    /// Glob pattern of files to leave out, matched against their path
+    /// relative to the directory or pattern given with --file
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Follow symlinks in directories given with --file
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Check `_private` classes, functions and methods
+    #[arg(long)]
+    include_private: bool,
+
+    /// Only run these rules (comma-separated codes, e.g. DOC001,DOC004)
+    #[arg(long, value_delimiter = ',', value_name = "CODES")]
+    select: Vec<Rule>,
+
+    /// Skip these rules (comma-separated codes)
+    #[arg(long, value_delimiter = ',', value_name = "CODES")]
+    ignore: Vec<Rule>,
+
+    /// How to print findings
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[derive(clap::Args)]
+struct DiffArgs {
+    /// The previous version: a Python file or a package directory
+    old_path: PathBuf,
+
+    /// The new version: a Python file or a package directory
+    new_path: PathBuf,
+
+    /// Compare `_private` classes, functions and methods too
+    #[arg(long)]
+    include_private: bool,
+
+    /// How to print the changes
+    #[arg(long, value_enum, default_value_t = DiffFormat::Markdown)]
+    format: DiffFormat,
+
+    #[command(flatten)]
+    log: LogArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Clone, clap::Args)]
+struct LogArgs {
+    /// Show more detail about what the tool is doing
+    #[arg(short, long, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Only print errors
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiagnosticsFormatArg {
+    Text,
+    Json,
+}
+
+impl From<DiagnosticsFormatArg> for DiagnosticsFormat {
+    fn from(format: DiagnosticsFormatArg) -> Self {
+        match format {
+            DiagnosticsFormatArg::Text => DiagnosticsFormat::Text,
+            DiagnosticsFormatArg::Json => DiagnosticsFormat::Json,
         }
-    })
+    }
 }
 
-fn extract_type(annotation: &Box<ast::Expr>) -> String {
-    match &**annotation {
-        ast::Expr::Name(name) => name.id.to_string(),
-        ast::Expr::Attribute(attr) => format!(
-            "{}.{}",
-            extract_type(&Box::new(*attr.value.clone())),
-            attr.attr
-        ),
-        ast::Expr::Subscript(subscript) => {
-            let value_type = extract_type(&Box::new(*subscript.value.clone()));
-            let slice_type = match &*subscript.slice {
-                ast::Expr::Tuple(tuple) => {
-                    let types: Vec<String> = tuple
-                        .elts
-                        .iter()
-                        .map(|k| extract_type(&Box::new(k.clone())))
-                        .collect();
-                    types.join(", ")
-                }
-                _ => extract_type(&Box::new(*subscript.slice.clone())),
-            };
-            format!("{}[{}]", value_type, slice_type)
+impl From<DiagnosticsFormat> for DiagnosticsFormatArg {
+    fn from(format: DiagnosticsFormat) -> Self {
+        match format {
+            DiagnosticsFormat::Text => DiagnosticsFormatArg::Text,
+            DiagnosticsFormat::Json => DiagnosticsFormatArg::Json,
         }
-        ast::Expr::List(list) => {
-            let elements: Vec<String> = list
-                .elts
-                .iter()
-                .map(|e| extract_type(&Box::new(e.clone())))
-                .collect();
-            format!("[{}]", elements.join(", "))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DocstringRequirementArg {
+    Error,
+    Warn,
+}
+
+impl From<DocstringRequirementArg> for DocstringRequirement {
+    fn from(requirement: DocstringRequirementArg) -> Self {
+        match requirement {
+            DocstringRequirementArg::Error => DocstringRequirement::Error,
+            DocstringRequirementArg::Warn => DocstringRequirement::Warn,
         }
-        ast::Expr::Tuple(tuple) => {
-            let elements: Vec<String> = tuple
-                .elts
-                .iter()
-                .map(|e| extract_type(&Box::new(e.clone())))
-                .collect();
-            format!("({})", elements.join(", "))
+    }
+}
+
+impl From<DocstringRequirement> for DocstringRequirementArg {
+    fn from(requirement: DocstringRequirement) -> Self {
+        match requirement {
+            DocstringRequirement::Error => DocstringRequirementArg::Error,
+            DocstringRequirement::Warn => DocstringRequirementArg::Warn,
         }
-        ast::Expr::Call(call) => {
-            let func_name = extract_type(&call.func);
-            let args: Vec<String> = call
-                .args
-                .iter()
-                .map(|arg| extract_type(&Box::new(arg.clone())))
-                .collect();
-            format!("{}[{}]", func_name, args.join(", "))
-        }
-        ast::Expr::BinOp(binop) => {
-            let left = extract_type(&binop.left);
-            let right = extract_type(&binop.right);
-            format!("{} | {}", left, right) // Assuming '|' is used for Union types
-        }
-        // If we encounter any other type that we haven't explicitly handled,
-        // we'll return it as a string representation
-        _ => format!("{:?}", annotation),
-    }
-}
-
-fn format_args_table(args: &ast::Arguments) -> String {
-    let mut table = String::from(
-        "\n**Parameters:**\n\n| Name | Type | Description | Default |\n| --- | --- | --- | --- |\n",
-    );
-
-    let args_len = args.args.len();
-    let defaults: Vec<&ast::Expr> = args.defaults().collect();
-    let defaults_len = defaults.len();
-
-    for (i, arg) in args.args.iter().enumerate() {
-        let name = &arg.def.arg;
-        let arg_type = arg
-            .def
-            .annotation
-            .as_ref()
-            .map_or("Any".to_string(), extract_type);
-        let description = ""; // You'd need to extract this from the docstring
-        let default = if i >= args_len - defaults_len {
-            let default_index = i - (args_len - defaults_len);
-            format!("{:?}", defaults[default_index])
-        } else {
-            "_required_".to_string()
-        };
+    }
+}
 
-        table.push_str(&format!(
-            "| `{}` | `{}` | {} | {} |\n",
-            name, arg_type, description, default
-        ));
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Prefer {
+    Stub,
+    Impl,
+}
+
+impl From<Prefer> for StubPreference {
+    fn from(prefer: Prefer) -> Self {
+        match prefer {
+            Prefer::Stub => StubPreference::Stub,
+            Prefer::Impl => StubPreference::Impl,
+        }
     }
+}
 
-    // Handle keyword-only arguments
-    for (_i, arg) in args.kwonlyargs.iter().enumerate() {
-        let name = &arg.def.arg;
-        let arg_type = arg
-            .def
-            .annotation
-            .as_ref()
-            .map_or("Any".to_string(), extract_type);
-        let description = ""; // You'd need to extract this from the docstring
-        let default = "_required_".to_string(); // Since kw_defaults is not available, we'll assume all are required
-
-        table.push_str(&format!(
-            "| `{}` | `{}` | {} | {} |\n",
-            name, arg_type, description, default
-        ));
+impl From<StubPreference> for Prefer {
+    fn from(prefer: StubPreference) -> Self {
+        match prefer {
+            StubPreference::Stub => Prefer::Stub,
+            StubPreference::Impl => Prefer::Impl,
+        }
     }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExpandAliases {
+    Link,
+    Expand,
+    Both,
+}
 
-    table
+impl From<ExpandAliases> for AliasStyle {
+    fn from(expand: ExpandAliases) -> Self {
+        match expand {
+            ExpandAliases::Link => AliasStyle::Link,
+            ExpandAliases::Expand => AliasStyle::Expand,
+            ExpandAliases::Both => AliasStyle::Both,
+        }
+    }
 }
 
-fn format_returns_table(returns: &Option<Box<ast::Expr>>) -> String {
-    let mut table = String::from("\n**Returns:**\n\n| Type | Description |\n| --- | --- |\n");
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FormatArg {
+    Mdx,
+    Markdown,
+}
 
-    if let Some(ret) = returns {
-        let ret_type = extract_type(ret);
-        let description = ""; // You'd need to extract this from the docstring
-        table.push_str(&format!("| `{}` | {} |\n", ret_type, description));
-    } else {
-        table.push_str("| None | This function doesn't return a value. |\n");
+impl From<FormatArg> for Format {
+    fn from(format: FormatArg) -> Self {
+        match format {
+            FormatArg::Mdx => Format::Mdx,
+            FormatArg::Markdown => Format::Markdown,
+        }
     }
+}
 
-    table
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum NavFormatArg {
+    Mkdocs,
+    Docusaurus,
+    Mdbook,
 }
 
-fn format_function_doc(func_def: &ast::Stmt) -> String {
-    if let ast::Stmt::FunctionDef(func_def) = func_def {
-        let mut doc = String::new();
+impl From<NavFormatArg> for NavFormat {
+    fn from(format: NavFormatArg) -> Self {
+        match format {
+            NavFormatArg::Mkdocs => NavFormat::Mkdocs,
+            NavFormatArg::Docusaurus => NavFormat::Docusaurus,
+            NavFormatArg::Mdbook => NavFormat::Mdbook,
+        }
+    }
+}
 
-        // Clean the function name and add it to the documentation
-        let clean_name = func_def.name.trim_matches('`');
-        doc.push_str(&format!("### `{}`\n\n", clean_name));
+impl From<NavFormat> for NavFormatArg {
+    fn from(format: NavFormat) -> Self {
+        match format {
+            NavFormat::Mkdocs => NavFormatArg::Mkdocs,
+            NavFormat::Docusaurus => NavFormatArg::Docusaurus,
+            NavFormat::Mdbook => NavFormatArg::Mdbook,
+        }
+    }
+}
 
-        // Add docstring if available
-        if let Some(ast::Stmt::Expr(expr)) = func_def.body.first() {
-            if let ast::Expr::Constant(ast::ExprConstant {
-                value: ast::Constant::Str(docstring),
-                ..
-            }) = &*expr.value
-            {
-                // Remove any leading/trailing whitespace and quotes from the docstring
-                let cleaned_docstring = docstring.trim().trim_matches('"').trim_matches('\'');
-                doc.push_str(&format!("{}\n\n", cleaned_docstring));
-            }
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DundersArg {
+    None,
+    InitOnly,
+    All,
+}
+
+impl From<DundersArg> for Dunders {
+    fn from(dunders: DundersArg) -> Self {
+        match dunders {
+            DundersArg::None => Dunders::None,
+            DundersArg::InitOnly => Dunders::InitOnly,
+            DundersArg::All => Dunders::All,
         }
+    }
+}
 
-        // Add parameters table
-        doc.push_str(&format_args_table(&func_def.args));
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum NestedFunctionsArg {
+    Hide,
+    List,
+    Document,
+    Omit,
+}
+
+impl From<NestedFunctionsArg> for NestedFunctions {
+    fn from(nested_functions: NestedFunctionsArg) -> Self {
+        match nested_functions {
+            NestedFunctionsArg::Hide => NestedFunctions::Hide,
+            NestedFunctionsArg::List => NestedFunctions::List,
+            NestedFunctionsArg::Document => NestedFunctions::Document,
+            NestedFunctionsArg::Omit => NestedFunctions::Omit,
+        }
+    }
+}
 
-        // Add returns table
-        doc.push_str(&format_returns_table(&func_def.returns));
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
 
-        // Add prose description (extracted from docstring)
-        doc.push_str("\n**Description:**\n\n");
-        if let Some(ast::Stmt::Expr(expr)) = func_def.body.first() {
-            if let ast::Expr::Constant(ast::ExprConstant {
-                value: ast::Constant::Str(docstring),
-                ..
-            }) = &*expr.value
-            {
-                // Extract description from docstring (assuming it's after the first empty line)
-                let description = docstring.splitn(2, "\n\n").nth(1).unwrap_or("");
-                doc.push_str(&format!("{}\n", description.trim()));
-            }
+impl From<OutputFormat> for CoverageFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Text => CoverageFormat::Text,
+            OutputFormat::Json => CoverageFormat::Json,
         }
+    }
+}
 
-        doc
-    } else {
-        String::new()
+impl From<CoverageFormat> for OutputFormat {
+    fn from(format: CoverageFormat) -> Self {
+        match format {
+            CoverageFormat::Text => OutputFormat::Text,
+            CoverageFormat::Json => OutputFormat::Json,
+        }
     }
 }
 
-fn reconstruct_function_def(func_def: &ast::StmtFunctionDef) -> String {
-    let mut func_str = String::new();
+impl Args {
+    /// Fills in the options left off the command line from the
+    /// configuration file. Flags only turn settings on, so a flag the file
+    /// sets is on unless the command line has its opposite.
+    fn merge(&self, config: &Config) -> Args {
+        let mut args = self.clone();
+        if args.file.is_empty() && !args.stdin {
+            args.file = config.file.clone();
+        }
+        if args.exclude.is_empty() {
+            args.exclude = config.exclude.clone();
+        }
+        let or = |value: &Option<PathBuf>, default: &Option<PathBuf>| {
+            value.clone().or_else(|| default.clone())
+        };
+        args.output_path = or(&args.output_path, &config.output_path);
+        args.index_name = args.index_name.or_else(|| config.index_name.clone());
+        args.output_template = args
+            .output_template
+            .or_else(|| config.output_template.clone());
+        args.locale = or(&args.locale, &config.locale);
+        args.stubs_dir = or(&args.stubs_dir, &config.stubs_dir);
+        args.examples_from = or(&args.examples_from, &config.examples_from);
+        args.package_prefix = args
+            .package_prefix
+            .or_else(|| config.package_prefix.clone());
+        args.prefer = args.prefer.or(config.prefer.map(Prefer::from));
+        args.jobs = args.jobs.or(config.jobs);
+        args.diagnostics_format = args
+            .diagnostics_format
+            .or(config.diagnostics_format.map(DiagnosticsFormatArg::from));
+        args.coverage_format = args
+            .coverage_format
+            .or(config.coverage_format.map(OutputFormat::from));
+        args.require_docstrings = args
+            .require_docstrings
+            .or(config.require_docstrings.map(DocstringRequirementArg::from));
+        let flag = |value: bool, default: Option<bool>| value || default == Some(true);
+        if !args.skip_private {
+            args.include_private = flag(args.include_private, config.include_private);
+        }
+        args.strict = flag(args.strict, config.strict);
+        args.cli = flag(args.cli, config.cli);
+        args.routes = flag(args.routes, config.routes);
+        args.check_links = flag(args.check_links, config.check_links);
+        args.timestamp = flag(args.timestamp, config.timestamp);
+        args.timing = flag(args.timing, config.timing);
+        args.flat = flag(args.flat, config.flat);
+        args.package_index = flag(args.package_index, config.package_index);
+        args.follow_symlinks = flag(args.follow_symlinks, config.follow_symlinks);
+        args.include_notebook_markdown = flag(
+            args.include_notebook_markdown,
+            config.include_notebook_markdown,
+        );
+        args.nav_format = args
+            .nav_format
+            .or(config.nav_format.map(NavFormatArg::from));
+        args.coverage = flag(args.coverage, config.coverage);
+        args.cache = flag(args.cache, config.cache);
+        args
+    }
 
-    // Add decorators
-    for decorator in &func_def.decorator_list {
-        func_str.push_str(&format!(
-            "@{}\n",
-            extract_type(&Box::new(decorator.clone()))
-        ));
+    /// Overrides `options` with the settings given on the command line.
+    fn apply(&self, options: &mut RenderOptions) {
+        if let Some(package_prefix) = &self.package_prefix {
+            options.module_prefix = package_prefix.clone();
+        }
+        if self.special_tag {
+            options.special_tag = true;
+        }
+        if self.prettify_titles {
+            options.prettify_titles = true;
+        }
+        if let Some(format) = self.format {
+            options.format = format.into();
+        }
+        if let Some(doc_version) = &self.doc_version {
+            options.doc_version = Some(doc_version.clone());
+        }
+        for (key, value) in &self.frontmatter {
+            options.frontmatter.insert(key.clone(), value.clone());
+        }
+        if self.nav_format == Some(NavFormatArg::Docusaurus) {
+            options.page_id = true;
+        }
+        if self.timestamp {
+            options.timestamp = Some(generation_timestamp());
+        }
+        if self.include_private {
+            options.include_private = true;
+        }
+        if self.skip_private {
+            options.include_private = false;
+        }
+        if let Some(dunders) = self.dunders {
+            options.dunders = dunders.into();
+        }
+        if self.ignore_all {
+            options.respect_all = false;
+        }
+        options
+            .skip_decorators
+            .extend(self.skip_decorator.iter().cloned());
+        options
+            .pydantic_bases
+            .extend(self.pydantic_base.iter().cloned());
+        if self.show_validators {
+            options.show_validators = true;
+        }
+        if self.skip_nested_config {
+            options.skip_nested_config = true;
+        }
+        if let Some(nested_functions) = self.nested_functions {
+            options.nested_functions = nested_functions.into();
+        }
+        if self.merge_init {
+            options.merge_init = true;
+        }
+        options
+            .deprecated_decorators
+            .extend(self.deprecated_decorator.iter().cloned());
+        if self.hide_deprecated {
+            options.hide_deprecated = true;
+        }
+        if self.deprecated_last {
+            options.deprecated_last = true;
+        }
+        if self.signature_from_docstring {
+            options.signature_from_docstring = true;
+        }
+        if self.all_module_attributes {
+            options.all_module_attributes = true;
+        }
+        if let Some(only) = &self.only {
+            options.only = Some(only.clone());
+        }
+        if self.no_infer_types {
+            options.infer_types = false;
+        }
+        if self.normalize_unions {
+            options.types.optional_style = OptionalStyle::PipeUnions;
+        }
+        if self.collapse_annotated {
+            options.types.collapse_annotated = true;
+        }
+        if self.infer_raises {
+            options.infer_raises = true;
+        }
+        if let Some(expand_aliases) = self.expand_aliases {
+            options.alias_style = expand_aliases.into();
+        }
+        if self.inheritance_diagrams {
+            options.inheritance_diagrams = true;
+        }
+        if self.referenced_by {
+            options.referenced_by = true;
+        }
+    }
+
+    /// The configuration file equivalent to these arguments and `options`.
+    fn effective_config(&self, options: &RenderOptions) -> Config {
+        Config {
+            file: self.file.clone(),
+            output_path: self.output_path.clone(),
+            flat: Some(self.flat),
+            index_name: Some(self.index_name().to_string()),
+            package_index: Some(self.package_index),
+            nav_format: self.nav_format.map(Into::into),
+            output_template: self.output_template.clone(),
+            exclude: self.exclude.clone(),
+            follow_symlinks: Some(self.follow_symlinks),
+            include_notebook_markdown: Some(self.include_notebook_markdown),
+            locale: self.locale.clone(),
+            package_prefix: Some(options.module_prefix.clone()),
+            special_tag: Some(options.special_tag),
+            prettify_titles: Some(options.prettify_titles),
+            doc_version: options.doc_version.clone(),
+            format: Some(options.format),
+            include_private: Some(options.include_private),
+            dunders: Some(options.dunders),
+            ignore_all: Some(!options.respect_all),
+            skip_decorator: options.skip_decorators.iter().cloned().collect(),
+            pydantic_base: options.pydantic_bases.iter().cloned().collect(),
+            show_validators: Some(options.show_validators),
+            skip_nested_config: Some(options.skip_nested_config),
+            nested_functions: Some(options.nested_functions),
+            merge_init: Some(options.merge_init),
+            deprecated_decorator: options.deprecated_decorators.iter().cloned().collect(),
+            hide_deprecated: Some(options.hide_deprecated),
+            deprecated_last: Some(options.deprecated_last),
+            signature_from_docstring: Some(options.signature_from_docstring),
+            all_module_attributes: Some(options.all_module_attributes),
+            only: options.only.clone(),
+            infer_raises: Some(options.infer_raises),
+            prefer: Some(self.prefer()),
+            stubs_dir: self.stubs_dir.clone(),
+            examples_from: self.examples_from.clone(),
+            expand_aliases: Some(options.alias_style),
+            inheritance_diagrams: Some(options.inheritance_diagrams),
+            referenced_by: Some(options.referenced_by),
+            no_infer_types: Some(!options.infer_types),
+            normalize_unions: Some(options.types.optional_style == OptionalStyle::PipeUnions),
+            collapse_annotated: Some(options.types.collapse_annotated),
+            usage_snippets: Some(options.usage_snippets),
+            diagnostics_format: Some(self.diagnostics_format()),
+            strict: Some(self.strict),
+            require_docstrings: self.require_docstrings.map(Into::into),
+            coverage: Some(self.coverage),
+            coverage_format: Some(self.coverage_format.map(Into::into).unwrap_or_default()),
+            cli: Some(self.cli),
+            routes: Some(self.routes),
+            check_links: Some(self.check_links),
+            timestamp: Some(self.timestamp),
+            timing: Some(self.timing),
+            jobs: Some(self.jobs()),
+            cache: Some(self.cache),
+            markers: options
+                .markers
+                .iter()
+                .map(|(name, action)| (name.clone(), action.into()))
+                .collect(),
+            frontmatter: options.frontmatter.clone(),
+            modules: options
+                .module_titles
+                .keys()
+                .chain(options.module_frontmatter.keys())
+                .map(|module| {
+                    let settings = ModuleConfig {
+                        title: options.module_titles.get(module).cloned(),
+                        frontmatter: options
+                            .module_frontmatter
+                            .get(module)
+                            .cloned()
+                            .unwrap_or_default(),
+                    };
+                    (module.clone(), settings)
+                })
+                .collect(),
+        }
     }
 
-    // Function signature
-    func_str.push_str(&format!("def {}(", func_def.name));
+    /// The stub of `file` in `--stubs-dir`, found by its path `relative` to
+    /// the directory it is in or, for a single file, by its name.
+    fn stub_file(&self, file: &Path, relative: Option<&Path>) -> Option<PathBuf> {
+        let relative = relative.or(file.file_name().map(Path::new))?;
+        Some(stub_in_dir(self.stubs_dir.as_deref()?, relative))
+    }
+
+    fn prefer(&self) -> StubPreference {
+        self.prefer.map(Into::into).unwrap_or_default()
+    }
+
+    fn diagnostics_format(&self) -> DiagnosticsFormat {
+        self.diagnostics_format.map(Into::into).unwrap_or_default()
+    }
+
+    fn index_name(&self) -> &str {
+        self.index_name.as_deref().unwrap_or(DEFAULT_INDEX_NAME)
+    }
+
+    fn jobs(&self) -> NonZeroUsize {
+        self.jobs
+            .or_else(|| thread::available_parallelism().ok())
+            .unwrap_or(NonZeroUsize::MIN)
+    }
+}
+
+impl From<&CoverageArgs> for RenderOptions {
+    fn from(args: &CoverageArgs) -> Self {
+        RenderOptions::builder()
+            .include_private(args.include_private)
+            .build()
+    }
+}
+
+impl From<&DiffArgs> for RenderOptions {
+    fn from(args: &DiffArgs) -> Self {
+        RenderOptions::builder()
+            .include_private(args.include_private)
+            .build()
+    }
+}
 
-    // Arguments
-    let args: Vec<String> = func_def
-        .args
-        .args
+impl From<&LintArgs> for RenderOptions {
+    fn from(args: &LintArgs) -> Self {
+        RenderOptions::builder()
+            .include_private(args.include_private)
+            .build()
+    }
+}
+
+/// Returns the generation time as an RFC 3339 UTC timestamp, taken from
+/// `SOURCE_DATE_EPOCH` when set so reproducible builds stay reproducible.
+fn generation_timestamp() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(now);
+    rfc3339(seconds)
+}
+
+/// Splits a `--frontmatter` value on its first `=`.
+fn parse_frontmatter(pair: &str) -> Result<(String, String), String> {
+    let (key, value) = pair
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, found `{}`", pair))?;
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("missing key in `{}`", pair));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+fn rfc3339(seconds: u64) -> String {
+    // Civil-from-days conversion (Howard Hinnant's algorithm).
+    let days = (seconds / 86_400) as i64;
+    let time_of_day = seconds % 86_400;
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
+
+/// Installs the global subscriber. `RUST_LOG` takes precedence over
+/// `--verbose`/`--quiet`. Returns the timing handle when `timing` is set.
+fn init_tracing(log: &LogArgs, timing: bool) -> Option<Arc<Mutex<Timings>>> {
+    let default_level = if log.quiet {
+        LevelFilter::ERROR
+    } else if log.verbose {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::WARN
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(default_level.into())
+        .from_env_lossy();
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_target(false)
+        .without_time()
+        .with_filter(filter);
+
+    let (timing_layer, timings) = if timing {
+        let (layer, timings) = TimingLayer::new();
+        (Some(layer), Some(timings))
+    } else {
+        (None, None)
+    };
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(timing_layer)
+        .init();
+    timings
+}
+
+/// Prints diagnostics to stderr, grouped by file.
+fn report_diagnostics(diagnostics: &[Diagnostic], format: DiagnosticsFormat, quiet: bool) {
+    let diagnostics: Vec<&Diagnostic> = diagnostics
         .iter()
-        .map(|arg| {
-            let mut arg_str = arg.def.arg.to_string();
-            if let Some(annotation) = &arg.def.annotation {
-                arg_str.push_str(&format!(": {}", extract_type(annotation)));
+        .filter(|diagnostic| !quiet || diagnostic.severity == Severity::Error)
+        .collect();
+    match format {
+        DiagnosticsFormat::Text => {
+            let mut by_file: BTreeMap<_, Vec<&Diagnostic>> = BTreeMap::new();
+            for diagnostic in diagnostics {
+                by_file
+                    .entry(&diagnostic.file)
+                    .or_default()
+                    .push(diagnostic);
+            }
+            for (file, diagnostics) in by_file {
+                eprintln!("{}:", normalize_path(file));
+                for diagnostic in diagnostics {
+                    eprintln!(
+                        "  {}: {}[{}]: {}",
+                        diagnostic.line,
+                        diagnostic.severity,
+                        diagnostic.category,
+                        diagnostic.message
+                    );
+                }
             }
-            arg_str
+        }
+        DiagnosticsFormat::Json => {
+            let json = serde_json::to_string_pretty(&diagnostics)
+                .expect("Failed to serialize diagnostics");
+            eprintln!("{}", json);
+        }
+    }
+}
+
+/// Reads a locale file, describing what went wrong otherwise.
+fn load_locale(path: &Path) -> Result<Strings, Error> {
+    let source = fs::read_to_string(path).map_err(|err| Error::read(path, err))?;
+    Strings::from_toml(&source)
+        .map_err(|err| Error::General(format!("Invalid locale {}: {}", path.display(), err)))
+}
+
+/// Time between checks of the input files for changes with `--watch`.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Changed lines of each out-of-date file shown by `--check`.
+const CHECK_DIFF_LINES: usize = 20;
+
+/// Page name of a package's `__init__.py` without `--index-name`.
+const DEFAULT_INDEX_NAME: &str = "index";
+
+/// Configuration file read from the current directory without `--config`.
+const DEFAULT_CONFIG: &str = "sdkdocgen.toml";
+
+/// Reads a configuration file, describing what went wrong otherwise.
+fn load_config(path: &Path) -> Result<Config, Error> {
+    let source = fs::read_to_string(path).map_err(|err| Error::read(path, err))?;
+    Config::from_toml(&source)
+        .map_err(|err| Error::General(format!("Invalid config {}: {}", path.display(), err)))
+}
+
+/// Reads and parses the input file.
+fn load_module(input_path: &Path, options: &RenderOptions) -> Result<ModuleDoc, Error> {
+    // Read the contents of the Python file
+    let mut code = read_source(input_path).map_err(|err| Error::read(input_path, err))?;
+    if is_notebook(input_path) {
+        code = read_notebook(input_path, &code)?.source;
+    }
+
+    // Parse the Python code
+    parse_module(&code, input_path, options).map_err(|err| Error::parse(input_path, &code, err))
+}
+
+/// Parses every file selected by `inputs` and not left out by `exclude`,
+/// as `--file` does when generating pages. Modules found in a directory or
+/// by a glob pattern are named by their path relative to it.
+fn load_selected(
+    inputs: &[PathBuf],
+    exclude: &[String],
+    follow_symlinks: bool,
+    options: &RenderOptions,
+) -> Result<Vec<ModuleDoc>, Error> {
+    selected_files(inputs, exclude, follow_symlinks)?
+        .iter()
+        .map(|(file, relative)| match relative {
+            Some(relative) => load_module(file, &package_options(options, relative)),
+            None => load_module(file, options),
         })
-        .collect();
-    func_str.push_str(&args.join(", "));
+        .collect()
+}
 
-    // Return annotation
-    if let Some(returns) = &func_def.returns {
-        func_str.push_str(&format!(" -> {}", extract_type(returns)));
+/// The cells of the notebook `json` read from `path`.
+fn read_notebook(path: &Path, json: &str) -> Result<Notebook, Error> {
+    Notebook::parse(json, path).map_err(|err| {
+        let message = err.to_string();
+        Error::Parse {
+            path: path.to_path_buf(),
+            line: err.line(),
+            column: err.column(),
+            message: match message.rsplit_once(" at line ") {
+                Some((message, _)) => message.to_string(),
+                None => message,
+            },
+        }
+    })
+}
+
+/// Parses a single file, or every `.py` file under a directory. Modules in
+/// a directory are named by their path relative to it.
+fn load_tree(root: &Path, options: &RenderOptions) -> Result<Vec<ModuleDoc>, Error> {
+    if !root.is_dir() {
+        return Ok(vec![load_module(root, options)?]);
     }
 
-    func_str.push_str("):\n");
+    discover_python_files(root, false)
+        .map_err(|err| Error::read(root, err))?
+        .iter()
+        .map(|file| {
+            let relative = file.strip_prefix(root).unwrap_or(file);
+            let mut module = load_module(file, &package_options(options, relative))?;
+            module.page = page_path(relative, DEFAULT_INDEX_NAME);
+            Ok(module)
+        })
+        .collect()
+}
+
+/// Options for a file at `relative` in a source directory: the module
+/// prefix is extended with the file's package.
+fn package_options(options: &RenderOptions, relative: &Path) -> RenderOptions {
+    let package = match dotted_module_name(relative).rsplit_once('.') {
+        Some((package, _)) => qualified_name(&options.module_prefix, package),
+        None => options.module_prefix.clone(),
+    };
+    RenderOptions {
+        module_prefix: package,
+        ..options.clone()
+    }
+}
 
-    // Docstring (if available)
-    if let Some(ast::Stmt::Expr(expr)) = func_def.body.first() {
-        if let ast::Expr::Constant(ast::ExprConstant {
-            value: ast::Constant::Str(docstring),
-            ..
-        }) = &*expr.value
-        {
-            func_str.push_str(&format!(
-                "    \"\"\"\n    {}\n    \"\"\"\n",
-                docstring.trim()
+/// Page of a file at `relative` in a source directory, mirroring the
+/// package layout: `pkg/sub/module.py` is `pkg/sub/module`, and the
+/// package's own `pkg/__init__.py` is `pkg/{index_name}`.
+fn page_path(relative: &Path, index_name: &str) -> String {
+    let page = if relative.file_stem() == Some("__init__".as_ref()) {
+        relative.with_file_name(index_name)
+    } else {
+        relative.with_extension("")
+    };
+    normalize_path(&page)
+}
+
+/// Describes the first two input files whose pages would overwrite each
+/// other, if there are any.
+fn page_collision(results: &[Result<PreparedModule, Error>], extension: &str) -> Option<String> {
+    let mut pages: BTreeMap<&str, &Path> = BTreeMap::new();
+    for prepared in results.iter().flatten() {
+        let module = &prepared.module;
+        if let Some(other) = pages.insert(&module.page, &module.path) {
+            return Some(format!(
+                "{} and {} would both be written to {}.{}",
+                normalize_path(other),
+                normalize_path(&module.path),
+                module.page,
+                extension
             ));
         }
     }
+    None
+}
 
-    // Function body
-    for stmt in &func_def.body {
-        match stmt {
-            ast::Stmt::Expr(expr) => {
-                // Skip the docstring, as it's already handled
-                if let ast::Expr::Constant(ast::ExprConstant {
-                    value: ast::Constant::Str(_),
-                    ..
-                }) = &*expr.value
-                {
-                    continue;
-                }
-                func_str.push_str(&format!("    {}\n", extract_type(&expr.value)));
-            }
-            ast::Stmt::Pass(_) => func_str.push_str("    pass\n"),
-            ast::Stmt::Return(ret) => {
-                if let Some(value) = &ret.value {
-                    func_str.push_str(&format!("    return {}\n", extract_type(value)));
+/// Prints one row per module with `documented/total` for each kind.
+fn print_coverage_table(reports: &[CoverageReport]) {
+    let header = [
+        "Module",
+        "Modules",
+        "Classes",
+        "Functions",
+        "Methods",
+        "Parameters",
+        "Returns",
+        "Total",
+    ];
+    let header = header.map(String::from).to_vec();
+
+    let cell = |counts: CoverageCounts| format!("{}/{}", counts.documented, counts.total);
+    let mut rows = vec![header];
+    let mut totals = [CoverageCounts::default(); SymbolKind::ALL.len()];
+    for report in reports {
+        let mut row = vec![report.module.clone()];
+        for (kind, total) in SymbolKind::ALL.iter().zip(&mut totals) {
+            let counts = report.counts(*kind);
+            *total += counts;
+            row.push(cell(counts));
+        }
+        row.push(format!("{:.1}%", report.total().percentage()));
+        rows.push(row);
+    }
+    let mut total_row = vec!["TOTAL".to_string()];
+    total_row.extend(totals.iter().map(|counts| cell(*counts)));
+    total_row.push(format!("{:.1}%", total_coverage(reports).percentage()));
+    rows.push(total_row);
+    print_table(&rows, 1);
+}
+
+/// Prints `rows` as aligned columns, the first `text_columns` aligned left
+/// and the others, holding numbers, aligned right.
+fn print_table(rows: &[Vec<String>], text_columns: usize) {
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].len()).max().unwrap_or(0))
+        .collect();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                if column < text_columns {
+                    format!("{:<width$}", cell, width = width)
                 } else {
-                    func_str.push_str("    return\n");
-                }
-            }
-            ast::Stmt::If(if_stmt) => {
-                func_str.push_str(&format!("    if {}:\n", extract_type(&if_stmt.test)));
-                for body_stmt in &if_stmt.body {
-                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(body_stmt)));
-                }
-                if !if_stmt.orelse.is_empty() {
-                    func_str.push_str("    else:\n");
-                    for else_stmt in &if_stmt.orelse {
-                        func_str.push_str(&format!("        {}\n", reconstruct_stmt(else_stmt)));
-                    }
+                    format!("{:>width$}", cell, width = width)
                 }
-            }
-            ast::Stmt::Assign(assign) => {
-                let targets: Vec<String> = assign
-                    .targets
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+/// Prints what a run would generate: the page of every input file and the
+/// classes and functions it would document, then the files that could not be
+/// read or parsed with their errors.
+fn print_plan(
+    files: &[(PathBuf, Option<PathBuf>)],
+    results: &[Result<PreparedModule, Error>],
+    output_path: &Path,
+    options: &RenderOptions,
+) {
+    let header = ["Input", "Output", "Classes", "Functions"];
+    let mut rows = vec![header.map(String::from).to_vec()];
+    let mut errors = Vec::new();
+    for ((file, _), result) in files.iter().zip(results) {
+        let row = match result {
+            Ok(prepared) => {
+                let module = &prepared.module;
+                let documented: Vec<&Item> = module
+                    .items
                     .iter()
-                    .map(|t| extract_type(&Box::new(t.clone())))
+                    .filter(|item| options.documents_export(module, item.name()))
                     .collect();
-                func_str.push_str(&format!(
-                    "    {} = {}\n",
-                    targets.join(", "),
-                    extract_type(&assign.value)
-                ));
+                let classes = documented
+                    .iter()
+                    .filter(|item| matches!(item, Item::Class(_)))
+                    .count();
+                let page =
+                    output_path.join(format!("{}.{}", module.page, options.format.extension()));
+                [
+                    normalize_path(file),
+                    normalize_path(&page),
+                    classes.to_string(),
+                    (documented.len() - classes).to_string(),
+                ]
             }
-            ast::Stmt::AugAssign(aug_assign) => {
-                func_str.push_str(&format!(
-                    "    {} {:?}= {}\n",
-                    extract_type(&aug_assign.target),
-                    aug_assign.op,
-                    extract_type(&aug_assign.value)
-                ));
+            Err(error) => {
+                errors.push(error);
+                let failure = match error {
+                    Error::Parse { .. } => "(fails to parse)",
+                    _ => "(fails to read)",
+                };
+                [
+                    normalize_path(file),
+                    failure.to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                ]
             }
-            ast::Stmt::For(for_stmt) => {
-                func_str.push_str(&format!(
-                    "    for {} in {}:\n",
-                    extract_type(&for_stmt.target),
-                    extract_type(&for_stmt.iter)
-                ));
-                for body_stmt in &for_stmt.body {
-                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(body_stmt)));
-                }
+        };
+        rows.push(row.to_vec());
+    }
+    print_table(&rows, 2);
+    for error in errors {
+        println!("{}", error);
+    }
+}
+
+fn total_coverage(reports: &[CoverageReport]) -> CoverageCounts {
+    reports
+        .iter()
+        .fold(CoverageCounts::default(), |mut total, report| {
+            total += report.total();
+            total
+        })
+}
+
+fn run_coverage(args: &CoverageArgs) -> Result<ExitCode, Error> {
+    init_tracing(&args.log, false);
+    let options = RenderOptions::from(args);
+    let modules = load_selected(&args.file, &args.exclude, args.follow_symlinks, &options)?;
+    let reports: Vec<_> = modules
+        .iter()
+        .map(|module| coverage(module, &options))
+        .collect();
+    let total = total_coverage(&reports);
+
+    print_coverage(&reports, args.format.into());
+
+    match args.fail_under {
+        Some(threshold) if total.percentage() < threshold => {
+            eprintln!(
+                "Coverage {:.1}% is below the threshold of {:.1}%",
+                total.percentage(),
+                threshold
+            );
+            Ok(ExitCode::FAILURE)
+        }
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+/// Prints the coverage of each module and the total, as a table or JSON.
+fn print_coverage(reports: &[CoverageReport], format: CoverageFormat) {
+    match format {
+        CoverageFormat::Text => print_coverage_table(reports),
+        CoverageFormat::Json => {
+            let total = total_coverage(reports);
+            let json = serde_json::json!({
+                "modules": reports
+                    .iter()
+                    .map(|report| {
+                        let counts: BTreeMap<String, CoverageCounts> = SymbolKind::ALL
+                            .iter()
+                            .map(|kind| (kind.to_string(), report.counts(*kind)))
+                            .collect();
+                        serde_json::json!({
+                            "module": report.module,
+                            "counts": counts,
+                            "percentage": report.total().percentage(),
+                            "undocumented": report.undocumented().collect::<Vec<_>>(),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+                "total": total,
+                "percentage": total.percentage(),
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("Failed to serialize coverage")
+            );
+        }
+    }
+}
+
+fn run_lint(args: &LintArgs) -> Result<ExitCode, Error> {
+    init_tracing(&args.log, false);
+    let options = RenderOptions::from(args);
+    let modules = load_selected(&args.file, &args.exclude, args.follow_symlinks, &options)?;
+    let findings: Vec<_> = modules
+        .iter()
+        .flat_map(|module| lint(module, &options))
+        .filter(|finding| args.select.is_empty() || args.select.contains(&finding.rule))
+        .filter(|finding| !args.ignore.contains(&finding.rule))
+        .collect();
+
+    match args.format {
+        OutputFormat::Text => {
+            for finding in &findings {
+                println!("{}", finding);
             }
-            ast::Stmt::While(while_stmt) => {
-                func_str.push_str(&format!("    while {}:\n", extract_type(&while_stmt.test)));
-                for body_stmt in &while_stmt.body {
-                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(body_stmt)));
-                }
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&findings).expect("Failed to serialize findings")
+        ),
+    }
+
+    if findings.is_empty() {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        if args.format == OutputFormat::Text {
+            eprintln!("Found {} problem(s)", findings.len());
+        }
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn run_diff(args: &DiffArgs) -> Result<ExitCode, Error> {
+    init_tracing(&args.log, false);
+    let options = RenderOptions::from(args);
+    let old = load_tree(&args.old_path, &options)?;
+    let new = load_tree(&args.new_path, &options)?;
+    let changes = diff_api(&old, &new, &options);
+
+    match args.format {
+        DiffFormat::Markdown => print!("{}", render_changelog(&changes)),
+        DiffFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&changes).expect("Failed to serialize changes")
+        ),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Exit codes, listed at the end of `--help`.
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  Success
+  1  Invalid arguments or settings, or a check failed (lint findings, coverage, stale pages, ...)
+  2  A Python file failed to parse
+  3  A file could not be read or written";
+
+/// An error ending a run, or leaving a file out of it.
+#[derive(Debug)]
+enum Error {
+    /// Invalid settings or inputs, such as a malformed configuration file.
+    General(String),
+    /// A Python file that is not valid syntax, with the line and column of
+    /// the error, both starting at 1.
+    Parse {
+        path: PathBuf,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// A file or directory that could not be read.
+    Read { path: PathBuf, source: io::Error },
+    /// A page or directory that could not be written.
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl Error {
+    fn read(path: &Path, source: io::Error) -> Self {
+        Error::Read {
+            path: path.to_path_buf(),
+            source,
+        }
+    }
+
+    /// The error of parsing `source`, the content of `path`.
+    fn parse(path: &Path, source: &str, err: ParseError) -> Self {
+        let location = LineIndex::from_source_text(source).source_location(err.offset, source);
+        Error::Parse {
+            path: path.to_path_buf(),
+            line: location.row.to_usize(),
+            column: location.column.to_usize(),
+            message: err.error.to_string(),
+        }
+    }
+
+    /// The exit code of a run that fails with this error.
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::General(_) => ExitCode::FAILURE,
+            Error::Parse { .. } => ExitCode::from(2),
+            Error::Read { .. } | Error::Write { .. } => ExitCode::from(3),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::General(message) => f.write_str(message),
+            Error::Parse {
+                path,
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Failed to parse {}:{}:{}: {}",
+                path.display(),
+                line,
+                column,
+                message
+            ),
+            Error::Read { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
             }
-            ast::Stmt::Raise(raise) => {
-                if let Some(exc) = &raise.exc {
-                    func_str.push_str(&format!("    raise {}\n", extract_type(exc)));
-                } else {
-                    func_str.push_str("    raise\n");
-                }
+            Error::Write { path, source } => {
+                write!(f, "Failed to write {}: {}", path.display(), source)
             }
-            _ => func_str.push_str(&format!("    # Unhandled statement: {:?}\n", stmt)),
         }
     }
-
-    func_str
 }
 
-fn reconstruct_stmt(stmt: &ast::Stmt) -> String {
-    match stmt {
-        ast::Stmt::Expr(expr) => extract_type(&expr.value),
-        ast::Stmt::Pass(_) => "pass".to_string(),
-        ast::Stmt::Return(ret) => {
-            if let Some(value) = &ret.value {
-                format!("return {}", extract_type(value))
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) => {
+            // A usage error is invalid settings, not a file that failed to
+            // parse, which is what clap's own status of 2 would say.
+            let _ = err.print();
+            return if err.use_stderr() {
+                ExitCode::FAILURE
             } else {
-                "return".to_string()
+                ExitCode::SUCCESS
+            };
+        }
+    };
+    let result = match &cli.command {
+        Some(Command::Coverage(args)) => run_coverage(args),
+        Some(Command::Lint(args)) => run_lint(args),
+        Some(Command::Diff(args)) => run_diff(args),
+        None => run_generate(&cli.args),
+    };
+    result.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        err.exit_code()
+    })
+}
+
+fn run_generate(args: &Args) -> Result<ExitCode, Error> {
+    let config_path = args.config.clone().or_else(|| {
+        let default = PathBuf::from(DEFAULT_CONFIG);
+        default.is_file().then_some(default)
+    });
+    let config = match config_path.as_deref().map(load_config) {
+        Some(config) => config?,
+        None => Config::default(),
+    };
+    let args = &args.merge(&config);
+    let timings = init_tracing(&args.log, args.timing);
+    let mut options = RenderOptions::default();
+    config.apply(&mut options);
+    args.apply(&mut options);
+    if args.print_config {
+        print!("{}", args.effective_config(&options).to_toml());
+        return Ok(ExitCode::SUCCESS);
+    }
+    if args.file.is_empty() && !args.stdin {
+        return Err(Error::General(
+            "No input: pass --file or --stdin, or set `file` in the configuration file".to_string(),
+        ));
+    }
+    if args.output_path.is_none() && !args.stdout && !args.dry_run {
+        return Err(Error::General(
+            "No output: pass --output-path or --stdout, or set `output_path` in the configuration file"
+                .to_string(),
+        ));
+    }
+    if let Some(template) = &args.output_template {
+        let extension = options.format.extension();
+        if !template.ends_with_extension(extension) {
+            return Err(Error::General(format!(
+                "The output template `{}` must end in `.{{ext}}` or `.{}`",
+                template, extension
+            )));
+        }
+    }
+    if args.nav_format == Some(NavFormatArg::Mdbook) && options.format != Format::Markdown {
+        return Err(Error::General(
+            "--nav-format mdbook needs --format markdown, as mdBook cannot render MDX".to_string(),
+        ));
+    }
+    if let Some(path) = &args.locale {
+        options.strings = load_locale(path)?;
+    }
+    let files = selected_files(&args.file, &args.exclude, args.follow_symlinks)?;
+    if args.title.is_some() && files.len() > 1 {
+        return Err(Error::General(
+            "--title needs a single input file".to_string(),
+        ));
+    }
+    let examples = args.examples_from.as_ref().map(|dir| {
+        let examples = Examples::collect(dir);
+        if examples.is_empty() {
+            tracing::warn!("no examples found under {}", dir.display());
+        }
+        examples
+    });
+    let pyproject = files.first().and_then(|(file, _)| pyproject_version(file));
+    if let Some((file, version)) = pyproject {
+        match &options.doc_version {
+            Some(doc_version) => tracing::debug!(
+                "using version {} over {} from {}",
+                doc_version,
+                version,
+                normalize_path(&file)
+            ),
+            None => {
+                tracing::debug!("using version {} from {}", version, normalize_path(&file));
+                options.doc_version = Some(version);
+            }
+        }
+    }
+    let output_path = args.output_path.clone().unwrap_or_default();
+    if args.watch {
+        if args.cache {
+            tracing::warn!("the cache is not used with --watch");
+        }
+        watch(args, &options, &output_path, examples.as_ref());
+    }
+
+    let use_cache = args.cache && !args.stdin && !args.stdout && !args.check && !args.dry_run;
+    let mut new_cache = Cache::default();
+    let mut cache = None;
+    if use_cache {
+        new_cache = Cache {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            settings: settings_hash(args, &options, examples.as_ref()),
+            inputs: input_hashes(args, &files),
+            pages: BTreeMap::new(),
+        };
+        cache = Cache::load(&output_path).filter(|_| !args.force);
+        if let Some(cache) = &cache {
+            let pages_exist = cache
+                .pages
+                .keys()
+                .all(|page| output_path.join(page).is_file());
+            if cache.version == new_cache.version
+                && cache.settings == new_cache.settings
+                && cache.inputs == new_cache.inputs
+                && pages_exist
+            {
+                if !args.log.quiet {
+                    eprintln!(
+                        "{} input file(s) unchanged, nothing to regenerate",
+                        files.len()
+                    );
+                }
+                return Ok(ExitCode::SUCCESS);
             }
         }
-        ast::Stmt::If(if_stmt) => {
-            let mut if_str = format!("if {}:\n", extract_type(&if_stmt.test));
-            for body_stmt in &if_stmt.body {
-                if_str.push_str(&format!("    {}\n", reconstruct_stmt(body_stmt)));
+    }
+
+    let mut prepared = Vec::new();
+    let mut failed = Vec::new();
+    let mut add = |result: Result<PreparedModule, Error>| match result {
+        Ok(module) => prepared.push(module),
+        Err(error) => {
+            eprintln!("{}", error);
+            failed.push(error);
+        }
+    };
+    if args.stdin {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .read_to_end(&mut bytes)
+            .map_err(|err| Error::read(Path::new("standard input"), err))?;
+        let source = decode_source(&bytes, Path::new("stdin"));
+        add(prepare_module(
+            args,
+            &options,
+            &source,
+            Path::new("stdin"),
+            None,
+            examples.as_ref(),
+        ));
+    }
+    let results = parallel_map(&files, args.jobs(), |(file, relative)| {
+        read_source(file)
+            .map_err(|err| Error::read(file, err))
+            .and_then(|source| {
+                prepare_module(
+                    args,
+                    &options,
+                    &source,
+                    file,
+                    relative.as_deref(),
+                    examples.as_ref(),
+                )
+            })
+    });
+    if let Some(message) = page_collision(&results, options.format.extension()) {
+        return Err(Error::General(message));
+    }
+    if args.dry_run {
+        print_plan(&files, &results, &output_path, &options);
+        let failed = results.iter().find_map(|result| result.as_ref().err());
+        return Ok(failed.map_or(ExitCode::SUCCESS, Error::exit_code));
+    }
+    results.into_iter().for_each(add);
+
+    let dunder_version = prepared
+        .iter()
+        .find_map(|prepared| Some((&prepared.module, prepared.module.version.as_ref()?)));
+    if let Some((module, version)) = dunder_version {
+        match &options.doc_version {
+            Some(doc_version) => tracing::debug!(
+                "using version {} over `__version__` {} in {}",
+                doc_version,
+                version,
+                normalize_path(&module.path)
+            ),
+            None => {
+                tracing::debug!(
+                    "using `__version__` {} in {}",
+                    version,
+                    normalize_path(&module.path)
+                );
+                options.doc_version = Some(version.clone());
             }
-            if !if_stmt.orelse.is_empty() {
-                if_str.push_str("else:\n");
-                for else_stmt in &if_stmt.orelse {
-                    if_str.push_str(&format!("    {}\n", reconstruct_stmt(else_stmt)));
+        }
+    }
+
+    let (project, pages) = render_pages(prepared, args, &output_path, &options);
+    let extension = options.format.extension();
+
+    let mut stale = false;
+    if args.check {
+        let stale_files = stale_pages(&pages, &output_path, extension, CHECK_DIFF_LINES);
+        for file in &stale_files {
+            println!("{}", file);
+        }
+        if !stale_files.is_empty() {
+            eprintln!("{} file(s) are out of date", stale_files.len());
+            stale = true;
+        }
+    } else if args.stdout {
+        for (index, (output_file, markdown_content)) in pages.iter().enumerate() {
+            if pages.len() > 1 {
+                if index > 0 {
+                    println!();
                 }
+                println!("==> {} <==", normalize_path(output_file));
             }
-            if_str
+            print!("{}", markdown_content);
         }
-        ast::Stmt::Assign(assign) => {
-            let targets: Vec<String> = assign
-                .targets
+    } else {
+        // Write the Markdown content to the files, except those the cache
+        // shows are already on disk
+        let changed: Vec<&(PathBuf, String)> = pages
+            .iter()
+            .filter(|(output_file, markdown_content)| {
+                let key = cache_key(output_file, &output_path);
+                !cache.as_ref().is_some_and(|cache| {
+                    cache.has_page(&key, markdown_content) && output_file.is_file()
+                })
+            })
+            .collect();
+        parallel_map(&changed, args.jobs(), |(output_file, markdown_content)| {
+            write_page(output_file, markdown_content)
+        })
+        .into_iter()
+        .collect::<Result<(), Error>>()?;
+        if !args.log.quiet {
+            for (output_file, _) in &changed {
+                println!("Markdown file generated: {:?}", output_file);
+            }
+        }
+        if use_cache && !args.log.quiet {
+            eprintln!(
+                "Regenerated {} page(s), skipped {} unchanged",
+                changed.len(),
+                pages.len() - changed.len()
+            );
+        }
+        if use_cache {
+            new_cache.pages = pages
                 .iter()
-                .map(|t| extract_type(&Box::new(t.clone())))
+                .map(|(output_file, markdown_content)| {
+                    (
+                        cache_key(output_file, &output_path),
+                        content_hash(markdown_content.as_bytes()),
+                    )
+                })
                 .collect();
-            format!("{} = {}", targets.join(", "), extract_type(&assign.value))
-        }
-        ast::Stmt::AugAssign(aug_assign) => {
-            format!(
-                "{} {:?}= {}",
-                extract_type(&aug_assign.target),
-                aug_assign.op,
-                extract_type(&aug_assign.value)
-            )
-        }
-        ast::Stmt::Raise(raise) => {
-            if let Some(exc) = &raise.exc {
-                format!("raise {}", extract_type(exc))
-            } else {
-                "raise".to_string()
+        }
+    }
+    if (files.len() > 1 || !failed.is_empty()) && !args.log.quiet {
+        eprintln!(
+            "Generated {} module(s), {} failed",
+            project.modules().len(),
+            failed.len()
+        );
+    }
+
+    let mut diagnostics: Vec<Diagnostic> = project
+        .modules()
+        .iter()
+        .flat_map(|module| module.diagnostics.iter().cloned())
+        .chain(project.diagnostics().iter().cloned())
+        .collect();
+    if args.check_links {
+        diagnostics.extend(check_links(&pages));
+    }
+    report_diagnostics(&diagnostics, args.diagnostics_format(), args.log.quiet);
+    if args.coverage {
+        let reports: Vec<CoverageReport> = project
+            .modules()
+            .iter()
+            .map(|module| coverage(module, &options))
+            .collect();
+        print_coverage(
+            &reports,
+            args.coverage_format.map(Into::into).unwrap_or_default(),
+        );
+    }
+    let mut undocumented = false;
+    if let Some(requirement) = args.require_docstrings.map(DocstringRequirement::from) {
+        let (level, fails) = match requirement {
+            DocstringRequirement::Error => ("error", true),
+            DocstringRequirement::Warn => ("warning", false),
+        };
+        for module in project.modules() {
+            for finding in missing_docstrings(module, &options) {
+                undocumented |= fails;
+                if fails || !args.log.quiet {
+                    eprintln!(
+                        "{}: {}::{} (line {}) has no docstring",
+                        level,
+                        normalize_path(&finding.file),
+                        finding.symbol,
+                        finding.line
+                    );
+                }
             }
         }
-        _ => format!("# Unhandled statement: {:?}", stmt),
     }
+    if let Some(timings) = timings {
+        eprint!("{}", timings.lock().unwrap().report());
+    }
+    if let Some(error) = failed.first() {
+        return Ok(error.exit_code());
+    }
+    if stale || undocumented || (args.strict && !diagnostics.is_empty()) {
+        return Ok(ExitCode::FAILURE);
+    }
+    // Only a successful run is cached, so a failing one is not skipped
+    // next time
+    if use_cache {
+        if let Err(err) = new_cache.save(&output_path) {
+            eprintln!("Failed to write {}: {}", CACHE_FILE, err);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
 }
 
-fn main() {
-    let args = Args::parse();
+/// Hash of everything besides the input files that affects the generated
+/// pages, for the cache.
+fn settings_hash(args: &Args, options: &RenderOptions, examples: Option<&Examples>) -> String {
+    let config = Config {
+        jobs: None,
+        timing: None,
+        cache: None,
+        ..args.effective_config(options)
+    };
+    let settings = format!("{}\n{:?}\n{:?}", config.to_toml(), options, examples);
+    content_hash(settings.as_bytes())
+}
 
-    // Read the contents of the Python file
-    let code = fs::read_to_string(&args.file).expect("Failed to read the Python file");
+/// Hash of each input file and of its stubs, next to it and in
+/// `--stubs-dir`, by path. Files that cannot be read are left out.
+fn input_hashes(args: &Args, files: &[(PathBuf, Option<PathBuf>)]) -> BTreeMap<String, String> {
+    files
+        .iter()
+        .flat_map(|(file, relative)| {
+            let stub = args.stub_file(file, relative.as_deref());
+            [file.clone(), file.with_extension("pyi")]
+                .into_iter()
+                .chain(stub)
+        })
+        .filter_map(|file| {
+            let content = fs::read(&file).ok()?;
+            Some((normalize_path(&file), content_hash(&content)))
+        })
+        .collect()
+}
 
-    // Parse the Python code
-    let ast = ast::Suite::parse(&code, "<string>").expect("Failed to parse the Python file");
-
-    // Create the output directory if it doesn't exist
-    fs::create_dir_all(&args.output_path).expect("Failed to create output directory");
-
-    // Generate the output file path
-    let input_path = PathBuf::from(&args.file);
-    let file_name = input_path
-        .file_stem()
-        .expect("Invalid file name")
-        .to_str()
-        .expect("Invalid file name");
-    let output_file = args.output_path.join(format!("{}.mdx", file_name));
-
-    // Extract the docstrings and write to the Markdown file
-    let mut markdown_content = String::new();
-
-    // Add the module header
-    markdown_content.push_str("---\n");
-    markdown_content.push_str(&format!("title: {}\n", file_name));
-    markdown_content.push_str("---\n\n");
-    markdown_content.push_str(&format!("## `zenml.{}` `special`\n\n", file_name));
-
-    // Add the module docstring if it exists
-    if let Some(first_stmt) = ast.first() {
-        if let ast::Stmt::Expr(expr) = first_stmt {
-            if let ast::Expr::Constant(ast::ExprConstant {
-                value: ast::Constant::Str(docstring),
-                ..
-            }) = &*expr.value
-            {
-                markdown_content.push_str(&format!("{}\n\n", docstring));
+/// Path of a page in the cache, relative to the output directory.
+fn cache_key(file: &Path, output_path: &Path) -> String {
+    normalize_path(file.strip_prefix(output_path).unwrap_or(file))
+}
+
+/// Every input file selected by `--file` and not left out by `--exclude`,
+/// with its path relative to the directory or glob pattern it was found
+/// under. A file selected twice is kept once.
+fn selected_files(
+    inputs: &[PathBuf],
+    exclude: &[String],
+    follow_symlinks: bool,
+) -> Result<Vec<(PathBuf, Option<PathBuf>)>, Error> {
+    let mut files = Vec::new();
+    let mut seen = BTreeSet::new();
+    for input in inputs {
+        files.extend(
+            input_files(input, follow_symlinks)?
+                .into_iter()
+                .filter(|(file, _)| {
+                    seen.insert(fs::canonicalize(file).unwrap_or_else(|_| file.clone()))
+                }),
+        );
+    }
+    files.retain(|(file, relative)| {
+        let path = relative.as_deref().unwrap_or(file);
+        match exclude.iter().find(|pattern| glob_matches(pattern, path)) {
+            Some(pattern) => {
+                tracing::debug!("excluding {} (matches {})", normalize_path(file), pattern);
+                false
             }
+            None => true,
+        }
+    });
+    Ok(files)
+}
+
+/// Renders the module, CLI and route pages of `prepared` under
+/// `output_path`, on up to `--jobs` threads, along with the landing pages
+/// of package directories and the navigation file asked for. Returns the
+/// project the modules were linked in and the pages as output file and
+/// content.
+fn render_pages(
+    prepared: Vec<PreparedModule>,
+    args: &Args,
+    output_path: &Path,
+    options: &RenderOptions,
+) -> (Project, Vec<(PathBuf, String)>) {
+    let mut modules = Vec::new();
+    let mut commands = Vec::new();
+    let mut endpoints = BTreeMap::new();
+    for prepared in prepared {
+        if !prepared.endpoints.is_empty() {
+            endpoints.insert(prepared.module.page.clone(), prepared.endpoints);
+        }
+        commands.extend(prepared.commands);
+        match &options.only {
+            Some(only) if prepared.module.items.is_empty() => tracing::debug!(
+                "skipping {}: no class or function matches `{}`",
+                normalize_path(&prepared.module.path),
+                only
+            ),
+            _ => modules.push(prepared.module),
         }
     }
+    let project = Project::new(modules);
+    let extension = options.format.extension();
 
-    // Extract class and function docstrings
-    for (_index, stmt) in ast.iter().enumerate() {
-        if let ast::Stmt::ClassDef(class_def) = stmt {
-            markdown_content.push_str(&format!("### `{}`\n", class_def.name));
-            markdown_content.push_str(" ([Integration](/integrations-integration/#zenml.integrations.integration.Integration \"zenml.integrations.integration.Integration\"))\n\n");
+    let index_name = args.index_name();
 
-            if let Some(first_stmt) = class_def.body.first() {
-                if let ast::Stmt::Expr(expr) = first_stmt {
-                    if let ast::Expr::Constant(ast::ExprConstant {
-                        value: ast::Constant::Str(docstring),
-                        ..
-                    }) = &*expr.value
-                    {
-                        markdown_content.push_str(&format!("{}\n", docstring));
+    let mut pages = parallel_map(project.modules(), args.jobs(), |module| {
+        let file = output_path.join(format!("{}.{}", module.page, extension));
+        (file, render_module(module, &project, options))
+    });
+    if args.package_index {
+        for directory in package_directories(&project) {
+            let listing = package_listing(&project, &directory, index_name, options);
+            if listing.is_empty() {
+                continue;
+            }
+            let page = index_page(&directory, index_name);
+            let file = output_path.join(format!("{}.{}", page, extension));
+            match pages.iter_mut().find(|(existing, _)| *existing == file) {
+                Some((_, content)) => {
+                    if !content.ends_with("\n\n") {
+                        content.push('\n');
                     }
+                    content.push_str(&listing);
+                }
+                None => {
+                    let content = render_package_index(&project, &directory, index_name, options);
+                    pages.push((file, content));
                 }
             }
+        }
+    }
+    for command in &commands {
+        let file = output_path
+            .join("cli")
+            .join(format!("{}.{}", command.name, extension));
+        pages.push((file, render_cli_page(command)));
+    }
+    for (page, endpoints) in &endpoints {
+        let file = output_path
+            .join("routes")
+            .join(format!("{}.{}", page, extension));
+        let title = page.rsplit('/').next().unwrap_or(page);
+        pages.push((file, render_routes_page(title, endpoints)));
+    }
+    if let Some(format) = args.nav_format {
+        let nav_pages: Vec<NavPage> = pages
+            .iter()
+            .map(|(file, _)| {
+                let relative = normalize_path(file.strip_prefix(output_path).unwrap_or(file));
+                let page = relative
+                    .strip_suffix(&format!(".{}", extension))
+                    .unwrap_or(&relative)
+                    .to_string();
+                let title = nav_title(&project, &page, index_name);
+                NavPage { page, title }
+            })
+            .collect();
+        let nav = render_nav(format.into(), &nav_pages, extension, index_name);
+        pages.push((output_path.join(NavFormat::from(format).file_name()), nav));
+    }
+    (project, pages)
+}
 
-            markdown_content.push_str(&format!(
-                "<Accordion\n  title=\"Source code in `zenml/{}/{}.py`\"\n>\n",
-                file_name, file_name
-            ));
-            markdown_content.push_str("```py\n");
-            // Reconstruct the class definition
-            markdown_content.push_str(&format!("class {}:\n", class_def.name));
-            for stmt in &class_def.body {
-                if let ast::Stmt::FunctionDef(func_def) = stmt {
-                    markdown_content.push_str(&reconstruct_function_def(func_def));
+/// Title of `page` in the navigation: the name of its module, the package
+/// for the page of an `__init__.py` or a directory's landing page, and
+/// otherwise the file name.
+fn nav_title(project: &Project, page: &str, index_name: &str) -> String {
+    let module = project.modules().iter().find(|module| module.page == page);
+    if let Some(module) = module {
+        let name = module.import_path().rsplit('.').next();
+        return name.unwrap_or(&module.name).to_string();
+    }
+    let mut segments = page.rsplit('/');
+    match segments.next() {
+        Some(name) if name == index_name => segments.next().unwrap_or(name).to_string(),
+        name => name.unwrap_or(page).to_string(),
+    }
+}
+
+/// Applies `f` to every item on up to `jobs` threads. The results are in
+/// the order of `items`, however the work was scheduled.
+fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    jobs: NonZeroUsize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let workers = jobs.get().min(items.len());
+    if workers <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break done;
+                        };
+                        done.push((index, f(item)));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Worker thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn write_page(file: &Path, content: &str) -> Result<(), Error> {
+    tracing::info_span!("write", file = %normalize_path(file)).in_scope(|| {
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir).map_err(|source| Error::Write {
+                path: dir.to_path_buf(),
+                source,
+            })?;
+        }
+        fs::write(file, content).map_err(|source| Error::Write {
+            path: file.to_path_buf(),
+            source,
+        })
+    })
+}
+
+/// Regenerates the pages whenever an input file changes, until the process
+/// is interrupted. Only the files modified since the last round are parsed
+/// again, and only the pages whose content changed are written. A file that
+/// fails to parse is reported and left out until it changes again.
+fn watch(
+    args: &Args,
+    options: &RenderOptions,
+    output_path: &Path,
+    examples: Option<&Examples>,
+) -> ! {
+    let mut prepared: BTreeMap<PathBuf, (Option<SystemTime>, Option<PreparedModule>)> =
+        BTreeMap::new();
+    let mut written: BTreeMap<PathBuf, String> = BTreeMap::new();
+    // The page each input file was last rendered to, kept while it fails
+    // to parse so that a syntax error does not remove its page.
+    let mut page_of: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    let mut last_error = None;
+    if !args.log.quiet {
+        eprintln!("Watching for changes, press Ctrl-C to stop");
+    }
+    loop {
+        let started = Instant::now();
+        let files = match selected_files(&args.file, &args.exclude, args.follow_symlinks) {
+            Ok(files) => files,
+            Err(error) => {
+                let message = error.to_string();
+                if last_error.as_ref() != Some(&message) {
+                    eprintln!("{}", message);
+                    last_error = Some(message);
                 }
+                thread::sleep(WATCH_INTERVAL);
+                continue;
             }
-            markdown_content.push_str("```\n");
-            markdown_content.push_str("</Accordion>\n\n");
-
-            // Extract methods
-            for stmt in &class_def.body {
-                if let ast::Stmt::FunctionDef(func_def) = stmt {
-                    markdown_content.push_str(&format!(
-                        "#### `{}()` `{}`\n\n",
-                        func_def.name,
-                        if is_classmethod(&func_def.decorator_list) {
-                            "classmethod"
-                        } else {
-                            ""
-                        }
-                    ));
-
-                    // Add the arguments table
-                    markdown_content.push_str(&format_args_table(&func_def.args));
-
-                    if let Some(first_stmt) = func_def.body.first() {
-                        if let ast::Stmt::Expr(expr) = first_stmt {
-                            if let ast::Expr::Constant(ast::ExprConstant {
-                                value: ast::Constant::Str(docstring),
-                                ..
-                            }) = &*expr.value
-                            {
-                                markdown_content.push_str(&format!("{}\n", docstring));
-                            }
-                        }
-                    }
+        };
+        last_error = None;
 
-                    markdown_content.push_str(&format!(
-                        "<Accordion\n  title=\"Source code in `zenml/{}/{}.py`\"\n\n>\n",
-                        file_name, file_name
-                    ));
-                    markdown_content.push_str("```py\n");
-                    markdown_content.push_str(&reconstruct_function_def(func_def));
-                    markdown_content.push_str("```\n");
-                    markdown_content.push_str("</Accordion>\n\n");
-
-                    // Add the returns table
-                    markdown_content.push_str(&format_returns_table(&func_def.returns));
+        let mut changed = 0;
+        let before = prepared.len();
+        prepared.retain(|file, _| files.iter().any(|(selected, _)| selected == file));
+        page_of.retain(|file, _| prepared.contains_key(file));
+        changed += before - prepared.len();
+        let mut diagnostics = Vec::new();
+        for (file, relative) in &files {
+            let modified = fs::metadata(file).and_then(|meta| meta.modified()).ok();
+            if prepared
+                .get(file)
+                .is_some_and(|(seen, _)| *seen == modified)
+            {
+                continue;
+            }
+            let result = read_source(file)
+                .map_err(|err| Error::read(file, err))
+                .and_then(|source| {
+                    prepare_module(args, options, &source, file, relative.as_deref(), examples)
+                });
+            let module = match result {
+                Ok(module) => {
+                    diagnostics.extend(module.module.diagnostics.iter().cloned());
+                    let page = format!("{}.{}", module.module.page, options.format.extension());
+                    page_of.insert(file.clone(), output_path.join(page));
+                    Some(module)
                 }
+                Err(error) => {
+                    eprintln!("{}", error);
+                    None
+                }
+            };
+            prepared.insert(file.clone(), (modified, module));
+            changed += 1;
+        }
+        if changed == 0 {
+            thread::sleep(WATCH_INTERVAL);
+            continue;
+        }
+
+        let modules = files
+            .iter()
+            .filter_map(|(file, _)| prepared.get(file)?.1.clone())
+            .collect();
+        let (_, pages) = render_pages(modules, args, output_path, options);
+        let mut removed = 0;
+        written.retain(|file, _| {
+            if pages.iter().any(|(page, _)| page == file)
+                || page_of.values().any(|page| page == file)
+            {
+                return true;
             }
-        } else if let ast::Stmt::FunctionDef(_) = stmt {
-            markdown_content.push_str(&format_function_doc(stmt));
+            match fs::remove_file(file) {
+                Ok(()) => {
+                    tracing::info!("removed {}", normalize_path(file));
+                    removed += 1;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => eprintln!("Failed to remove {}: {}", file.display(), err),
+            }
+            false
+        });
+        let mut updated = 0;
+        for (file, content) in pages {
+            if written.get(&file) == Some(&content) {
+                continue;
+            }
+            match write_page(&file, &content) {
+                Ok(()) => {
+                    written.insert(file, content);
+                    updated += 1;
+                }
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+        report_diagnostics(&diagnostics, args.diagnostics_format(), args.log.quiet);
+        if !args.log.quiet {
+            eprintln!(
+                "[{}] {} file(s) changed, {} page(s) written, {} removed in {} ms",
+                rfc3339(now()),
+                changed,
+                updated,
+                removed,
+                started.elapsed().as_millis()
+            );
+        }
+    }
+}
+
+/// Files selected by `--file`, each with its path relative to the
+/// directory or the literal part of the glob pattern it was found under.
+/// A single file has no relative path.
+fn input_files(
+    input: &Path,
+    follow_symlinks: bool,
+) -> Result<Vec<(PathBuf, Option<PathBuf>)>, Error> {
+    let pattern = input.to_string_lossy();
+    let (base, files) = if is_glob(&pattern) {
+        let files =
+            expand_glob(&pattern, follow_symlinks).map_err(|err| Error::read(input, err))?;
+        if files.is_empty() {
+            return Err(Error::General(format!(
+                "No files match the pattern {}",
+                pattern
+            )));
+        }
+        (glob_base(&pattern), files)
+    } else if input.is_dir() {
+        let files = tracing::info_span!("discover")
+            .in_scope(|| discover_python_files(input, follow_symlinks))
+            .map_err(|err| Error::read(input, err))?;
+        (input.to_path_buf(), files)
+    } else {
+        return Ok(vec![(input.to_path_buf(), None)]);
+    };
+    Ok(files
+        .into_iter()
+        .map(|file| {
+            let relative = file.strip_prefix(&base).unwrap_or(&file).to_path_buf();
+            (file, Some(relative))
+        })
+        .collect())
+}
+
+/// A parsed module ready to be rendered, with the CLI commands and routes
+/// documented on pages of their own.
+#[derive(Clone)]
+struct PreparedModule {
+    module: ModuleDoc,
+    commands: Vec<CliCommand>,
+    endpoints: Vec<Endpoint>,
+}
+
+/// Parses the `source` of `file` and applies stubs, re-exports, examples
+/// and the `--cli` and `--routes` extraction to it. A file found in a
+/// directory is named by its path `relative` to the directory.
+fn prepare_module(
+    args: &Args,
+    options: &RenderOptions,
+    source: &str,
+    file: &Path,
+    relative: Option<&Path>,
+    examples: Option<&Examples>,
+) -> Result<PreparedModule, Error> {
+    let options = match relative {
+        Some(relative) => package_options(options, relative),
+        None => options.clone(),
+    };
+    let notebook = if is_notebook(file) {
+        Some(read_notebook(file, source)?)
+    } else {
+        None
+    };
+    let source = notebook
+        .as_ref()
+        .map_or(source, |notebook| notebook.source.as_str());
+    let parse_error = |err| Error::parse(file, source, err);
+    let mut module = parse_module(source, file, &options).map_err(parse_error)?;
+    if args.include_notebook_markdown {
+        module.prose = notebook
+            .iter()
+            .flat_map(|notebook| notebook.markdown.clone())
+            .collect();
+    }
+    if let Some(relative) = relative {
+        module.page = page_path(relative, args.index_name());
+        if args.flat {
+            module.page = module
+                .page
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+        }
+    }
+    if let Some(template) = &args.output_template {
+        module.page = template.page(&module, options.format.extension());
+    }
+    if let Some(title) = &args.title {
+        module.title = Some(title.clone());
+    }
+    for item in &module.items {
+        if !options.documents_export(&module, item.name()) {
+            let reason = if module.exports.is_some() && options.respect_all {
+                "not listed in `__all__`"
+            } else {
+                "private"
+            };
+            tracing::debug!("skipping `{}` in {}: {}", item.name(), module.name, reason);
         }
     }
+    let merged = args
+        .stub_file(file, relative)
+        .is_some_and(|stub| merge_stub_signatures(&mut module, &stub, &options, args.prefer()));
+    if !merged {
+        merge_adjacent_stub(&mut module, &options, args.prefer());
+    }
+    inline_private_reexports(&mut module, &options);
+    if let Some(examples) = examples {
+        attach_examples(&mut module, examples);
+    }
 
-    // Write the Markdown content to the file
-    fs::write(&output_file, markdown_content).expect("Failed to write Markdown file");
+    let commands = if args.cli {
+        let commands = extract_cli(source, &module.path).map_err(parse_error)?;
+        if commands.is_empty() {
+            tracing::warn!("no Click or argparse commands found in {}", file.display());
+        }
+        let functions: BTreeSet<&str> = commands.iter().flat_map(CliCommand::functions).collect();
+        module.items.retain(
+            |item| !matches!(item, Item::Function(f) if functions.contains(f.name.as_str())),
+        );
+        commands
+    } else {
+        Vec::new()
+    };
+    let endpoints = if args.routes {
+        let endpoints = extract_routes(source, &module.path).map_err(parse_error)?;
+        if endpoints.is_empty() {
+            tracing::warn!("no FastAPI or Flask routes found in {}", file.display());
+        }
+        let handlers: BTreeSet<&str> = endpoints
+            .iter()
+            .map(|endpoint| endpoint.function.as_str())
+            .collect();
+        module.items.retain(
+            |item| !matches!(item, Item::Function(f) if handlers.contains(f.name.as_str())),
+        );
+        endpoints
+    } else {
+        Vec::new()
+    };
 
-    println!("Markdown file generated: {:?}", output_file);
+    Ok(PreparedModule {
+        module,
+        commands,
+        endpoints,
+    })
 }
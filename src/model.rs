@@ -0,0 +1,560 @@
+//! The documentation model produced by the parse stage.
+
+use std::path::PathBuf;
+
+use crate::diagnostics::Diagnostic;
+use crate::docstring::{Docstring, VersionMarker};
+
+/// Everything extracted from a single Python module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleDoc {
+    /// Module name, taken from the file stem.
+    pub name: String,
+    /// Path of the module's page relative to the output root, without the
+    /// file extension and with `/` separators. The module name unless the
+    /// module was found in a directory, e.g. `client/base`.
+    pub page: String,
+    /// Page title in place of the module name.
+    pub title: Option<String>,
+    /// Dotted module path including the configured prefix.
+    pub qualified_name: String,
+    /// Path of the source file the module was parsed from.
+    pub path: PathBuf,
+    pub docstring: Option<String>,
+    /// Top-level classes and functions in source order.
+    pub items: Vec<Item>,
+    /// Module-level imports in source order.
+    pub imports: Vec<Import>,
+    /// Symbols imported from private modules and documented on this
+    /// module's page, in import order.
+    pub reexports: Vec<Reexport>,
+    /// Module-level type aliases in source order.
+    pub aliases: Vec<TypeAlias>,
+    /// Module-level assignments to a single name, in source order, other
+    /// than type aliases, type variables and dunders such as `__all__`.
+    pub attributes: Vec<AttributeDoc>,
+    /// Names listed in `__all__`, when the module assigns it a list or
+    /// tuple of strings.
+    pub exports: Option<Vec<String>>,
+    /// The string assigned to `__version__`, if any.
+    pub version: Option<String>,
+    /// Problems found while the module was parsed.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Markdown written between the definitions, such as a notebook's
+    /// Markdown cells, in source order.
+    pub prose: Vec<Prose>,
+}
+
+impl ModuleDoc {
+    /// Whether the module is a package's `__init__`.
+    pub fn is_package(&self) -> bool {
+        self.name == "__init__"
+    }
+
+    /// Dotted path the module is imported as; `pkg.__init__` is `pkg`.
+    pub fn import_path(&self) -> &str {
+        self.qualified_name
+            .strip_suffix(".__init__")
+            .unwrap_or(&self.qualified_name)
+    }
+}
+
+/// Markdown shown before the definitions from `line` on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prose {
+    /// One-based line of the code the text comes before.
+    pub line: usize,
+    pub text: String,
+}
+
+/// A name bound by an `import` or `from ... import` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// Name bound in the importing module.
+    pub local_name: String,
+    /// Module written in the statement, without leading dots.
+    pub module: String,
+    /// Number of leading dots of a relative import; 0 for absolute imports.
+    pub level: usize,
+    /// Imported name for `from` imports; `None` for plain `import`.
+    pub name: Option<String>,
+    pub line: usize,
+}
+
+/// A module-level type alias: `PathLike = Union[str, Path]`, an assignment
+/// annotated with `TypeAlias`, or a `type` statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeAlias {
+    pub name: String,
+    /// One-based line of the definition.
+    pub line: usize,
+    /// Rendered aliased type.
+    pub value: String,
+    /// String literal following the definition, if any.
+    pub docstring: Option<String>,
+}
+
+/// A class or function defined in a private module and documented where it
+/// is re-exported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reexport {
+    /// The definition, named as it is bound in the re-exporting module.
+    pub item: Item,
+    /// Name of the definition in the defining module.
+    pub original_name: String,
+    /// Dotted path of the defining module.
+    pub module: String,
+    /// File the definition was parsed from.
+    pub path: PathBuf,
+    /// One-based line of the import in the re-exporting module.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum Item {
+    Class(ClassDoc),
+    Function(FunctionDoc),
+}
+
+impl Item {
+    pub fn name(&self) -> &str {
+        match self {
+            Item::Class(class) => &class.name,
+            Item::Function(function) => &function.name,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            Item::Class(class) => class.line,
+            Item::Function(function) => function.line,
+        }
+    }
+
+    /// The message of the definition's deprecation decorator, if any.
+    pub fn deprecated(&self) -> Option<&str> {
+        match self {
+            Item::Class(class) => class.deprecated.as_deref(),
+            Item::Function(function) => function.deprecated.as_deref(),
+        }
+    }
+
+    /// The same definition bound under another name, as `import x as y`
+    /// does.
+    pub fn renamed(mut self, name: &str) -> Item {
+        match &mut self {
+            Item::Class(class) => class.name = name.to_string(),
+            Item::Function(function) => function.name = name.to_string(),
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassDoc {
+    pub name: String,
+    /// One-based line of the `class` statement.
+    pub line: usize,
+    /// Base classes as written, e.g. `BaseModel` or `Generic[T]`, without
+    /// keyword arguments such as `metaclass=`.
+    pub bases: Vec<String>,
+    /// Keyword arguments of the class statement as source text, e.g.
+    /// `metaclass=ABCMeta`.
+    pub keywords: Vec<String>,
+    /// Type parameters from `class Name[T]:`, or from a `Generic[...]` or
+    /// `Protocol[...]` base.
+    pub type_params: Vec<TypeParameter>,
+    /// Decorators rendered as source text, without the leading `@`.
+    pub decorators: Vec<String>,
+    pub docstring: Option<String>,
+    /// Methods in source order, without those serving as property accessors.
+    pub methods: Vec<FunctionDoc>,
+    pub properties: Vec<PropertyDoc>,
+    /// Attributes assigned in the class body, in source order, without
+    /// those that are fields of [`ClassDoc::data`] or enum members.
+    pub attributes: Vec<AttributeDoc>,
+    /// Names listed in `__slots__`, when the class declares it.
+    pub slots: Option<Vec<String>>,
+    /// Classes defined in the class body, such as the `Config` of a
+    /// pydantic model, in source order.
+    pub classes: Vec<ClassDoc>,
+    /// Members of an enum, in declaration order.
+    pub enum_members: Vec<EnumMember>,
+    /// The keys of a `TypedDict`.
+    pub typed_dict: Option<TypedDictDoc>,
+    /// Fields of a class whose constructor is generated from them.
+    pub data: Option<DataClassDoc>,
+    /// The message of a deprecation decorator on the class, empty when it
+    /// gives none.
+    pub deprecated: Option<String>,
+    /// Badges from the configured markers on the class.
+    pub badges: Vec<Badge>,
+    /// Version markers taken out of the docstring, in version order.
+    pub versions: Vec<VersionMarker>,
+}
+
+impl ClassDoc {
+    /// The `metaclass=` keyword of the class statement, as source text.
+    pub fn metaclass(&self) -> Option<&str> {
+        self.keywords
+            .iter()
+            .find_map(|keyword| keyword.strip_prefix("metaclass="))
+    }
+
+    /// Derives from `abc.ABC`, has `abc.ABCMeta` as its metaclass or
+    /// declares abstract methods, so that only its subclasses can be
+    /// instantiated.
+    pub fn is_abstract(&self) -> bool {
+        let is_abc = self
+            .bases
+            .iter()
+            .any(|base| base.rsplit('.').next() == Some("ABC"));
+        let has_abc_meta = self
+            .metaclass()
+            .is_some_and(|metaclass| metaclass.rsplit('.').next() == Some("ABCMeta"));
+        is_abc || has_abc_meta || self.methods.iter().any(FunctionDoc::is_abstract)
+    }
+
+    /// Derives from `typing.Protocol`, as an interface for others to
+    /// implement.
+    pub fn is_protocol(&self) -> bool {
+        self.bases.iter().any(|base| {
+            let base = base.split('[').next().unwrap_or(base);
+            base.rsplit('.').next() == Some("Protocol")
+        })
+    }
+}
+
+/// What generates a class's constructor from its fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataClassKind {
+    /// `@dataclass` from the standard library.
+    Dataclass,
+    /// `@attr.s`, `@attrs.define` and their variants.
+    Attrs,
+    /// A subclass of pydantic's `BaseModel` or another configured base.
+    Pydantic,
+    /// A `typing.NamedTuple` subclass, or a `namedtuple(...)` call.
+    NamedTuple,
+}
+
+/// The generated constructor of a dataclass-like class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataClassDoc {
+    pub kind: DataClassKind,
+    /// Fields taken by the constructor, in source order.
+    pub fields: Vec<FieldDoc>,
+    /// Instances are immutable: `frozen=True`, or `@attrs.frozen`.
+    pub frozen: bool,
+    /// Every field is keyword-only: `kw_only=True`.
+    pub kw_only: bool,
+}
+
+/// A type variable a generic class or function is parameterized by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeParameter {
+    pub name: String,
+    /// Rendered upper bound, from `bound=` or `[T: Bound]`.
+    pub bound: Option<String>,
+    /// Rendered types the variable is restricted to, as in
+    /// `TypeVar("AnyStr", str, bytes)` or `[T: (str, bytes)]`.
+    pub constraints: Vec<String>,
+}
+
+/// A field of a dataclass-like class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDoc {
+    pub name: String,
+    /// One-based line of the field's definition.
+    pub line: usize,
+    /// Rendered annotation, or the `type=` of an `attr.ib()`.
+    pub type_name: Option<String>,
+    /// Source text of the default; a factory is shown as a call, e.g.
+    /// `list()`. `None` means the field is required.
+    pub default: Option<String>,
+    /// The string literal following the field, if any.
+    pub docstring: Option<String>,
+}
+
+/// An attribute assigned in a class body or at the top level of a module,
+/// such as `timeout: int = 30`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeDoc {
+    pub name: String,
+    /// One-based line of the assignment.
+    pub line: usize,
+    /// Rendered annotation, without the `ClassVar[...]` around it, or the
+    /// type inferred from the value.
+    pub type_name: Option<String>,
+    /// The assigned value as source text; `None` for a bare annotation.
+    pub default: Option<String>,
+    /// The string literal following the assignment, if any.
+    pub docstring: Option<String>,
+    /// Annotated with `ClassVar`: shared by the class rather than set per
+    /// instance.
+    pub class_var: bool,
+}
+
+/// The keys of a `TypedDict`, declared as a class or with the functional
+/// form `Config = TypedDict("Config", {...})`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedDictDoc {
+    /// Keys are required unless `total=False`.
+    pub total: bool,
+    /// Keys in declaration order.
+    pub keys: Vec<TypedDictKey>,
+}
+
+/// A key of a `TypedDict`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedDictKey {
+    pub name: String,
+    /// One-based line of the declaration.
+    pub line: usize,
+    /// Rendered type of the value, without a `Required[...]` or
+    /// `NotRequired[...]` around it.
+    pub type_name: String,
+    /// The annotation as written.
+    pub annotation: String,
+    /// Whether the key must be present: from `total=`, unless the
+    /// annotation says otherwise.
+    pub required: bool,
+    /// The string literal following the declaration, if any.
+    pub docstring: Option<String>,
+}
+
+/// A member of an enum, such as `FAST = "fast"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumMember {
+    pub name: String,
+    /// One-based line of the assignment.
+    pub line: usize,
+    /// The assigned value as source text, e.g. `"fast"` or `auto()`.
+    pub value: String,
+    /// The string literal following the assignment, if any.
+    pub docstring: Option<String>,
+}
+
+/// A label shown next to a symbol's heading, with an optional note shown
+/// as an admonition below it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub label: String,
+    pub note: Option<String>,
+}
+
+/// A property, with the methods implementing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDoc {
+    pub name: String,
+    /// One-based line where the property is defined.
+    pub line: usize,
+    /// The property's own docstring, falling back to the getter's.
+    pub docstring: Option<String>,
+    /// Type of the value, from the getter's return annotation.
+    pub type_name: Option<String>,
+    /// Defined with `@cached_property`, computed once per instance.
+    pub cached: bool,
+    pub getter: Option<FunctionDoc>,
+    pub setter: Option<FunctionDoc>,
+    pub deleter: Option<FunctionDoc>,
+}
+
+impl PropertyDoc {
+    /// Type the setter accepts, from the annotation of its value parameter.
+    pub fn value_type(&self) -> Option<&str> {
+        self.setter
+            .as_ref()?
+            .arguments(true)
+            .first()?
+            .annotation
+            .as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionDoc {
+    pub name: String,
+    /// One-based line of the `def` statement.
+    pub line: usize,
+    pub docstring: Option<String>,
+    /// Sections parsed out of the docstring; empty without a docstring.
+    pub parsed_docstring: Docstring,
+    /// Decorators rendered as source text, without the leading `@`.
+    pub decorators: Vec<String>,
+    pub parameters: Vec<Parameter>,
+    /// Type parameters from `def name[T](...)`.
+    pub type_params: Vec<TypeParameter>,
+    /// Defined with `async def`.
+    pub is_async: bool,
+    /// Rendered return annotation, if any.
+    pub returns: Option<String>,
+    /// Distinct exception types raised directly in the body, in source
+    /// order. Bare re-raises and nested definitions are not included.
+    pub raises: Vec<String>,
+    /// Reconstructed source of the whole definition.
+    pub source: String,
+    /// Functions defined directly in the body, in source order.
+    pub nested: Vec<FunctionDoc>,
+    /// Signatures of the `@overload` declarations grouped with this
+    /// function, in source order.
+    pub overloads: Vec<String>,
+    /// A test exercising the function, shown when the docstring has no
+    /// examples of its own. Filled in by [`attach_examples`].
+    ///
+    /// [`attach_examples`]: crate::examples::attach_examples
+    pub example: Option<String>,
+    /// The message of a deprecation decorator on the function, empty when
+    /// it gives none.
+    pub deprecated: Option<String>,
+    /// Badges from the configured markers on the function.
+    pub badges: Vec<Badge>,
+    /// Version markers taken out of the docstring, in version order.
+    pub versions: Vec<VersionMarker>,
+}
+
+/// How a method is bound, from its decorators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodKind {
+    Instance,
+    /// `@classmethod`.
+    Class,
+    /// `@staticmethod`.
+    Static,
+}
+
+/// The kind of a method with `decorators`, rendered as source text.
+pub fn method_kind(decorators: &[String]) -> MethodKind {
+    let decorated = |name: &str| decorators.iter().any(|d| decorator_name(d) == name);
+    if decorated("classmethod") {
+        MethodKind::Class
+    } else if decorated("staticmethod") {
+        MethodKind::Static
+    } else {
+        MethodKind::Instance
+    }
+}
+
+/// The last segment of a decorator's dotted name, called or not: `command`
+/// for `click.command(name="up")`.
+pub fn decorator_name(decorator: &str) -> &str {
+    let callee = decorator.split('(').next().unwrap_or(decorator);
+    callee.rsplit('.').next().unwrap_or(callee)
+}
+
+impl FunctionDoc {
+    pub fn method_kind(&self) -> MethodKind {
+        method_kind(&self.decorators)
+    }
+
+    /// Decorated with `@abstractmethod` or `@abc.abstractmethod`.
+    pub fn is_abstract(&self) -> bool {
+        self.decorators
+            .iter()
+            .any(|d| decorator_name(d) == "abstractmethod")
+    }
+
+    /// Decorated with `@overload` or `@typing.overload`.
+    pub fn is_overload(&self) -> bool {
+        self.decorators
+            .iter()
+            .any(|d| decorator_name(d) == "overload")
+    }
+
+    /// The `def` line of the source, without decorators or the colon.
+    pub fn signature(&self) -> &str {
+        self.source
+            .lines()
+            .find(|line| !line.starts_with('@'))
+            .unwrap_or_default()
+            .trim_end_matches(':')
+    }
+
+    /// Parameters a caller passes: a method's leading `self` or `cls` is
+    /// left out.
+    pub fn arguments(&self, is_method: bool) -> &[Parameter] {
+        let skip_receiver = is_method
+            && self.method_kind() != MethodKind::Static
+            && self
+                .parameters
+                .first()
+                .is_some_and(|p| p.name == "self" || p.name == "cls");
+        &self.parameters[usize::from(skip_receiver)..]
+    }
+
+    /// The first `Args:` entry listed out of signature order, with the
+    /// parameter the signature has in its place. Entries for variadic or
+    /// unknown parameters are ignored.
+    pub fn misordered_parameter(&self) -> Option<(&str, &str)> {
+        let documented: Vec<&str> = self
+            .parsed_docstring
+            .params
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .filter(|name| self.parameters.iter().any(|p| p.name == *name))
+            .collect();
+        let expected = self
+            .parameters
+            .iter()
+            .map(|parameter| parameter.name.as_str())
+            .filter(|name| documented.contains(name));
+        documented
+            .iter()
+            .zip(expected)
+            .find(|(documented, expected)| *documented != expected)
+            .map(|(documented, expected)| (*documented, expected))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    /// Name without the `*` or `**` of a variadic parameter.
+    pub name: String,
+    pub kind: ParameterKind,
+    /// Rendered type annotation, if any.
+    pub annotation: Option<String>,
+    /// Rendered default value; `None` means the parameter is required.
+    pub default: Option<String>,
+    /// Description from the docstring's `Args:` section.
+    pub description: Option<String>,
+    /// Type given in the docstring's `Args:` section, e.g. `name (str): ...`.
+    pub docstring_type: Option<String>,
+    /// Type inferred from the default value, only set when there is neither
+    /// an annotation nor a docstring type.
+    pub inferred_type: Option<&'static str>,
+}
+
+/// Whether a parameter takes one argument or collects the extra ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParameterKind {
+    #[default]
+    Single,
+    /// Declared before `/`, so only passed by position.
+    PositionalOnly,
+    /// `*args`: extra positional arguments.
+    VarPositional,
+    /// `**kwargs`: extra keyword arguments.
+    VarKeyword,
+}
+
+impl Parameter {
+    /// The name as written in the signature, e.g. `**kwargs`.
+    pub fn display_name(&self) -> String {
+        match self.kind {
+            ParameterKind::Single | ParameterKind::PositionalOnly => self.name.clone(),
+            ParameterKind::VarPositional => format!("*{}", self.name),
+            ParameterKind::VarKeyword => format!("**{}", self.name),
+        }
+    }
+
+    /// Whether a caller must pass this parameter: it has no default and
+    /// is not variadic.
+    pub fn is_required(&self) -> bool {
+        self.default.is_none()
+            && matches!(
+                self.kind,
+                ParameterKind::Single | ParameterKind::PositionalOnly
+            )
+    }
+}
@@ -0,0 +1,237 @@
+//! Navigation files listing the generated pages, for the static site
+//! generators that need one besides the pages themselves.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::render::yaml_scalar;
+
+/// The site generator a navigation file is written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NavFormat {
+    /// A `nav:` section for `mkdocs.yml`.
+    Mkdocs,
+    /// The items of a Docusaurus sidebar, as JSON.
+    Docusaurus,
+    /// The `SUMMARY.md` of an mdBook, for Markdown pages only.
+    Mdbook,
+}
+
+impl NavFormat {
+    /// Name of the navigation file in the output directory.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            NavFormat::Mkdocs => "nav.yml",
+            NavFormat::Docusaurus => "sidebar.json",
+            NavFormat::Mdbook => "SUMMARY.md",
+        }
+    }
+}
+
+/// A generated page listed in the navigation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavPage {
+    /// Path relative to the output directory, without the file extension
+    /// and with `/` separators.
+    pub page: String,
+    pub title: String,
+}
+
+/// An entry of a section: a page, or a subdirectory's section.
+enum Entry<'a> {
+    Page(&'a NavPage),
+    Section(&'a str, &'a Section),
+}
+
+/// The pages of one directory and its subdirectories.
+#[derive(Debug, Default)]
+struct Section {
+    pages: Vec<NavPage>,
+    sections: BTreeMap<String, Section>,
+}
+
+impl Section {
+    fn insert(&mut self, page: &NavPage) {
+        let mut section = self;
+        let mut directories: Vec<&str> = page.page.split('/').collect();
+        directories.pop();
+        for directory in directories {
+            section = section.sections.entry(directory.to_string()).or_default();
+        }
+        section.pages.push(page.clone());
+    }
+
+    /// The pages and subsections in alphabetical order, with the index page
+    /// of the directory first.
+    fn entries(&self, index_name: &str) -> Vec<Entry<'_>> {
+        let mut entries: Vec<Entry> = self
+            .pages
+            .iter()
+            .map(Entry::Page)
+            .chain(
+                self.sections
+                    .iter()
+                    .map(|(name, section)| Entry::Section(name, section)),
+            )
+            .collect();
+        entries.sort_by_key(|entry| match entry {
+            Entry::Page(page) => {
+                let is_index = page.page.rsplit('/').next() == Some(index_name);
+                (!is_index, page.title.to_lowercase())
+            }
+            Entry::Section(name, _) => (true, name.to_lowercase()),
+        });
+        entries
+    }
+}
+
+/// The navigation of `pages` nested by directory, each page linked with its
+/// `extension`. Index pages, named `index_name`, come first in their
+/// directory.
+pub fn render_nav(
+    format: NavFormat,
+    pages: &[NavPage],
+    extension: &str,
+    index_name: &str,
+) -> String {
+    let mut root = Section::default();
+    for page in pages {
+        root.insert(page);
+    }
+    match format {
+        NavFormat::Mkdocs => {
+            let mut nav = String::from("nav:\n");
+            mkdocs_entries(&mut nav, &root, extension, index_name, 1);
+            nav
+        }
+        NavFormat::Docusaurus => {
+            let items = docusaurus_items(&root, index_name, 1);
+            let mut sidebar = serde_json::to_string_pretty(&items).unwrap_or_default();
+            sidebar.push('\n');
+            sidebar
+        }
+        NavFormat::Mdbook => {
+            let mut summary = String::from("# Summary\n\n");
+            mdbook_entries(&mut summary, &root, extension, index_name, 0);
+            summary
+        }
+    }
+}
+
+fn mkdocs_entries(
+    nav: &mut String,
+    section: &Section,
+    extension: &str,
+    index_name: &str,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    for entry in section.entries(index_name) {
+        match entry {
+            Entry::Page(page) => nav.push_str(&format!(
+                "{}- {}: {}\n",
+                indent,
+                yaml_scalar(&page.title),
+                yaml_scalar(&format!("{}.{}", page.page, extension))
+            )),
+            Entry::Section(name, section) => {
+                nav.push_str(&format!("{}- {}:\n", indent, yaml_scalar(name)));
+                mkdocs_entries(nav, section, extension, index_name, depth + 1);
+            }
+        }
+    }
+}
+
+/// Sidebar items of `section`: a `doc` per page, with the page as its id,
+/// and a `category` per subdirectory. A subdirectory's index page becomes
+/// the link of its category rather than one of its items. Categories below
+/// the top level start collapsed.
+fn docusaurus_items(section: &Section, index_name: &str, depth: usize) -> Vec<Value> {
+    let is_index = |page: &NavPage| depth > 1 && page.page.rsplit('/').next() == Some(index_name);
+    let mut items = Vec::new();
+    for entry in section.entries(index_name) {
+        match entry {
+            Entry::Page(page) if is_index(page) => {}
+            Entry::Page(page) => items.push(json!({
+                "type": "doc",
+                "id": page.page,
+                "label": page.title,
+            })),
+            Entry::Section(name, section) => {
+                let mut category = json!({
+                    "type": "category",
+                    "label": name,
+                    "collapsed": depth > 1,
+                });
+                let index = section
+                    .pages
+                    .iter()
+                    .find(|page| page.page.rsplit('/').next() == Some(index_name));
+                if let Some(index) = index {
+                    category["label"] = json!(index.title);
+                    category["link"] = json!({ "type": "doc", "id": index.page });
+                }
+                category["items"] = json!(docusaurus_items(section, index_name, depth + 1));
+                items.push(category);
+            }
+        }
+    }
+    items
+}
+
+/// List entries of `section` for an mdBook summary, nested two spaces per
+/// level. A subdirectory is a chapter linking to its index page, or a draft
+/// chapter without a link when it has none.
+fn mdbook_entries(
+    summary: &mut String,
+    section: &Section,
+    extension: &str,
+    index_name: &str,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    let is_index = |page: &NavPage| depth > 0 && page.page.rsplit('/').next() == Some(index_name);
+    for entry in section.entries(index_name) {
+        match entry {
+            Entry::Page(page) if is_index(page) => {}
+            Entry::Page(page) => summary.push_str(&format!(
+                "{}- [{}]({})\n",
+                indent,
+                link_text(&page.title),
+                link_target(&format!("{}.{}", page.page, extension))
+            )),
+            Entry::Section(name, section) => {
+                let index = section
+                    .pages
+                    .iter()
+                    .find(|page| page.page.rsplit('/').next() == Some(index_name));
+                let (title, target) = match index {
+                    Some(page) => (
+                        page.title.as_str(),
+                        link_target(&format!("{}.{}", page.page, extension)),
+                    ),
+                    None => (name, String::new()),
+                };
+                summary.push_str(&format!("{}- [{}]({})\n", indent, link_text(title), target));
+                mdbook_entries(summary, section, extension, index_name, depth + 1);
+            }
+        }
+    }
+}
+
+/// `text` with the brackets that would end a link's text escaped.
+fn link_text(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+/// `path` as a link target, in angle brackets when it contains spaces.
+fn link_target(path: &str) -> String {
+    if path.contains(' ') {
+        format!("<{}>", path)
+    } else {
+        path.to_string()
+    }
+}
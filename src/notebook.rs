@@ -0,0 +1,119 @@
+//! Jupyter notebooks as input: the code cells are documented as one
+//! module, and the Markdown cells can be kept as prose between the
+//! definitions.
+
+use std::path::Path;
+
+use rustpython_parser::{ast, Parse};
+use serde::Deserialize;
+
+use crate::model::Prose;
+use crate::paths::normalize_path;
+
+/// Whether `path` names a notebook, by its `.ipynb` extension.
+pub fn is_notebook(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "ipynb")
+}
+
+/// The Python code and Markdown of a notebook.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Notebook {
+    /// The code cells that parse, without IPython magics and shell escapes,
+    /// separated by blank lines.
+    pub source: String,
+    /// The Markdown cells, each at the line of the code that follows it.
+    pub markdown: Vec<Prose>,
+}
+
+#[derive(Deserialize)]
+struct NotebookFile {
+    cells: Vec<Cell>,
+}
+
+#[derive(Deserialize)]
+struct Cell {
+    cell_type: String,
+    #[serde(default)]
+    source: CellSource,
+}
+
+/// Cell text, stored either whole or as a list of lines.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CellSource {
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl Default for CellSource {
+    fn default() -> Self {
+        CellSource::Text(String::new())
+    }
+}
+
+impl CellSource {
+    fn text(self) -> String {
+        match self {
+            CellSource::Text(text) => text,
+            CellSource::Lines(lines) => lines.concat(),
+        }
+    }
+}
+
+impl Notebook {
+    /// Reads the cells of the notebook `json` at `path`. A code cell that
+    /// is not valid Python, or is run by a cell magic such as `%%bash`, is
+    /// skipped with a warning.
+    pub fn parse(json: &str, path: &Path) -> serde_json::Result<Notebook> {
+        let file: NotebookFile = serde_json::from_str(json)?;
+        let mut notebook = Notebook::default();
+        for (index, cell) in file.cells.into_iter().enumerate() {
+            let text = cell.source.text();
+            match cell.cell_type.as_str() {
+                "markdown" if !text.trim().is_empty() => notebook.markdown.push(Prose {
+                    line: notebook.source.lines().count() + 1,
+                    text: text.trim().to_string(),
+                }),
+                "code" => {
+                    if text.trim_start().starts_with("%%") {
+                        tracing::debug!(
+                            "skipping cell {} of {}: cell magic",
+                            index + 1,
+                            normalize_path(path)
+                        );
+                        continue;
+                    }
+                    let code: Vec<&str> = text.lines().filter(|line| !is_magic(line)).collect();
+                    let code = code.join("\n");
+                    if let Err(err) = ast::Suite::parse(&code, &path.to_string_lossy()) {
+                        tracing::warn!(
+                            "skipping cell {} of {}: {}",
+                            index + 1,
+                            normalize_path(path),
+                            err.error
+                        );
+                        continue;
+                    }
+                    if code.trim().is_empty() {
+                        continue;
+                    }
+                    if !notebook.source.is_empty() {
+                        notebook.source.push('\n');
+                    }
+                    notebook.source.push_str(code.trim_end());
+                    notebook.source.push('\n');
+                }
+                _ => {}
+            }
+        }
+        Ok(notebook)
+    }
+}
+
+/// Whether a line of a code cell is for IPython rather than Python: a line
+/// magic such as `%matplotlib inline` or a shell escape such as
+/// `!pip install zenml`.
+fn is_magic(line: &str) -> bool {
+    line.trim_start().starts_with(['%', '!'])
+}
@@ -0,0 +1,626 @@
+//! Options controlling how Python modules are parsed and rendered.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use regex_automata::meta::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::format::Format;
+use crate::locale::Strings;
+use crate::model::{Badge, ModuleDoc};
+
+/// Settings shared by the parse and render stages.
+///
+/// Construct it with [`RenderOptions::builder`] or start from the
+/// [`Default`] implementation, which reproduces the tool's standard output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Dotted package path prepended to module names in headings, links and
+    /// import snippets, e.g. `zenml`. Empty by default.
+    pub module_prefix: String,
+    /// Write a `special` tag after the module heading.
+    pub special_tag: bool,
+    /// Markup written around the documented content.
+    pub format: Format,
+    /// Generation time written to the frontmatter. Off by default so that
+    /// repeated runs produce identical output.
+    pub timestamp: Option<String>,
+    /// Release of the documented package, written to the frontmatter as
+    /// `version`.
+    pub doc_version: Option<String>,
+    /// Extra frontmatter keys of every page. A key replaces the built-in
+    /// one of the same name, such as `title`.
+    pub frontmatter: BTreeMap<String, String>,
+    /// Extra frontmatter keys of single modules' pages, keyed by dotted
+    /// module path. They take precedence over `frontmatter`.
+    pub module_frontmatter: BTreeMap<String, BTreeMap<String, String>>,
+    /// Write the file name of each page, without the extension, to its
+    /// frontmatter as `id`, so that Docusaurus document ids follow the
+    /// generated paths.
+    pub page_id: bool,
+    /// Titles of single modules' pages, keyed by dotted module path.
+    pub module_titles: BTreeMap<String, String>,
+    /// Title pages without a title of their own after their module in Title
+    /// Case, e.g. `Base Orchestrator` for `base_orchestrator`.
+    pub prettify_titles: bool,
+    /// How parameter and return annotations are rendered.
+    pub types: TypeRenderOptions,
+    /// Document `_private` classes, functions and methods. Dunder methods
+    /// such as `__init__` are governed by `dunders` instead.
+    pub include_private: bool,
+    /// Which dunder methods are documented.
+    pub dunders: Dunders,
+    /// Document only the module-level names listed in a module's `__all__`,
+    /// private or not, when it has one.
+    pub respect_all: bool,
+    /// Show a type inferred from the default value for parameters with
+    /// neither an annotation nor a docstring type.
+    pub infer_types: bool,
+    /// List the exceptions raised in a function's body when its docstring
+    /// has no `Raises:` section.
+    pub infer_raises: bool,
+    /// What to do with classes, functions and methods carrying a marker,
+    /// keyed by name. A name matches both a decorator (`@internal`, also
+    /// `@lib.internal(...)`) and a docstring tag (`:internal:`).
+    pub markers: BTreeMap<String, MarkerAction>,
+    /// Decorators whose functions and methods are left out, matched on the
+    /// last segment of their dotted name, so `command` matches both
+    /// `@command` and `@click.command()`.
+    pub skip_decorators: BTreeSet<String>,
+    /// Base classes making a class a pydantic model, whose annotated
+    /// attributes are its fields, matched on the last segment of their
+    /// dotted name.
+    pub pydantic_bases: BTreeSet<String>,
+    /// Document every public module-level variable, not only constants
+    /// named in upper case and names listed in `__all__`.
+    pub all_module_attributes: bool,
+    /// Document the validators of pydantic models, such as those decorated
+    /// with `@field_validator`.
+    pub show_validators: bool,
+    /// Leave out the `Config` class nested in pydantic models.
+    pub skip_nested_config: bool,
+    /// How functions defined inside functions and methods are shown.
+    pub nested_functions: NestedFunctions,
+    /// Decorators marking a class, function or method as deprecated,
+    /// matched on the last segment of their dotted name.
+    pub deprecated_decorators: BTreeSet<String>,
+    /// Leave deprecated classes, functions and methods out.
+    pub hide_deprecated: bool,
+    /// Document deprecated classes and functions after the others.
+    pub deprecated_last: bool,
+    /// Take the parameters and return type of a function from a signature
+    /// such as `run(name: str) -> Run` on the first line of its docstring,
+    /// for functions whose own signature a decorator hides.
+    pub signature_from_docstring: bool,
+    /// Document the constructor's parameters and docstring with the class
+    /// instead of in an `__init__` section of their own.
+    pub merge_init: bool,
+    /// Document only the module-level classes and functions whose name
+    /// matches, with all the methods of a matching class.
+    pub only: Option<NamePattern>,
+    /// How types naming a type alias defined in the same module are shown.
+    pub alias_style: AliasStyle,
+    /// Show a Mermaid class diagram with each class's documented ancestors
+    /// and direct subclasses.
+    pub inheritance_diagrams: bool,
+    /// List the symbols naming each class and function in an annotation, a
+    /// base class list or a decorator.
+    pub referenced_by: bool,
+    /// Start each class, function and method entry with a code block
+    /// importing the symbol and calling it with its required arguments.
+    pub usage_snippets: bool,
+    /// Labels and sentences written around the documented content.
+    pub strings: Strings,
+}
+
+/// A regular expression matched anywhere in a name, e.g.
+/// `Client|StackComponent`. Patterns compare by their source text.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct NamePattern {
+    source: String,
+    regex: Regex,
+}
+
+impl NamePattern {
+    pub fn is_match(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+}
+
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for NamePattern {}
+
+impl FromStr for NamePattern {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let regex = Regex::new(source).map_err(|err| match err.syntax_error() {
+            Some(syntax) => syntax.to_string(),
+            None => err.to_string(),
+        })?;
+        Ok(NamePattern {
+            source: source.to_string(),
+            regex,
+        })
+    }
+}
+
+impl TryFrom<String> for NamePattern {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        source.parse()
+    }
+}
+
+impl From<NamePattern> for String {
+    fn from(pattern: NamePattern) -> Self {
+        pattern.source
+    }
+}
+
+impl fmt::Display for NamePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// How a type naming a locally defined type alias is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AliasStyle {
+    /// The alias, linked to its definition.
+    #[default]
+    Link,
+    /// The aliased type in place of the alias.
+    Expand,
+    /// The alias followed by the aliased type in parentheses.
+    Both,
+}
+
+/// Which dunder methods such as `__init__` and `__repr__` are documented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dunders {
+    /// None of them.
+    None,
+    /// Only `__init__`, whose parameters are the constructor's.
+    #[default]
+    InitOnly,
+    /// All of them.
+    All,
+}
+
+/// How functions defined inside a function or method are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NestedFunctions {
+    /// Only in the source of the enclosing function.
+    #[default]
+    Hide,
+    /// Named in a list under the enclosing function.
+    List,
+    /// Each in a section of its own under the enclosing function.
+    Document,
+    /// Nowhere, not even in the source, as for the `wrapper` of a
+    /// decorator.
+    Omit,
+}
+
+/// How a marked symbol is documented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkerAction {
+    /// Leave the symbol out, as if it were private.
+    Exclude,
+    /// Document the symbol with a badge.
+    Badge(Badge),
+}
+
+/// The markers known without configuration: `internal` symbols are left
+/// out, `experimental` ones and `@runtime_checkable` protocols are badged.
+fn default_markers() -> BTreeMap<String, MarkerAction> {
+    BTreeMap::from([
+        ("internal".to_string(), MarkerAction::Exclude),
+        (
+            "experimental".to_string(),
+            MarkerAction::Badge(Badge {
+                label: "experimental".to_string(),
+                note: Some("This API is experimental and may change without notice.".to_string()),
+            }),
+        ),
+        (
+            "runtime_checkable".to_string(),
+            MarkerAction::Badge(Badge {
+                label: "runtime_checkable".to_string(),
+                note: None,
+            }),
+        ),
+    ])
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            module_prefix: String::new(),
+            special_tag: false,
+            format: Format::default(),
+            timestamp: None,
+            doc_version: None,
+            frontmatter: BTreeMap::new(),
+            module_frontmatter: BTreeMap::new(),
+            page_id: false,
+            module_titles: BTreeMap::new(),
+            prettify_titles: false,
+            types: TypeRenderOptions::default(),
+            include_private: false,
+            dunders: Dunders::default(),
+            respect_all: true,
+            infer_types: true,
+            infer_raises: false,
+            markers: default_markers(),
+            skip_decorators: BTreeSet::new(),
+            pydantic_bases: BTreeSet::from(["BaseModel".to_string()]),
+            show_validators: false,
+            skip_nested_config: false,
+            nested_functions: NestedFunctions::default(),
+            merge_init: false,
+            signature_from_docstring: false,
+            deprecated_decorators: BTreeSet::from(["deprecated".to_string()]),
+            hide_deprecated: false,
+            deprecated_last: false,
+            all_module_attributes: false,
+            only: None,
+            alias_style: AliasStyle::default(),
+            inheritance_diagrams: false,
+            referenced_by: false,
+            usage_snippets: true,
+            strings: Strings::default(),
+        }
+    }
+}
+
+impl RenderOptions {
+    pub fn builder() -> RenderOptionsBuilder {
+        RenderOptionsBuilder::default()
+    }
+
+    /// Whether a class, function or method called `name` is documented.
+    /// Rendering and coverage both go through this so they agree.
+    pub fn documents(&self, name: &str) -> bool {
+        self.include_private || !is_private(name)
+    }
+
+    /// Title of the page of `module`: its own, the one configured for it, or
+    /// its name. A package's `__init__` is named after the package.
+    pub fn page_title(&self, module: &ModuleDoc) -> String {
+        let configured = self.module_titles.get(&module.qualified_name);
+        if let Some(title) = module.title.as_ref().or(configured) {
+            return title.clone();
+        }
+        let name = module
+            .import_path()
+            .rsplit('.')
+            .next()
+            .unwrap_or(&module.name);
+        if !self.prettify_titles {
+            return name.to_string();
+        }
+        name.split(['_', '-'])
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether a module-level class or function called `name` passes the
+    /// `only` filter.
+    pub fn selects(&self, name: &str) -> bool {
+        self.only.as_ref().is_none_or(|only| only.is_match(name))
+    }
+
+    /// Whether a method called `name` is documented.
+    pub fn documents_method(&self, name: &str) -> bool {
+        if !is_dunder(name) {
+            return self.documents(name);
+        }
+        match self.dunders {
+            Dunders::None => false,
+            Dunders::InitOnly => name == "__init__",
+            Dunders::All => true,
+        }
+    }
+
+    /// Whether a class, function or type alias defined at the top level of
+    /// `module` is documented, taking the module's `__all__` into account.
+    pub fn documents_export(&self, module: &ModuleDoc, name: &str) -> bool {
+        match &module.exports {
+            Some(exports) if self.respect_all => exports.iter().any(|export| export == name),
+            _ => self.documents(name),
+        }
+    }
+
+    /// Whether a module-level variable called `name` is documented: a
+    /// constant such as `DEFAULT_TIMEOUT` or a name listed in `__all__`,
+    /// or any public name with [`RenderOptions::all_module_attributes`].
+    pub fn documents_module_attribute(&self, module: &ModuleDoc, name: &str) -> bool {
+        let is_constant =
+            name.chars().any(|c| c.is_ascii_uppercase()) && !name.chars().any(|c| c.is_lowercase());
+        let is_exported = module
+            .exports
+            .as_ref()
+            .is_some_and(|exports| exports.iter().any(|export| export == name));
+        (self.all_module_attributes || is_constant || is_exported)
+            && self.documents_export(module, name)
+    }
+}
+
+/// Builder for [`RenderOptions`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptionsBuilder {
+    options: RenderOptions,
+}
+
+impl RenderOptionsBuilder {
+    pub fn module_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.options.module_prefix = prefix.into();
+        self
+    }
+
+    pub fn special_tag(mut self, special_tag: bool) -> Self {
+        self.options.special_tag = special_tag;
+        self
+    }
+
+    pub fn format(mut self, format: Format) -> Self {
+        self.options.format = format;
+        self
+    }
+
+    pub fn timestamp(mut self, timestamp: Option<String>) -> Self {
+        self.options.timestamp = timestamp;
+        self
+    }
+
+    pub fn doc_version(mut self, doc_version: Option<String>) -> Self {
+        self.options.doc_version = doc_version;
+        self
+    }
+
+    /// Adds a frontmatter key to every page.
+    pub fn frontmatter(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.frontmatter.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn page_id(mut self, page_id: bool) -> Self {
+        self.options.page_id = page_id;
+        self
+    }
+
+    /// Titles the page of the module at dotted `module`.
+    pub fn module_title(mut self, module: impl Into<String>, title: impl Into<String>) -> Self {
+        self.options
+            .module_titles
+            .insert(module.into(), title.into());
+        self
+    }
+
+    pub fn prettify_titles(mut self, prettify_titles: bool) -> Self {
+        self.options.prettify_titles = prettify_titles;
+        self
+    }
+
+    /// Adds a frontmatter key to the page of the module at dotted `module`.
+    pub fn module_frontmatter(
+        mut self,
+        module: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.options
+            .module_frontmatter
+            .entry(module.into())
+            .or_default()
+            .insert(key.into(), value.into());
+        self
+    }
+
+    pub fn types(mut self, types: TypeRenderOptions) -> Self {
+        self.options.types = types;
+        self
+    }
+
+    pub fn optional_style(mut self, optional_style: OptionalStyle) -> Self {
+        self.options.types.optional_style = optional_style;
+        self
+    }
+
+    pub fn collapse_annotated(mut self, collapse_annotated: bool) -> Self {
+        self.options.types.collapse_annotated = collapse_annotated;
+        self
+    }
+
+    pub fn include_private(mut self, include_private: bool) -> Self {
+        self.options.include_private = include_private;
+        self
+    }
+
+    pub fn dunders(mut self, dunders: Dunders) -> Self {
+        self.options.dunders = dunders;
+        self
+    }
+
+    pub fn respect_all(mut self, respect_all: bool) -> Self {
+        self.options.respect_all = respect_all;
+        self
+    }
+
+    pub fn infer_types(mut self, infer_types: bool) -> Self {
+        self.options.infer_types = infer_types;
+        self
+    }
+
+    pub fn infer_raises(mut self, infer_raises: bool) -> Self {
+        self.options.infer_raises = infer_raises;
+        self
+    }
+
+    /// Sets the action for a marker, replacing any default for that name.
+    pub fn marker(mut self, name: impl Into<String>, action: MarkerAction) -> Self {
+        self.options.markers.insert(name.into(), action);
+        self
+    }
+
+    /// Leaves out functions and methods carrying the decorator `name`.
+    pub fn skip_decorator(mut self, name: impl Into<String>) -> Self {
+        self.options.skip_decorators.insert(name.into());
+        self
+    }
+
+    /// Treats subclasses of `name` as pydantic models, besides those of
+    /// `BaseModel`.
+    pub fn pydantic_base(mut self, name: impl Into<String>) -> Self {
+        self.options.pydantic_bases.insert(name.into());
+        self
+    }
+
+    pub fn all_module_attributes(mut self, all_module_attributes: bool) -> Self {
+        self.options.all_module_attributes = all_module_attributes;
+        self
+    }
+
+    pub fn show_validators(mut self, show_validators: bool) -> Self {
+        self.options.show_validators = show_validators;
+        self
+    }
+
+    pub fn skip_nested_config(mut self, skip_nested_config: bool) -> Self {
+        self.options.skip_nested_config = skip_nested_config;
+        self
+    }
+
+    pub fn nested_functions(mut self, nested_functions: NestedFunctions) -> Self {
+        self.options.nested_functions = nested_functions;
+        self
+    }
+
+    pub fn deprecated_decorator(mut self, name: impl Into<String>) -> Self {
+        self.options.deprecated_decorators.insert(name.into());
+        self
+    }
+
+    pub fn hide_deprecated(mut self, hide_deprecated: bool) -> Self {
+        self.options.hide_deprecated = hide_deprecated;
+        self
+    }
+
+    pub fn deprecated_last(mut self, deprecated_last: bool) -> Self {
+        self.options.deprecated_last = deprecated_last;
+        self
+    }
+
+    pub fn signature_from_docstring(mut self, signature_from_docstring: bool) -> Self {
+        self.options.signature_from_docstring = signature_from_docstring;
+        self
+    }
+
+    pub fn merge_init(mut self, merge_init: bool) -> Self {
+        self.options.merge_init = merge_init;
+        self
+    }
+
+    pub fn only(mut self, only: Option<NamePattern>) -> Self {
+        self.options.only = only;
+        self
+    }
+
+    pub fn alias_style(mut self, alias_style: AliasStyle) -> Self {
+        self.options.alias_style = alias_style;
+        self
+    }
+
+    pub fn inheritance_diagrams(mut self, inheritance_diagrams: bool) -> Self {
+        self.options.inheritance_diagrams = inheritance_diagrams;
+        self
+    }
+
+    pub fn referenced_by(mut self, referenced_by: bool) -> Self {
+        self.options.referenced_by = referenced_by;
+        self
+    }
+
+    pub fn usage_snippets(mut self, usage_snippets: bool) -> Self {
+        self.options.usage_snippets = usage_snippets;
+        self
+    }
+
+    pub fn strings(mut self, strings: Strings) -> Self {
+        self.options.strings = strings;
+        self
+    }
+
+    pub fn build(self) -> RenderOptions {
+        self.options
+    }
+}
+
+/// Whether `name` is private by Python convention: a leading underscore that
+/// is not part of a dunder name.
+pub fn is_private(name: &str) -> bool {
+    name.starts_with('_') && !is_dunder(name)
+}
+
+/// Whether `name` is a dunder name such as `__init__`.
+pub(crate) fn is_dunder(name: &str) -> bool {
+    name.starts_with("__") && name.ends_with("__")
+}
+
+/// How `Optional[X]` and `Union[A, B]` annotations are displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OptionalStyle {
+    /// Keep the annotation as written.
+    #[default]
+    Preserve,
+    /// Rewrite `Optional[X]` to `X | None`.
+    Pipe,
+    /// Rewrite `Optional[X]` to `X | None` and `Union[A, B]` to `A | B`.
+    PipeUnions,
+}
+
+/// Normalizations applied when rendering type annotations.
+///
+/// The default renders annotations exactly as written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeRenderOptions {
+    pub optional_style: OptionalStyle,
+    /// Show `Annotated[T, ...]` as just `T`.
+    pub collapse_annotated: bool,
+    /// Drop `typing.` and `typing_extensions.` qualifiers.
+    pub strip_typing_prefix: bool,
+    /// Import map from local names to the dotted paths they refer to, e.g.
+    /// `np` to `numpy` for `import numpy as np`.
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl TypeRenderOptions {
+    pub(crate) fn stripped() -> Self {
+        Self {
+            strip_typing_prefix: true,
+            ..Self::default()
+        }
+    }
+}
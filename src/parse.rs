@@ -0,0 +1,1691 @@
+//! The parse stage: turns Python source into a [`ModuleDoc`].
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use rustpython_parser::ast::Ranged;
+use rustpython_parser::source_code::LineIndex;
+use rustpython_parser::text_size::{TextRange, TextSize};
+use rustpython_parser::{ast, Parse, ParseError};
+
+use crate::diagnostics::{Category, Diagnostic, Severity};
+use crate::docstring::{extract_version_markers, parse_docstring, Docstring, VersionMarker};
+use crate::model::{
+    decorator_name, AttributeDoc, Badge, ClassDoc, DataClassDoc, DataClassKind, EnumMember,
+    FieldDoc, FunctionDoc, Import, Item, ModuleDoc, Parameter, ParameterKind, PropertyDoc,
+    TypeAlias, TypeParameter, TypedDictDoc, TypedDictKey,
+};
+use crate::options::{is_dunder, MarkerAction, RenderOptions};
+use crate::paths::{normalize_path, qualified_name};
+use crate::source::{
+    expr_to_source, is_true, keyword, reconstruct_function_def, render_decorator, string,
+};
+use crate::types::{extract_type, infer_type, render_type};
+
+/// Parses `source` and extracts its documentation.
+///
+/// `path` is used for the module name and is recorded on the result; the
+/// file itself is never read.
+#[tracing::instrument(name = "parse", skip_all, fields(file = %normalize_path(path)))]
+pub fn parse_module(
+    source: &str,
+    path: &Path,
+    options: &RenderOptions,
+) -> Result<ModuleDoc, ParseError> {
+    let suite = ast::Suite::parse(source, &path.to_string_lossy())?;
+
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut ctx = Context::new(source, path, options);
+    let mut items = Vec::new();
+    let mut imports = Vec::new();
+    let mut aliases = Vec::new();
+    let mut attributes = Vec::new();
+    collect_imports(&ctx, &suite, &mut imports);
+    ctx.type_vars = suite
+        .iter()
+        .filter_map(|stmt| type_var(&ctx, stmt))
+        .collect();
+    for (index, stmt) in suite.iter().enumerate() {
+        if let Some((name, value)) = type_alias(stmt) {
+            aliases.push(TypeAlias {
+                name: name.to_string(),
+                line: ctx.line(stmt.start()),
+                value: ctx.annotation(value),
+                docstring: docstring(&suite[index + 1..]),
+            });
+            continue;
+        }
+        match stmt {
+            ast::Stmt::ClassDef(class_def) if options.selects(&class_def.name) => {
+                items.extend(extract_class(&mut ctx, class_def).map(Item::Class))
+            }
+            ast::Stmt::Assign(assign) if named_tuple_call(assign).is_some() => {
+                if let Some((name, call)) =
+                    named_tuple_call(assign).filter(|(name, _)| options.selects(name))
+                {
+                    let docstring = docstring(&suite[index + 1..]);
+                    items.extend(
+                        functional_named_tuple(&ctx, name, call, docstring).map(Item::Class),
+                    );
+                }
+            }
+            ast::Stmt::Assign(assign) if typed_dict_call(assign).is_some() => {
+                if let Some((name, call)) =
+                    typed_dict_call(assign).filter(|(name, _)| options.selects(name))
+                {
+                    let docstring = docstring(&suite[index + 1..]);
+                    items.extend(
+                        functional_typed_dict(&ctx, name, call, docstring).map(Item::Class),
+                    );
+                }
+            }
+            ast::Stmt::Assign(_) | ast::Stmt::AnnAssign(_) if type_var(&ctx, stmt).is_none() => {
+                let docstring = docstring(&suite[index + 1..]);
+                attributes.extend(
+                    extract_attribute(&ctx, stmt, docstring)
+                        .filter(|attribute| !is_dunder(&attribute.name)),
+                );
+            }
+            _ => {
+                let func_def = FunctionDef::from_stmt(stmt);
+                if let Some(func_def) = func_def.filter(|func_def| options.selects(func_def.name)) {
+                    items.extend(extract_function(&mut ctx, &func_def).map(Item::Function))
+                }
+            }
+        }
+    }
+    let exports = suite
+        .iter()
+        .rev()
+        .find_map(|stmt| dunder_all(stmt).map(|names| (stmt, names)));
+    if let Some((stmt, names)) = &exports {
+        let bound = bound_names(&suite, &imports);
+        if !bound.contains("*") {
+            for name in names.iter().filter(|name| !bound.contains(name.as_str())) {
+                ctx.warn(
+                    Category::UnresolvedReference,
+                    stmt.start(),
+                    format!(
+                        "`__all__` lists `{}`, which the module does not define",
+                        name
+                    ),
+                );
+            }
+        }
+    }
+
+    let items = group_overloads(items, |item| match item {
+        Item::Function(function) => Some(function),
+        Item::Class(_) => None,
+    });
+
+    let classes = items
+        .iter()
+        .filter(|item| matches!(item, Item::Class(_)))
+        .count();
+    tracing::debug!(
+        "parsed {}: {} class(es), {} function(s)",
+        normalize_path(path),
+        classes,
+        items.len() - classes
+    );
+
+    Ok(ModuleDoc {
+        qualified_name: qualified_name(&options.module_prefix, &name),
+        page: name.clone(),
+        title: None,
+        name,
+        path: path.to_path_buf(),
+        docstring: docstring(&suite),
+        items,
+        imports,
+        reexports: Vec::new(),
+        aliases,
+        attributes,
+        exports: exports.map(|(_, names)| names),
+        version: suite.iter().rev().find_map(dunder_version),
+        diagnostics: ctx.diagnostics,
+        prose: Vec::new(),
+    })
+}
+
+/// State shared by the extraction helpers while a module is parsed.
+pub(crate) struct Context<'a> {
+    pub(crate) options: &'a RenderOptions,
+    path: &'a Path,
+    source: &'a str,
+    line_index: LineIndex,
+    diagnostics: Vec<Diagnostic>,
+    /// Module-level `TypeVar`, `ParamSpec` and `TypeVarTuple` assignments.
+    type_vars: Vec<TypeParameter>,
+}
+
+impl<'a> Context<'a> {
+    fn new(source: &'a str, path: &'a Path, options: &'a RenderOptions) -> Self {
+        Self {
+            options,
+            path,
+            source,
+            line_index: LineIndex::from_source_text(source),
+            diagnostics: Vec::new(),
+            type_vars: Vec::new(),
+        }
+    }
+
+    /// Returns the one-based line number of a byte offset in the source.
+    pub(crate) fn line(&self, offset: TextSize) -> usize {
+        self.line_index
+            .source_location(offset, self.source)
+            .row
+            .to_usize()
+    }
+
+    /// Source text of a node.
+    pub(crate) fn text(&self, range: TextRange) -> &str {
+        &self.source[range]
+    }
+
+    /// Renders a parameter or return annotation using the type options.
+    fn annotation(&self, expr: &ast::Expr) -> String {
+        render_type(expr, &self.options.types)
+    }
+
+    pub(crate) fn warn(&mut self, category: Category, offset: TextSize, message: String) {
+        let diagnostic = Diagnostic {
+            severity: Severity::Warning,
+            category,
+            file: self.path.to_path_buf(),
+            line: self.line(offset),
+            message,
+        };
+        // A nested function is reconstructed both on its own and as part
+        // of the function around it.
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
+
+/// Collects module-level imports, including those nested in top-level `if`
+/// and `try` blocks such as `if TYPE_CHECKING:`.
+fn collect_imports(ctx: &Context, body: &[ast::Stmt], imports: &mut Vec<Import>) {
+    for stmt in body {
+        match stmt {
+            ast::Stmt::Import(import) => {
+                for alias in &import.names {
+                    // `import a.b` binds `a`; `import a.b as c` binds `c` to `a.b`.
+                    let (local_name, module) = match &alias.asname {
+                        Some(asname) => (asname.to_string(), alias.name.to_string()),
+                        None => {
+                            let top = alias.name.split('.').next().unwrap_or_default();
+                            (top.to_string(), top.to_string())
+                        }
+                    };
+                    imports.push(Import {
+                        local_name,
+                        module,
+                        level: 0,
+                        name: None,
+                        line: ctx.line(stmt.start()),
+                    });
+                }
+            }
+            ast::Stmt::ImportFrom(import) => {
+                for alias in &import.names {
+                    imports.push(Import {
+                        local_name: alias.asname.as_ref().unwrap_or(&alias.name).to_string(),
+                        module: import
+                            .module
+                            .as_ref()
+                            .map(|module| module.to_string())
+                            .unwrap_or_default(),
+                        level: import.level.as_ref().map_or(0, |level| level.to_usize()),
+                        name: Some(alias.name.to_string()),
+                        line: ctx.line(stmt.start()),
+                    });
+                }
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_imports(ctx, &if_stmt.body, imports);
+                collect_imports(ctx, &if_stmt.orelse, imports);
+            }
+            ast::Stmt::Try(try_stmt) => {
+                collect_imports(ctx, &try_stmt.body, imports);
+                for handler in &try_stmt.handlers {
+                    let ast::ExceptHandler::ExceptHandler(handler) = handler;
+                    collect_imports(ctx, &handler.body, imports);
+                }
+                collect_imports(ctx, &try_stmt.orelse, imports);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Matches a type alias definition, returning its name and aliased type.
+///
+/// Plain assignments count when the name is in CapWords and the value is a
+/// subscripted type or a `|` union, as in `PathLike = Union[str, Path]`;
+/// anything else could just as well be a constant or a class alias.
+fn type_alias(stmt: &ast::Stmt) -> Option<(&str, &ast::Expr)> {
+    match stmt {
+        ast::Stmt::TypeAlias(alias) => match &*alias.name {
+            ast::Expr::Name(name) => Some((name.id.as_str(), &alias.value)),
+            _ => None,
+        },
+        ast::Stmt::AnnAssign(assign) => {
+            let ast::Expr::Name(target) = &*assign.target else {
+                return None;
+            };
+            let is_alias = extract_type(&assign.annotation)
+                .rsplit('.')
+                .next()
+                .is_some_and(|annotation| annotation == "TypeAlias");
+            is_alias
+                .then_some(assign.value.as_deref())
+                .flatten()
+                .map(|value| (target.id.as_str(), value))
+        }
+        ast::Stmt::Assign(assign) => {
+            let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+                return None;
+            };
+            let name = target.id.as_str();
+            let is_cap_words = name.starts_with(|c: char| c.is_ascii_uppercase())
+                && name.chars().any(|c| c.is_ascii_lowercase());
+            let is_type = match &*assign.value {
+                ast::Expr::Subscript(_) => true,
+                ast::Expr::BinOp(binop) => matches!(binop.op, ast::Operator::BitOr),
+                _ => false,
+            };
+            (is_cap_words && is_type).then_some((name, &*assign.value))
+        }
+        _ => None,
+    }
+}
+
+/// Matches `T = TypeVar("T", ...)` and the `ParamSpec` and `TypeVarTuple`
+/// equivalents, returning the variable with its bound and constraints.
+fn type_var(ctx: &Context, stmt: &ast::Stmt) -> Option<TypeParameter> {
+    let ast::Stmt::Assign(assign) = stmt else {
+        return None;
+    };
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    let ast::Expr::Call(call) = &*assign.value else {
+        return None;
+    };
+    let callee = extract_type(&call.func);
+    if !matches!(
+        callee.rsplit('.').next(),
+        Some("TypeVar" | "ParamSpec" | "TypeVarTuple")
+    ) {
+        return None;
+    }
+    Some(TypeParameter {
+        name: target.id.to_string(),
+        bound: keyword(call, "bound").map(|bound| ctx.annotation(bound)),
+        constraints: call
+            .args
+            .iter()
+            .skip(1)
+            .map(|constraint| ctx.annotation(constraint))
+            .collect(),
+    })
+}
+
+/// The type parameters declared with PEP 695 syntax, as in
+/// `class Repository[T: BaseModel]:`.
+fn declared_type_params(ctx: &Context, type_params: &[ast::TypeParam]) -> Vec<TypeParameter> {
+    type_params
+        .iter()
+        .map(|type_param| match type_param {
+            ast::TypeParam::TypeVar(type_var) => {
+                let (bound, constraints) = match type_var.bound.as_deref() {
+                    Some(ast::Expr::Tuple(tuple)) => (
+                        None,
+                        tuple.elts.iter().map(|elt| ctx.annotation(elt)).collect(),
+                    ),
+                    bound => (bound.map(|bound| ctx.annotation(bound)), Vec::new()),
+                };
+                TypeParameter {
+                    name: type_var.name.to_string(),
+                    bound,
+                    constraints,
+                }
+            }
+            ast::TypeParam::ParamSpec(param_spec) => TypeParameter {
+                name: param_spec.name.to_string(),
+                bound: None,
+                constraints: Vec::new(),
+            },
+            ast::TypeParam::TypeVarTuple(type_var_tuple) => TypeParameter {
+                name: type_var_tuple.name.to_string(),
+                bound: None,
+                constraints: Vec::new(),
+            },
+        })
+        .collect()
+}
+
+/// The type parameters of a class: those of `class Name[T]:`, or else the
+/// variables listed by a `Generic[...]` or `Protocol[...]` base, with the
+/// bounds of their module-level definitions.
+fn class_type_params(ctx: &Context, class_def: &ast::StmtClassDef) -> Vec<TypeParameter> {
+    if !class_def.type_params.is_empty() {
+        return declared_type_params(ctx, &class_def.type_params);
+    }
+    let generic = class_def.bases.iter().find_map(|base| match base {
+        ast::Expr::Subscript(subscript)
+            if matches!(
+                extract_type(&subscript.value).rsplit('.').next(),
+                Some("Generic" | "Protocol")
+            ) =>
+        {
+            Some(&*subscript.slice)
+        }
+        _ => None,
+    });
+    let variables = match generic {
+        Some(ast::Expr::Tuple(tuple)) => tuple.elts.as_slice(),
+        Some(slice) => std::slice::from_ref(slice),
+        None => &[],
+    };
+    variables
+        .iter()
+        .map(|variable| {
+            let name = extract_type(variable);
+            ctx.type_vars
+                .iter()
+                .find(|type_var| type_var.name == name)
+                .cloned()
+                .unwrap_or(TypeParameter {
+                    name,
+                    bound: None,
+                    constraints: Vec::new(),
+                })
+        })
+        .collect()
+}
+
+/// The string assigned to `__version__` by `stmt`, if it is such an
+/// assignment.
+fn dunder_version(stmt: &ast::Stmt) -> Option<String> {
+    let ast::Stmt::Assign(assign) = stmt else {
+        return None;
+    };
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    match &*assign.value {
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(version),
+            ..
+        }) if target.id.as_str() == "__version__" => Some(version.clone()),
+        _ => None,
+    }
+}
+
+/// Matches `__all__ = [...]` or `__all__ = (...)` listing string literals,
+/// returning the names.
+fn dunder_all(stmt: &ast::Stmt) -> Option<Vec<String>> {
+    let ast::Stmt::Assign(assign) = stmt else {
+        return None;
+    };
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    if target.id.as_str() != "__all__" {
+        return None;
+    }
+    let elements = match &*assign.value {
+        ast::Expr::List(list) => &list.elts,
+        ast::Expr::Tuple(tuple) => &tuple.elts,
+        _ => return None,
+    };
+    elements
+        .iter()
+        .map(|element| match element {
+            ast::Expr::Constant(ast::ExprConstant {
+                value: ast::Constant::Str(name),
+                ..
+            }) => Some(name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Matches `__slots__ = ("name", "id")`, also written with a list, a
+/// dict of names to docstrings or a single string, returning the names.
+fn dunder_slots(assign: &ast::StmtAssign) -> Option<Vec<String>> {
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    if target.id.as_str() != "__slots__" {
+        return None;
+    }
+    let names: Vec<&ast::Expr> = match &*assign.value {
+        ast::Expr::List(list) => list.elts.iter().collect(),
+        ast::Expr::Tuple(tuple) => tuple.elts.iter().collect(),
+        ast::Expr::Dict(dict) => dict.keys.iter().flatten().collect(),
+        value => vec![value],
+    };
+    names
+        .into_iter()
+        .map(|name| string(name).map(str::to_string))
+        .collect()
+}
+
+/// Names bound at the top level of a module by definitions, assignments
+/// and imports. A star import is included as `*`.
+fn bound_names<'a>(suite: &'a [ast::Stmt], imports: &'a [Import]) -> BTreeSet<&'a str> {
+    let mut names: BTreeSet<&str> = imports
+        .iter()
+        .map(|import| import.local_name.as_str())
+        .collect();
+    for stmt in suite {
+        match stmt {
+            ast::Stmt::ClassDef(class_def) => {
+                names.insert(class_def.name.as_str());
+            }
+            ast::Stmt::FunctionDef(func_def) => {
+                names.insert(func_def.name.as_str());
+            }
+            ast::Stmt::AsyncFunctionDef(func_def) => {
+                names.insert(func_def.name.as_str());
+            }
+            ast::Stmt::Assign(assign) => {
+                names.extend(assign.targets.iter().filter_map(|target| match target {
+                    ast::Expr::Name(name) => Some(name.id.as_str()),
+                    _ => None,
+                }));
+            }
+            ast::Stmt::AnnAssign(assign) => {
+                if let ast::Expr::Name(name) = &*assign.target {
+                    names.insert(name.id.as_str());
+                }
+            }
+            ast::Stmt::TypeAlias(alias) => {
+                if let ast::Expr::Name(name) = &*alias.name {
+                    names.insert(name.id.as_str());
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Returns the docstring of a module, class or function body.
+fn docstring(body: &[ast::Stmt]) -> Option<String> {
+    if let Some(ast::Stmt::Expr(expr)) = body.first() {
+        if let ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(docstring),
+            ..
+        }) = &*expr.value
+        {
+            return Some(docstring.clone());
+        }
+    }
+    None
+}
+
+/// Applies the configured markers to a symbol's decorators and docstring.
+/// Returns `None` when a marker excludes the symbol, and otherwise its
+/// badges and its docstring without the marker tags.
+fn apply_markers(
+    options: &RenderOptions,
+    symbol: &str,
+    decorators: &[String],
+    mut docstring: Option<String>,
+) -> Option<(Vec<Badge>, Option<String>)> {
+    let decorator_names: Vec<&str> = decorators.iter().map(|d| decorator_name(d)).collect();
+
+    let mut badges: Vec<Badge> = Vec::new();
+    for (name, action) in &options.markers {
+        let tag = format!(":{}:", name);
+        let tagged = docstring.as_ref().is_some_and(|doc| doc.contains(&tag));
+        if tagged {
+            // Drop lines holding only the tag, then inline occurrences.
+            docstring = docstring.map(|doc| {
+                doc.lines()
+                    .filter(|line| line.trim() != tag)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .replace(&format!(" {}", tag), "")
+                    .replace(&tag, "")
+            });
+        }
+        if !tagged && !decorator_names.contains(&name.as_str()) {
+            continue;
+        }
+        match action {
+            MarkerAction::Exclude => {
+                let marker = if tagged { tag } else { format!("@{}", name) };
+                tracing::debug!("skipping `{}`: marked `{}`", symbol, marker);
+                return None;
+            }
+            MarkerAction::Badge(badge) => {
+                if !badges.contains(badge) {
+                    badges.push(badge.clone());
+                }
+            }
+        }
+    }
+    Some((badges, docstring))
+}
+
+/// The message of a deprecation decorator such as
+/// `@deprecated("Use `Client` instead.")`: its first positional argument,
+/// or its `reason` or `message` keyword. Empty for a decorator without one.
+fn deprecation(options: &RenderOptions, decorators: &[ast::Expr]) -> Option<String> {
+    decorators.iter().find_map(|decorator| {
+        let (callee, call) = match decorator {
+            ast::Expr::Call(call) => (&*call.func, Some(call)),
+            decorator => (decorator, None),
+        };
+        let callee = extract_type(callee);
+        let name = callee.rsplit('.').next().unwrap_or(&callee);
+        if !options.deprecated_decorators.contains(name) {
+            return None;
+        }
+        let message = call.and_then(|call| {
+            call.args
+                .first()
+                .or_else(|| keyword(call, "reason"))
+                .or_else(|| keyword(call, "message"))
+                .and_then(string)
+        });
+        Some(message.unwrap_or_default().to_string())
+    })
+}
+
+fn take_version_markers(docstring: Option<String>) -> (Vec<VersionMarker>, Option<String>) {
+    match docstring {
+        Some(docstring) => {
+            let (versions, docstring) = extract_version_markers(&docstring);
+            (versions, Some(docstring))
+        }
+        None => (Vec::new(), None),
+    }
+}
+
+fn extract_class(ctx: &mut Context, class_def: &ast::StmtClassDef) -> Option<ClassDoc> {
+    let decorators: Vec<String> = class_def
+        .decorator_list
+        .iter()
+        .map(|decorator| render_decorator(ctx, decorator))
+        .collect();
+    let (badges, docstring) = apply_markers(
+        ctx.options,
+        &class_def.name,
+        &decorators,
+        docstring(&class_def.body),
+    )?;
+    let (versions, docstring) = take_version_markers(docstring);
+    let deprecated = deprecation(ctx.options, &class_def.decorator_list);
+    if deprecated.is_some() && ctx.options.hide_deprecated {
+        tracing::debug!("skipping `{}`: deprecated", class_def.name);
+        return None;
+    }
+
+    let mut data = data_class(ctx.options, class_def);
+    let mut methods = Vec::new();
+    let mut attributes = Vec::new();
+    let mut enum_members = Vec::new();
+    let mut typed_dict = class_def
+        .bases
+        .iter()
+        .any(|base| extract_type(base).rsplit('.').next() == Some("TypedDict"))
+        .then(|| TypedDictDoc {
+            total: !class_def
+                .keywords
+                .iter()
+                .any(|keyword| keyword.arg.as_deref() == Some("total") && !is_true(&keyword.value)),
+            keys: Vec::new(),
+        });
+    let is_enum = class_def.bases.iter().any(|base| {
+        matches!(
+            extract_type(base).rsplit('.').next(),
+            Some("Enum" | "IntEnum" | "StrEnum" | "Flag" | "IntFlag")
+        )
+    });
+    let is_pydantic = data
+        .as_ref()
+        .is_some_and(|(data, _)| data.kind == DataClassKind::Pydantic);
+    let mut slots = None;
+    let mut classes = Vec::new();
+    let mut property_calls = Vec::new();
+    for (index, stmt) in class_def.body.iter().enumerate() {
+        match stmt {
+            ast::Stmt::FunctionDef(_) | ast::Stmt::AsyncFunctionDef(_) => methods.extend(
+                FunctionDef::from_stmt(stmt).and_then(|func_def| extract_function(ctx, &func_def)),
+            ),
+            ast::Stmt::ClassDef(nested)
+                if !(is_pydantic
+                    && ctx.options.skip_nested_config
+                    && nested.name.as_str() == "Config") =>
+            {
+                classes.extend(extract_class(ctx, nested));
+            }
+            ast::Stmt::Assign(assign) if dunder_slots(assign).is_some() => {
+                slots = dunder_slots(assign);
+            }
+            ast::Stmt::Assign(assign) if property_call(assign).is_some() => {
+                property_calls.extend(property_call(assign));
+            }
+            ast::Stmt::Assign(assign) if is_enum && enum_member(assign).is_some() => {
+                enum_members.extend(enum_member(assign).map(|(name, value)| EnumMember {
+                    name: name.to_string(),
+                    line: ctx.line(stmt.start()),
+                    value: expr_to_source(value),
+                    docstring: self::docstring(&class_def.body[index + 1..]),
+                }));
+            }
+            ast::Stmt::AnnAssign(assign) if typed_dict.is_some() => {
+                if let (Some(typed_dict), ast::Expr::Name(name)) =
+                    (&mut typed_dict, &*assign.target)
+                {
+                    let docstring = self::docstring(&class_def.body[index + 1..]);
+                    typed_dict.keys.push(typed_dict_key(
+                        ctx,
+                        name.id.as_str(),
+                        &assign.annotation,
+                        typed_dict.total,
+                        docstring,
+                    ));
+                }
+            }
+            ast::Stmt::Assign(_) | ast::Stmt::AnnAssign(_) => {
+                let docstring = self::docstring(&class_def.body[index + 1..]);
+                let field = data.as_ref().and_then(|(data, auto_attribs)| {
+                    extract_field(ctx, stmt, data.kind, *auto_attribs, docstring.clone())
+                });
+                match (field, &mut data) {
+                    (Some(field), Some((data, _))) => data.fields.push(field),
+                    (None, Some((data, _))) if data.kind == DataClassKind::Pydantic => attributes
+                        .extend(
+                            // The configuration of the model, not an attribute.
+                            extract_attribute(ctx, stmt, docstring)
+                                .filter(|attribute| attribute.name != "model_config"),
+                        ),
+                    _ => attributes.extend(extract_attribute(ctx, stmt, docstring)),
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut methods = group_overloads(methods, |method| Some(method));
+    if data.is_some() {
+        // Hooks called by the generated `__init__`, not by users.
+        methods.retain(|method| {
+            !matches!(
+                method.name.as_str(),
+                "__post_init__" | "__attrs_pre_init__" | "__attrs_post_init__" | "__attrs_init__"
+            )
+        });
+    }
+    if is_pydantic && !ctx.options.show_validators {
+        methods.retain(|method| !method.decorators.iter().any(|name| is_validator(name)));
+    }
+
+    let mut properties: Vec<PropertyDoc> = property_calls
+        .into_iter()
+        .map(|(name, call)| {
+            let line = ctx.line(call.start());
+            property_from_call(name, line, call, &mut methods)
+        })
+        .collect();
+    properties.extend(decorated_properties(&mut methods));
+    properties.sort_by_key(|property| property.line);
+
+    Some(ClassDoc {
+        name: class_def.name.to_string(),
+        line: ctx.line(class_def.start()),
+        bases: class_def.bases.iter().map(extract_type).collect(),
+        keywords: class_keywords(&class_def.keywords),
+        type_params: class_type_params(ctx, class_def),
+        decorators,
+        docstring,
+        methods,
+        properties,
+        attributes,
+        slots,
+        classes,
+        enum_members,
+        typed_dict,
+        data: data.map(|(data, _)| data),
+        deprecated,
+        badges,
+        versions,
+    })
+}
+
+/// Folds the `@overload` declarations of a function into the definition
+/// that follows them, which keeps its own docstring and parameters and
+/// lists their signatures. In a stub, where every definition is an
+/// overload, the last one stands for the others.
+fn group_overloads<T>(items: Vec<T>, function: fn(&mut T) -> Option<&mut FunctionDoc>) -> Vec<T> {
+    let mut grouped: Vec<T> = Vec::new();
+    for mut item in items {
+        if let Some(current) = function(&mut item) {
+            let previous = grouped
+                .last_mut()
+                .and_then(function)
+                .filter(|previous| previous.name == current.name && previous.is_overload());
+            if let Some(previous) = previous {
+                let mut overloads = std::mem::take(&mut previous.overloads);
+                overloads.push(previous.signature().to_string());
+                current.overloads = overloads;
+                grouped.pop();
+            }
+        }
+        grouped.push(item);
+    }
+    grouped
+}
+
+/// Keyword arguments of a class statement as source text.
+fn class_keywords(keywords: &[ast::Keyword]) -> Vec<String> {
+    keywords
+        .iter()
+        .map(|keyword| match &keyword.arg {
+            Some(arg) => format!("{}={}", arg, expr_to_source(&keyword.value)),
+            None => format!("**{}", expr_to_source(&keyword.value)),
+        })
+        .collect()
+}
+
+/// A key of a `TypedDict` annotated with `annotation`. `Required[...]` and
+/// `NotRequired[...]` override `total` and are left out of the type.
+fn typed_dict_key(
+    ctx: &Context,
+    name: &str,
+    annotation: &ast::Expr,
+    total: bool,
+    docstring: Option<String>,
+) -> TypedDictKey {
+    let (value_type, required) = match annotation {
+        ast::Expr::Subscript(subscript) => {
+            match extract_type(&subscript.value).rsplit('.').next() {
+                Some("Required") => (&*subscript.slice, true),
+                Some("NotRequired") => (&*subscript.slice, false),
+                _ => (annotation, total),
+            }
+        }
+        _ => (annotation, total),
+    };
+    TypedDictKey {
+        name: name.to_string(),
+        line: ctx.line(annotation.start()),
+        type_name: ctx.annotation(value_type),
+        annotation: ctx.text(annotation.range()).to_string(),
+        required,
+        docstring,
+    }
+}
+
+/// Matches `Name = TypedDict("Name", {...})`, returning the name bound and
+/// the call.
+fn typed_dict_call(assign: &ast::StmtAssign) -> Option<(&str, &ast::ExprCall)> {
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    let ast::Expr::Call(call) = &*assign.value else {
+        return None;
+    };
+    (extract_type(&call.func).rsplit('.').next() == Some("TypedDict"))
+        .then_some((target.id.as_str(), call))
+}
+
+/// The class a functional `TypedDict` definition stands for, with the
+/// string keys of its dictionary as keys.
+fn functional_typed_dict(
+    ctx: &Context,
+    name: &str,
+    call: &ast::ExprCall,
+    docstring: Option<String>,
+) -> Option<ClassDoc> {
+    let (badges, docstring) = apply_markers(ctx.options, name, &[], docstring)?;
+    let (versions, docstring) = take_version_markers(docstring);
+    let total = keyword(call, "total").is_none_or(is_true);
+    let keys = match call.args.get(1) {
+        Some(ast::Expr::Dict(dict)) => dict
+            .keys
+            .iter()
+            .zip(&dict.values)
+            .filter_map(|(key, value)| {
+                let key = string(key.as_ref()?)?;
+                Some(typed_dict_key(ctx, key, value, total, None))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Some(ClassDoc {
+        name: name.to_string(),
+        line: ctx.line(call.start()),
+        bases: vec![extract_type(&call.func)],
+        keywords: class_keywords(&call.keywords),
+        type_params: Vec::new(),
+        decorators: Vec::new(),
+        docstring,
+        methods: Vec::new(),
+        properties: Vec::new(),
+        attributes: Vec::new(),
+        slots: None,
+        classes: Vec::new(),
+        enum_members: Vec::new(),
+        typed_dict: Some(TypedDictDoc { total, keys }),
+        data: None,
+        deprecated: None,
+        badges,
+        versions,
+    })
+}
+
+/// Matches `Name = namedtuple("Name", ...)` and `typing.NamedTuple`'s
+/// functional form, returning the name bound and the call.
+fn named_tuple_call(assign: &ast::StmtAssign) -> Option<(&str, &ast::ExprCall)> {
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    let ast::Expr::Call(call) = &*assign.value else {
+        return None;
+    };
+    matches!(
+        extract_type(&call.func).rsplit('.').next(),
+        Some("namedtuple" | "NamedTuple")
+    )
+    .then_some((target.id.as_str(), call))
+}
+
+/// The class a functional named tuple definition stands for. The fields
+/// of `namedtuple()` are names in a string or a list, with `defaults=` for
+/// the last of them; those of `NamedTuple()` are `(name, type)` pairs.
+fn functional_named_tuple(
+    ctx: &Context,
+    name: &str,
+    call: &ast::ExprCall,
+    docstring: Option<String>,
+) -> Option<ClassDoc> {
+    let (badges, docstring) = apply_markers(ctx.options, name, &[], docstring)?;
+    let (versions, docstring) = take_version_markers(docstring);
+    // Classes made by `namedtuple()` derive from `tuple` itself.
+    let callee = extract_type(&call.func);
+    let base = if callee.rsplit('.').next() == Some("namedtuple") {
+        "tuple".to_string()
+    } else {
+        callee
+    };
+    let field = |name: &str, type_name: Option<String>| FieldDoc {
+        name: name.to_string(),
+        line: ctx.line(call.start()),
+        type_name,
+        default: None,
+        docstring: None,
+    };
+    let mut fields: Vec<FieldDoc> = match call.args.get(1) {
+        Some(ast::Expr::List(ast::ExprList { elts, .. }))
+        | Some(ast::Expr::Tuple(ast::ExprTuple { elts, .. })) => elts
+            .iter()
+            .filter_map(|elt| match elt {
+                ast::Expr::Tuple(pair) => match pair.elts.as_slice() {
+                    [name, type_name] => {
+                        Some(field(string(name)?, Some(ctx.annotation(type_name))))
+                    }
+                    _ => None,
+                },
+                elt => Some(field(string(elt)?, None)),
+            })
+            .collect(),
+        Some(names) => string(names)
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .map(|name| field(name, None))
+            .collect(),
+        None => Vec::new(),
+    };
+    let defaults = match keyword(call, "defaults") {
+        Some(ast::Expr::List(ast::ExprList { elts, .. }))
+        | Some(ast::Expr::Tuple(ast::ExprTuple { elts, .. })) => elts.as_slice(),
+        _ => &[],
+    };
+    let first_default = fields.len().saturating_sub(defaults.len());
+    for (field, default) in fields[first_default..].iter_mut().zip(defaults) {
+        field.default = Some(expr_to_source(default));
+    }
+    Some(ClassDoc {
+        name: name.to_string(),
+        line: ctx.line(call.start()),
+        bases: vec![base],
+        keywords: Vec::new(),
+        type_params: Vec::new(),
+        decorators: Vec::new(),
+        docstring,
+        methods: Vec::new(),
+        properties: Vec::new(),
+        attributes: Vec::new(),
+        slots: None,
+        classes: Vec::new(),
+        enum_members: Vec::new(),
+        typed_dict: None,
+        data: Some(DataClassDoc {
+            kind: DataClassKind::NamedTuple,
+            fields,
+            frozen: false,
+            kw_only: false,
+        }),
+        deprecated: None,
+        badges,
+        versions,
+    })
+}
+
+/// Recognizes a `@dataclass` or attrs class decorator, with its `frozen`
+/// and `kw_only` settings, or a pydantic model by its base. The flag tells
+/// whether annotated attributes are fields on their own; with `@attr.s`
+/// only `attr.ib()` attributes are, unless `auto_attribs=True`.
+fn data_class(
+    options: &RenderOptions,
+    class_def: &ast::StmtClassDef,
+) -> Option<(DataClassDoc, bool)> {
+    let is_pydantic = class_def.bases.iter().any(|base| {
+        let base = match base {
+            ast::Expr::Subscript(subscript) => &subscript.value,
+            base => base,
+        };
+        extract_type(base)
+            .rsplit('.')
+            .next()
+            .is_some_and(|name| options.pydantic_bases.contains(name))
+    });
+    if is_pydantic {
+        let frozen = class_def
+            .keywords
+            .iter()
+            .find(|keyword| keyword.arg.as_deref() == Some("frozen"))
+            .is_some_and(|keyword| is_true(&keyword.value));
+        let data = DataClassDoc {
+            kind: DataClassKind::Pydantic,
+            fields: Vec::new(),
+            frozen,
+            // The constructor of a model only takes keyword arguments.
+            kw_only: true,
+        };
+        return Some((data, true));
+    }
+    let is_named_tuple = class_def
+        .bases
+        .iter()
+        .any(|base| extract_type(base).rsplit('.').next() == Some("NamedTuple"));
+    if is_named_tuple {
+        let data = DataClassDoc {
+            kind: DataClassKind::NamedTuple,
+            fields: Vec::new(),
+            frozen: false,
+            kw_only: false,
+        };
+        return Some((data, true));
+    }
+    class_def.decorator_list.iter().find_map(|decorator| {
+        let (callee, call) = match decorator {
+            ast::Expr::Call(call) => (extract_type(&call.func), Some(call)),
+            _ => (extract_type(decorator), None),
+        };
+        let (kind, frozen, auto_attribs) = match callee.as_str() {
+            "dataclass" | "dataclasses.dataclass" => (DataClassKind::Dataclass, false, true),
+            "attr.s" | "attr.attrs" | "attrs" => (DataClassKind::Attrs, false, false),
+            "attr.dataclass" | "define" | "mutable" | "attr.define" | "attr.mutable"
+            | "attrs.define" | "attrs.mutable" => (DataClassKind::Attrs, false, true),
+            "frozen" | "attr.frozen" | "attrs.frozen" => (DataClassKind::Attrs, true, true),
+            _ => return None,
+        };
+        let flag = |name: &str, default: bool| {
+            call.and_then(|call| keyword(call, name))
+                .map_or(default, is_true)
+        };
+        let data = DataClassDoc {
+            kind,
+            fields: Vec::new(),
+            frozen: flag("frozen", frozen),
+            kw_only: flag("kw_only", false),
+        };
+        Some((data, flag("auto_attribs", auto_attribs)))
+    })
+}
+
+/// Extracts a field from an assignment in the body of a dataclass-like
+/// class. `ClassVar`s and fields left out of the constructor with
+/// `init=False` are not fields.
+fn extract_field(
+    ctx: &Context,
+    stmt: &ast::Stmt,
+    kind: DataClassKind,
+    auto_attribs: bool,
+    docstring: Option<String>,
+) -> Option<FieldDoc> {
+    let (target, annotation, value) = match stmt {
+        ast::Stmt::AnnAssign(assign) => (
+            &*assign.target,
+            Some(&*assign.annotation),
+            assign.value.as_deref(),
+        ),
+        ast::Stmt::Assign(assign) => match assign.targets.as_slice() {
+            [target] => (target, None, Some(&*assign.value)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let ast::Expr::Name(name) = target else {
+        return None;
+    };
+    // Pydantic keeps underscored attributes out of the model's fields.
+    if kind == DataClassKind::Pydantic && name.id.starts_with('_') {
+        return None;
+    }
+    let is_class_var = annotation.is_some_and(|annotation| {
+        let annotation = extract_type(annotation);
+        let callee = annotation.split('[').next().unwrap_or(&annotation);
+        callee.rsplit('.').next() == Some("ClassVar")
+    });
+    let call = value.and_then(field_call);
+    if is_class_var || (call.is_none() && !(auto_attribs && annotation.is_some())) {
+        return None;
+    }
+
+    let mut type_name = annotation.map(|annotation| ctx.annotation(annotation));
+    let default = match call {
+        Some(call) => {
+            let is_false = |expr: &ast::Expr| {
+                matches!(
+                    expr,
+                    ast::Expr::Constant(ast::ExprConstant {
+                        value: ast::Constant::Bool(false),
+                        ..
+                    })
+                )
+            };
+            if keyword(call, "init").is_some_and(is_false) {
+                return None;
+            }
+            if type_name.is_none() {
+                type_name = keyword(call, "type").map(|type_name| ctx.annotation(type_name));
+            }
+            // `attr.ib()` and pydantic's `Field()` also take the default as
+            // their first argument, where `Field(...)` marks a required field.
+            let factory = keyword(call, "default_factory").or_else(|| keyword(call, "factory"));
+            let default = keyword(call, "default").or_else(|| call.args.first());
+            match default.filter(|default| !is_ellipsis(default)) {
+                Some(default) => Some(default_text(ctx, default)),
+                None => factory.map(|factory| format!("{}()", ctx.text(factory.range()))),
+            }
+        }
+        None => value.map(|value| default_text(ctx, value)),
+    };
+
+    let description = call
+        .and_then(|call| keyword(call, "description"))
+        .and_then(string)
+        .map(str::to_string);
+    Some(FieldDoc {
+        name: name.id.to_string(),
+        line: ctx.line(stmt.start()),
+        type_name,
+        default,
+        docstring: description.or(docstring),
+    })
+}
+
+/// Matches an assignment defining an enum member, returning its name and
+/// value. Underscored names such as `_ignore_` are not members.
+fn enum_member(assign: &ast::StmtAssign) -> Option<(&str, &ast::Expr)> {
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    (!target.id.starts_with('_')).then_some((target.id.as_str(), &*assign.value))
+}
+
+/// Extracts an attribute from an assignment to a single name in a class
+/// body, either annotated or not.
+fn extract_attribute(
+    ctx: &Context,
+    stmt: &ast::Stmt,
+    docstring: Option<String>,
+) -> Option<AttributeDoc> {
+    let (target, annotation, value) = match stmt {
+        ast::Stmt::AnnAssign(assign) => (
+            &*assign.target,
+            Some(&*assign.annotation),
+            assign.value.as_deref(),
+        ),
+        ast::Stmt::Assign(assign) => match assign.targets.as_slice() {
+            [target] => (target, None, Some(&*assign.value)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let ast::Expr::Name(name) = target else {
+        return None;
+    };
+    let (annotation, class_var) = match annotation {
+        Some(ast::Expr::Subscript(subscript))
+            if extract_type(&subscript.value).rsplit('.').next() == Some("ClassVar") =>
+        {
+            (Some(&*subscript.slice), true)
+        }
+        // A bare `Final` says nothing of the type, which is inferred instead.
+        Some(annotation) if extract_type(annotation).rsplit('.').next() == Some("Final") => {
+            (None, false)
+        }
+        annotation => (annotation, false),
+    };
+    let type_name = match annotation {
+        Some(annotation) => Some(ctx.annotation(annotation)),
+        // Unlike a default, the value of an attribute is the only one it is
+        // expected to hold, so a container literal tells its type too.
+        None => value
+            .filter(|_| ctx.options.infer_types)
+            .and_then(|value| {
+                infer_type(value).or(match value {
+                    ast::Expr::List(_) => Some("list"),
+                    ast::Expr::Tuple(_) => Some("tuple"),
+                    ast::Expr::Dict(_) => Some("dict"),
+                    ast::Expr::Set(_) => Some("set"),
+                    _ => None,
+                })
+            })
+            .map(str::to_string),
+    };
+    Some(AttributeDoc {
+        name: name.id.to_string(),
+        line: ctx.line(stmt.start()),
+        type_name,
+        default: value.map(expr_to_source),
+        docstring,
+        class_var,
+    })
+}
+
+/// Matches `field(...)`, `attr.ib(...)` and their other spellings.
+fn field_call(value: &ast::Expr) -> Option<&ast::ExprCall> {
+    let ast::Expr::Call(call) = value else {
+        return None;
+    };
+    matches!(
+        extract_type(&call.func).as_str(),
+        "field"
+            | "dataclasses.field"
+            | "attrib"
+            | "attr.ib"
+            | "attr.attrib"
+            | "attr.field"
+            | "attrs.field"
+            | "Field"
+            | "pydantic.Field"
+    )
+    .then_some(call)
+}
+
+fn is_ellipsis(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Ellipsis,
+            ..
+        })
+    )
+}
+
+/// Whether `decorator` makes a method a pydantic validator, as
+/// `@field_validator("name")` or `@validator("name", pre=True)` do.
+fn is_validator(decorator: &str) -> bool {
+    matches!(
+        decorator_name(decorator),
+        "validator" | "root_validator" | "field_validator" | "model_validator"
+    )
+}
+
+/// Source text of a field default, showing `attrs.Factory(list)` as the
+/// call it stands for, `list()`.
+fn default_text(ctx: &Context, default: &ast::Expr) -> String {
+    if let ast::Expr::Call(call) = default {
+        let callee = extract_type(&call.func);
+        if matches!(
+            callee.as_str(),
+            "Factory" | "attr.Factory" | "attrs.Factory"
+        ) {
+            if let Some(factory) = call.args.first() {
+                return format!("{}()", ctx.text(factory.range()));
+            }
+        }
+    }
+    ctx.text(default.range()).to_string()
+}
+
+/// Matches `name = property(...)` in a class body.
+fn property_call(assign: &ast::StmtAssign) -> Option<(String, &ast::ExprCall)> {
+    let [ast::Expr::Name(target)] = assign.targets.as_slice() else {
+        return None;
+    };
+    let ast::Expr::Call(call) = &*assign.value else {
+        return None;
+    };
+    let callee = extract_type(&call.func);
+    (callee == "property" || callee == "builtins.property").then(|| (target.id.to_string(), call))
+}
+
+/// Builds the properties defined with `@property` or `@cached_property`,
+/// moving their getters, and the methods decorated with `@name.setter` or
+/// `@name.deleter`, out of `methods`.
+fn decorated_properties(methods: &mut Vec<FunctionDoc>) -> Vec<PropertyDoc> {
+    let mut properties: Vec<PropertyDoc> = Vec::new();
+    let mut remaining = Vec::new();
+    for method in methods.drain(..) {
+        let cached = method
+            .decorators
+            .iter()
+            .any(|decorator| decorator_name(decorator) == "cached_property");
+        let getter = cached
+            || method
+                .decorators
+                .iter()
+                .any(|decorator| decorator_name(decorator) == "property");
+        if getter {
+            properties.push(PropertyDoc {
+                name: method.name.clone(),
+                line: method.line,
+                docstring: method.docstring.clone(),
+                type_name: method.returns.clone(),
+                cached,
+                getter: Some(method),
+                setter: None,
+                deleter: None,
+            });
+            continue;
+        }
+        let accessor = method.decorators.iter().find_map(|decorator| {
+            let (name, kind) = decorator.split_once('.')?;
+            let property = properties
+                .iter()
+                .position(|property| property.name == name)?;
+            matches!(kind, "setter" | "deleter").then_some((property, kind == "setter"))
+        });
+        match accessor {
+            Some((property, true)) => properties[property].setter = Some(method),
+            Some((property, false)) => properties[property].deleter = Some(method),
+            None => remaining.push(method),
+        }
+    }
+    *methods = remaining;
+    properties
+}
+
+/// Builds a property from the arguments of `property(fget, fset, fdel, doc)`,
+/// moving the accessor methods it names out of `methods`.
+fn property_from_call(
+    name: String,
+    line: usize,
+    call: &ast::ExprCall,
+    methods: &mut Vec<FunctionDoc>,
+) -> PropertyDoc {
+    const PARAMETERS: [&str; 4] = ["fget", "fset", "fdel", "doc"];
+    let argument = |index: usize| {
+        call.args.get(index).or_else(|| {
+            call.keywords
+                .iter()
+                .find(|keyword| keyword.arg.as_deref() == Some(PARAMETERS[index]))
+                .map(|keyword| &keyword.value)
+        })
+    };
+    let mut accessor = |index: usize| {
+        let ast::Expr::Name(accessor) = argument(index)? else {
+            return None;
+        };
+        let position = methods
+            .iter()
+            .position(|method| method.name == accessor.id.as_str())?;
+        Some(methods.remove(position))
+    };
+
+    let getter = accessor(0);
+    let setter = accessor(1);
+    let deleter = accessor(2);
+    let doc = match argument(3) {
+        Some(ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(doc),
+            ..
+        })) => Some(doc.clone()),
+        _ => None,
+    };
+    PropertyDoc {
+        name,
+        line,
+        docstring: doc.or_else(|| getter.as_ref().and_then(|getter| getter.docstring.clone())),
+        type_name: getter.as_ref().and_then(|getter| getter.returns.clone()),
+        cached: false,
+        getter,
+        setter,
+        deleter,
+    }
+}
+
+/// A function or method statement, `def` or `async def`.
+pub(crate) struct FunctionDef<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) args: &'a ast::Arguments,
+    pub(crate) decorator_list: &'a [ast::Expr],
+    pub(crate) body: &'a [ast::Stmt],
+    pub(crate) returns: Option<&'a ast::Expr>,
+    pub(crate) type_params: &'a [ast::TypeParam],
+    pub(crate) start: TextSize,
+    pub(crate) is_async: bool,
+}
+
+impl<'a> FunctionDef<'a> {
+    pub(crate) fn from_stmt(stmt: &'a ast::Stmt) -> Option<Self> {
+        match stmt {
+            ast::Stmt::FunctionDef(func_def) => Some(FunctionDef {
+                name: &func_def.name,
+                args: &func_def.args,
+                decorator_list: &func_def.decorator_list,
+                body: &func_def.body,
+                returns: func_def.returns.as_deref(),
+                type_params: &func_def.type_params,
+                start: func_def.start(),
+                is_async: false,
+            }),
+            ast::Stmt::AsyncFunctionDef(func_def) => Some(FunctionDef {
+                name: &func_def.name,
+                args: &func_def.args,
+                decorator_list: &func_def.decorator_list,
+                body: &func_def.body,
+                returns: func_def.returns.as_deref(),
+                type_params: &func_def.type_params,
+                start: func_def.start(),
+                is_async: true,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn extract_function(ctx: &mut Context, func_def: &FunctionDef) -> Option<FunctionDoc> {
+    let decorators: Vec<String> = func_def
+        .decorator_list
+        .iter()
+        .map(|decorator| render_decorator(ctx, decorator))
+        .collect();
+    let skipped = decorators
+        .iter()
+        .map(|decorator| decorator_name(decorator))
+        .find(|name| ctx.options.skip_decorators.contains(*name));
+    if let Some(name) = skipped {
+        tracing::debug!("skipping `{}`: decorated with `@{}`", func_def.name, name);
+        return None;
+    }
+    let deprecated = deprecation(ctx.options, func_def.decorator_list);
+    if deprecated.is_some() && ctx.options.hide_deprecated {
+        tracing::debug!("skipping `{}`: deprecated", func_def.name);
+        return None;
+    }
+    let (badges, raw_docstring) = apply_markers(
+        ctx.options,
+        func_def.name,
+        &decorators,
+        docstring(func_def.body),
+    )?;
+    let (versions, raw_docstring) = take_version_markers(raw_docstring);
+    let mut parameters = extract_parameters(ctx, func_def.args);
+    let mut returns = func_def.returns.map(|ret| ctx.annotation(ret));
+    let signature = raw_docstring
+        .as_deref()
+        .filter(|_| ctx.options.signature_from_docstring)
+        .and_then(|docstring| docstring_signature(ctx, func_def, docstring));
+    let raw_docstring = match signature {
+        Some((signature_parameters, signature_returns, rest)) => {
+            parameters = signature_parameters;
+            returns = signature_returns.or(returns);
+            (!rest.is_empty()).then_some(rest)
+        }
+        None => raw_docstring,
+    };
+    let parsed_docstring = raw_docstring
+        .as_deref()
+        .map(parse_docstring)
+        .unwrap_or_default();
+    describe_parameters(&mut parameters, &parsed_docstring);
+
+    let function = FunctionDoc {
+        name: func_def.name.to_string(),
+        line: ctx.line(func_def.start),
+        docstring: raw_docstring,
+        parsed_docstring,
+        decorators,
+        parameters,
+        type_params: declared_type_params(ctx, func_def.type_params),
+        is_async: func_def.is_async,
+        returns,
+        raises: raised_exceptions(func_def.body),
+        source: reconstruct_function_def(ctx, func_def),
+        nested: func_def
+            .body
+            .iter()
+            .filter_map(FunctionDef::from_stmt)
+            .filter_map(|nested| extract_function(ctx, &nested))
+            .collect(),
+        overloads: Vec::new(),
+        example: None,
+        deprecated,
+        badges,
+        versions,
+    };
+    if let Some((documented, expected)) = function.misordered_parameter() {
+        ctx.warn(
+            Category::DocstringMismatch,
+            func_def.start,
+            format!(
+                "`Args:` of `{}` lists `{}` where the signature has `{}`",
+                function.name, documented, expected
+            ),
+        );
+    }
+    Some(function)
+}
+
+/// The signature on the first line of a docstring, such as
+/// `run(pipeline_name: str, stack: Stack) -> Run`, for a function whose own
+/// signature is hidden by a decorator: its parameters, its return type and
+/// the rest of the docstring. The line must name the function and parse as
+/// a Python signature; one that names it but does not parse is reported
+/// and left in the docstring.
+fn docstring_signature(
+    ctx: &mut Context,
+    func_def: &FunctionDef,
+    docstring: &str,
+) -> Option<(Vec<Parameter>, Option<String>, String)> {
+    let docstring = docstring.trim();
+    let (first, rest) = docstring.split_once('\n').unwrap_or((docstring, ""));
+    let first = first.trim();
+    if !first
+        .strip_prefix(func_def.name)
+        .is_some_and(|signature| signature.starts_with('('))
+    {
+        return None;
+    }
+    let parsed = ast::Suite::parse(&format!("def {}: ...", first), "<docstring>");
+    let signature = match parsed.as_deref() {
+        Ok([ast::Stmt::FunctionDef(signature)]) => signature,
+        _ => {
+            ctx.warn(
+                Category::DocstringMismatch,
+                func_def.start,
+                format!(
+                    "the first line of the docstring of `{}` is not a valid signature: `{}`",
+                    func_def.name, first
+                ),
+            );
+            return None;
+        }
+    };
+    Some((
+        extract_parameters(ctx, &signature.args),
+        signature.returns.as_deref().map(|ret| ctx.annotation(ret)),
+        rest.trim_start().to_string(),
+    ))
+}
+
+/// Fills parameter descriptions and docstring types from the `Args:`
+/// section. Inferred types only survive where nothing better is known.
+pub(crate) fn describe_parameters(parameters: &mut [Parameter], docstring: &Docstring) {
+    for parameter in parameters {
+        let entry = docstring
+            .params
+            .iter()
+            .find(|entry| entry.name.trim_start_matches('*') == parameter.name);
+        parameter.description = entry.map(|entry| entry.description.clone());
+        parameter.docstring_type = entry.and_then(|entry| entry.type_name.clone());
+        if parameter.annotation.is_some() || parameter.docstring_type.is_some() {
+            parameter.inferred_type = None;
+        }
+    }
+}
+
+/// Collects the exception types raised by `raise X(...)` and `raise X` in a
+/// function body. Names bound by an enclosing `except ... as name` are
+/// re-raises of the caught exception and are skipped.
+fn raised_exceptions(body: &[ast::Stmt]) -> Vec<String> {
+    fn walk(body: &[ast::Stmt], caught: &mut Vec<String>, raises: &mut Vec<String>) {
+        for stmt in body {
+            match stmt {
+                ast::Stmt::Raise(raise) => {
+                    let Some(exc) = raise.exc.as_deref() else {
+                        continue;
+                    };
+                    let exc = match exc {
+                        ast::Expr::Call(call) => &*call.func,
+                        exc => exc,
+                    };
+                    let name = extract_type(exc);
+                    if !caught.contains(&name) && !raises.contains(&name) {
+                        raises.push(name);
+                    }
+                }
+                ast::Stmt::If(stmt) => {
+                    walk(&stmt.body, caught, raises);
+                    walk(&stmt.orelse, caught, raises);
+                }
+                ast::Stmt::For(stmt) => {
+                    walk(&stmt.body, caught, raises);
+                    walk(&stmt.orelse, caught, raises);
+                }
+                ast::Stmt::AsyncFor(stmt) => {
+                    walk(&stmt.body, caught, raises);
+                    walk(&stmt.orelse, caught, raises);
+                }
+                ast::Stmt::While(stmt) => {
+                    walk(&stmt.body, caught, raises);
+                    walk(&stmt.orelse, caught, raises);
+                }
+                ast::Stmt::With(stmt) => walk(&stmt.body, caught, raises),
+                ast::Stmt::AsyncWith(stmt) => walk(&stmt.body, caught, raises),
+                ast::Stmt::Match(stmt) => {
+                    for case in &stmt.cases {
+                        walk(&case.body, caught, raises);
+                    }
+                }
+                ast::Stmt::Try(ast::StmtTry {
+                    body,
+                    handlers,
+                    orelse,
+                    finalbody,
+                    ..
+                })
+                | ast::Stmt::TryStar(ast::StmtTryStar {
+                    body,
+                    handlers,
+                    orelse,
+                    finalbody,
+                    ..
+                }) => {
+                    walk(body, caught, raises);
+                    for handler in handlers {
+                        let ast::ExceptHandler::ExceptHandler(handler) = handler;
+                        let name = handler.name.as_ref().map(|name| name.to_string());
+                        caught.extend(name.clone());
+                        walk(&handler.body, caught, raises);
+                        if name.is_some() {
+                            caught.pop();
+                        }
+                    }
+                    walk(orelse, caught, raises);
+                    walk(finalbody, caught, raises);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut raises = Vec::new();
+    walk(body, &mut Vec::new(), &mut raises);
+    raises
+}
+
+fn extract_parameters(ctx: &Context, args: &ast::Arguments) -> Vec<Parameter> {
+    let named = |arg: &ast::ArgWithDefault, kind: ParameterKind| Parameter {
+        name: arg.def.arg.to_string(),
+        kind,
+        annotation: arg.def.annotation.as_deref().map(|ann| ctx.annotation(ann)),
+        default: arg.default.as_deref().map(expr_to_source),
+        description: None,
+        docstring_type: None,
+        inferred_type: arg
+            .default
+            .as_deref()
+            .filter(|_| ctx.options.infer_types && arg.def.annotation.is_none())
+            .and_then(infer_type),
+    };
+    let mut parameters: Vec<Parameter> = args
+        .posonlyargs
+        .iter()
+        .map(|arg| named(arg, ParameterKind::PositionalOnly))
+        .chain(
+            args.args
+                .iter()
+                .map(|arg| named(arg, ParameterKind::Single)),
+        )
+        .collect();
+
+    let variadic = |arg: &ast::Arg, kind: ParameterKind| Parameter {
+        name: arg.arg.to_string(),
+        kind,
+        annotation: arg.annotation.as_deref().map(|ann| ctx.annotation(ann)),
+        default: None,
+        description: None,
+        docstring_type: None,
+        inferred_type: None,
+    };
+    parameters.extend(
+        args.vararg
+            .as_deref()
+            .map(|arg| variadic(arg, ParameterKind::VarPositional)),
+    );
+
+    parameters.extend(
+        args.kwonlyargs
+            .iter()
+            .map(|arg| named(arg, ParameterKind::Single)),
+    );
+    parameters.extend(
+        args.kwarg
+            .as_deref()
+            .map(|arg| variadic(arg, ParameterKind::VarKeyword)),
+    );
+
+    parameters
+}
@@ -0,0 +1,240 @@
+//! Platform-independent path rendering and input file discovery.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Renders `path` with `/` separators so output is identical on every
+/// platform. Prefixes such as Windows drive letters are kept verbatim.
+pub fn normalize_path(path: &Path) -> String {
+    let mut rendered = String::new();
+    for component in path.components() {
+        match component {
+            Component::RootDir => rendered.push('/'),
+            Component::Prefix(prefix) => rendered.push_str(&prefix.as_os_str().to_string_lossy()),
+            Component::CurDir => push_segment(&mut rendered, "."),
+            Component::ParentDir => push_segment(&mut rendered, ".."),
+            Component::Normal(segment) => push_segment(&mut rendered, &segment.to_string_lossy()),
+        }
+    }
+    rendered
+}
+
+fn push_segment(rendered: &mut String, segment: &str) {
+    if !rendered.is_empty() && !rendered.ends_with('/') {
+        rendered.push('/');
+    }
+    rendered.push_str(segment);
+}
+
+/// Dotted module name of a source file given relative to the package root,
+/// e.g. `client/base.py` is `client.base`. A package's `__init__.py` keeps
+/// its `__init__` segment, as in [`ModuleDoc::qualified_name`].
+///
+/// [`ModuleDoc::qualified_name`]: crate::model::ModuleDoc::qualified_name
+pub fn dotted_module_name(relative: &Path) -> String {
+    let mut segments: Vec<String> = relative
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.components())
+        .filter_map(|component| match component {
+            Component::Normal(segment) => Some(segment.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    if let Some(stem) = relative.file_stem() {
+        segments.push(stem.to_string_lossy().into_owned());
+    }
+    segments.join(".")
+}
+
+/// Dotted path of `name` in the package `prefix`; an empty prefix leaves
+/// the name as it is.
+pub fn qualified_name(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+/// Every `.py` file under `root`, and every `.pyi` stub without a `.py`
+/// file next to it, sorted by path. Stubs next to a `.py` file are merged
+/// into its module instead. Hidden files and directories and `__pycache__`
+/// directories are skipped, and symlinks unless `follow_symlinks`.
+pub fn discover_python_files(root: &Path, follow_symlinks: bool) -> io::Result<Vec<PathBuf>> {
+    let mut files = files_under(root, follow_symlinks)?;
+    let sources: BTreeSet<PathBuf> = files
+        .iter()
+        .filter(|path| path.extension().is_some_and(|extension| extension == "py"))
+        .cloned()
+        .collect();
+    files.retain(
+        |path| match path.extension().and_then(|extension| extension.to_str()) {
+            Some("py") => true,
+            Some("pyi") => !sources.contains(&path.with_extension("py")),
+            _ => false,
+        },
+    );
+    Ok(files)
+}
+
+/// Whether `pattern` contains a wildcard: `*`, `**` or `?`.
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// The directory a glob pattern is matched under: its leading components
+/// without wildcards, e.g. `src/zenml` for `src/zenml/**/*.py`.
+pub fn glob_base(pattern: &str) -> PathBuf {
+    let pattern = pattern.replace('\\', "/");
+    let literal: Vec<&str> = pattern
+        .split('/')
+        .take_while(|component| !is_glob(component))
+        .collect();
+    if literal.is_empty() {
+        return PathBuf::from(".");
+    }
+    let base = literal.join("/");
+    PathBuf::from(if base.is_empty() { "/" } else { &base })
+}
+
+/// Files matching a glob pattern, sorted by path. `*` and `?` match any
+/// characters and any one character of a file or directory name, and `**`
+/// matches any number of directories. Separators may be `/` or `\`.
+///
+/// As in shells, wildcards do not match a leading `.`, so hidden files are
+/// left out; `__pycache__` directories are skipped too, and symlinks unless
+/// `follow_symlinks`.
+pub fn expand_glob(pattern: &str, follow_symlinks: bool) -> io::Result<Vec<PathBuf>> {
+    let normalized = pattern.replace('\\', "/");
+    let base = glob_base(&normalized);
+    let components: Vec<&str> = normalized
+        .split('/')
+        .skip_while(|component| !is_glob(component))
+        .filter(|component| !component.is_empty())
+        .collect();
+    if !base.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = files_under(&base, follow_symlinks)?;
+    files.retain(|file| matches_path(&components, file.strip_prefix(&base).unwrap_or(file)));
+    Ok(files)
+}
+
+/// Whether a relative `path` matches a glob pattern as a whole, e.g.
+/// `pkg/tests/test_client.py` matches `**/tests/**`.
+pub fn glob_matches(pattern: &str, path: &Path) -> bool {
+    let pattern = pattern.replace('\\', "/");
+    let components: Vec<&str> = pattern
+        .split('/')
+        .filter(|component| !component.is_empty() && *component != ".")
+        .collect();
+    matches_path(&components, path)
+}
+
+/// Every file under `root`, sorted by path, without hidden entries and
+/// `__pycache__` directories.
+///
+/// Symlinks are skipped unless `follow_symlinks`; followed, a directory is
+/// walked only once however many links lead to it, so that link cycles
+/// end, and a file reached through several paths is listed once, by one
+/// that is not a link itself if there is one. Broken links are skipped with
+/// a warning.
+pub(crate) fn files_under(root: &Path, follow_symlinks: bool) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut visited = BTreeSet::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        if !visited.insert(fs::canonicalize(&dir)?) {
+            tracing::debug!("skipping {}, already walked", normalize_path(&dir));
+            continue;
+        }
+        let mut directories = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if name.starts_with('.') || name == "__pycache__" {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            let is_dir = if !file_type.is_symlink() {
+                file_type.is_dir()
+            } else if !follow_symlinks {
+                tracing::debug!("skipping symlink {}", normalize_path(&path));
+                continue;
+            } else {
+                match fs::metadata(&path) {
+                    Ok(target) => target.is_dir(),
+                    Err(err) => {
+                        tracing::warn!(
+                            "skipping broken symlink {}: {}",
+                            normalize_path(&path),
+                            err
+                        );
+                        continue;
+                    }
+                }
+            };
+            if is_dir {
+                directories.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+        // Walked in order, so that the first path to a directory wins.
+        directories.sort();
+        pending.extend(directories.into_iter().rev());
+    }
+    if follow_symlinks {
+        // Links to a file come after the file itself, so that it is kept.
+        files.sort_by_key(|file| (file.is_symlink(), file.clone()));
+        let mut seen = BTreeSet::new();
+        files.retain(|file| seen.insert(fs::canonicalize(file).unwrap_or_else(|_| file.clone())));
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn matches_path(pattern: &[&str], path: &Path) -> bool {
+    let names: Vec<String> = path
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    matches_components(pattern, &names)
+}
+
+fn matches_components(pattern: &[&str], names: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => names.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=names.len()).any(|skip| matches_components(rest, &names[skip..]))
+        }
+        Some((first, rest)) => names.split_first().is_some_and(|(name, tail)| {
+            matches_name(first, name) && matches_components(rest, tail)
+        }),
+    }
+}
+
+/// Matches one file or directory name against one pattern component.
+fn matches_name(pattern: &str, name: &str) -> bool {
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches_chars(&pattern, &name)
+}
+
+fn matches_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| matches_chars(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && matches_chars(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && matches_chars(rest, &name[1..]),
+    }
+}
@@ -0,0 +1,524 @@
+//! A whole-project view over every documented module.
+//!
+//! [`Project`] owns the parsed modules and a symbol table keyed by dotted
+//! path, so renderers can answer "where is this name documented?" across
+//! module boundaries, following imports and re-exports.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::diagnostics::{Category, Diagnostic, Severity};
+use crate::model::{ClassDoc, FunctionDoc, Import, Item, ModuleDoc};
+
+/// How deep re-export chains are followed before giving up.
+const MAX_REEXPORT_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefinitionKind {
+    Module,
+    Class,
+    Function,
+    Method,
+    Property,
+    #[serde(rename = "type_alias")]
+    TypeAlias,
+}
+
+impl fmt::Display for DefinitionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DefinitionKind::Module => "module",
+            DefinitionKind::Class => "class",
+            DefinitionKind::Function => "function",
+            DefinitionKind::Method => "method",
+            DefinitionKind::Property => "property",
+            DefinitionKind::TypeAlias => "type alias",
+        })
+    }
+}
+
+/// A documented definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// Full dotted path, e.g. `zenml.client.Client.create_stack`. This is
+    /// also the anchor id of the symbol's entry on its page.
+    pub path: String,
+    pub kind: DefinitionKind,
+    /// Import path of the module defining the symbol.
+    pub module: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Project {
+    /// Modules ordered by import path.
+    modules: Vec<ModuleDoc>,
+    symbols: BTreeMap<String, Symbol>,
+    /// Documented bases of each class, by dotted path, in declaration order.
+    bases: BTreeMap<String, Vec<String>>,
+    /// Documented direct subclasses of each class, by dotted path.
+    subclasses: BTreeMap<String, Vec<String>>,
+    /// Symbols naming each class and function, by dotted path.
+    references: BTreeMap<String, Vec<String>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Project {
+    /// Builds the symbol table over `modules`. A module whose import path was
+    /// already seen is dropped with a collision diagnostic.
+    pub fn new(modules: Vec<ModuleDoc>) -> Self {
+        let mut project = Project::default();
+        let mut by_path: BTreeMap<String, ModuleDoc> = BTreeMap::new();
+        for module in modules {
+            let path = module.import_path().to_string();
+            if let Some(existing) = by_path.get(&path) {
+                project.diagnostics.push(Diagnostic {
+                    severity: Severity::Warning,
+                    category: Category::Collision,
+                    file: module.path.clone(),
+                    line: 0,
+                    message: format!(
+                        "module `{}` is already documented from {}",
+                        path,
+                        crate::paths::normalize_path(&existing.path)
+                    ),
+                });
+                continue;
+            }
+            by_path.insert(path, module);
+        }
+
+        for module in by_path.values() {
+            project.insert_symbols(module);
+        }
+        project.modules = by_path.into_values().collect();
+        project.index_bases();
+        project.index_references();
+        project
+    }
+
+    /// Resolves the bases of every class to documented classes, recording
+    /// the inverse relation as subclasses.
+    fn index_bases(&mut self) {
+        let mut bases: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for module in &self.modules {
+            for (item, origin) in bindings(module) {
+                let Item::Class(class) = item else {
+                    continue;
+                };
+                let class_path = format!("{}.{}", module.import_path(), class.name);
+                let resolved = class
+                    .bases
+                    .iter()
+                    .filter_map(|base| {
+                        // `Generic[T]` names the class `Generic`.
+                        let name = base.split('[').next().unwrap_or(base);
+                        self.resolve_binding(module, origin, name)
+                    })
+                    .filter(|symbol| symbol.kind == DefinitionKind::Class)
+                    .filter(|symbol| symbol.path != class_path)
+                    .map(|symbol| symbol.path.clone())
+                    .collect::<Vec<_>>();
+                let entry = bases.entry(class_path).or_default();
+                for base in resolved {
+                    if !entry.contains(&base) {
+                        entry.push(base);
+                    }
+                }
+            }
+        }
+
+        for (class, class_bases) in &bases {
+            for base in class_bases {
+                self.subclasses
+                    .entry(base.clone())
+                    .or_default()
+                    .push(class.clone());
+            }
+        }
+        self.bases = bases;
+    }
+
+    /// Records, for each documented class and function, the symbols naming
+    /// it in an annotation, a base class list or a decorator. Uses inside
+    /// the symbol itself, such as a method returning its own class, are not
+    /// references.
+    fn index_references(&mut self) {
+        let mut references: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for module in &self.modules {
+            for (item, origin) in bindings(module) {
+                let item_path = format!("{}.{}", module.import_path(), item.name());
+                let mut uses: Vec<(String, &str)> = Vec::new();
+                match item {
+                    Item::Class(class) => {
+                        for expression in class.bases.iter().chain(&class.decorators) {
+                            uses.push((item_path.clone(), expression));
+                        }
+                        for method in &class.methods {
+                            function_uses(
+                                format!("{}.{}", item_path, method.name),
+                                method,
+                                &mut uses,
+                            );
+                        }
+                        for property in &class.properties {
+                            if let Some(type_name) = &property.type_name {
+                                uses.push((format!("{}.{}", item_path, property.name), type_name));
+                            }
+                        }
+                    }
+                    Item::Function(function) => function_uses(item_path, function, &mut uses),
+                }
+
+                for (referrer, expression) in uses {
+                    for name in dotted_names(expression) {
+                        let Some(symbol) = self.resolve_binding(module, origin, name) else {
+                            continue;
+                        };
+                        let is_inside = referrer == symbol.path
+                            || referrer
+                                .strip_prefix(symbol.path.as_str())
+                                .is_some_and(|rest| rest.starts_with('.'));
+                        if !matches!(
+                            symbol.kind,
+                            DefinitionKind::Class | DefinitionKind::Function
+                        ) || is_inside
+                        {
+                            continue;
+                        }
+                        let entry = references.entry(symbol.path.clone()).or_default();
+                        if !entry.contains(&referrer) {
+                            entry.push(referrer.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        for referrers in references.values_mut() {
+            referrers.sort();
+        }
+        self.references = references;
+    }
+
+    /// Resolves `name` as written in the source of a binding of `module`:
+    /// a re-exported definition is resolved in its defining module first.
+    fn resolve_binding(
+        &self,
+        module: &ModuleDoc,
+        origin: Option<&str>,
+        name: &str,
+    ) -> Option<&Symbol> {
+        origin
+            .and_then(|origin| self.resolve(origin, name))
+            .or_else(|| self.resolve(module.import_path(), name))
+    }
+
+    fn insert_symbols(&mut self, module: &ModuleDoc) {
+        let module_path = module.import_path().to_string();
+        let mut insert = |path: String, kind, line| {
+            self.symbols.insert(
+                path.clone(),
+                Symbol {
+                    path,
+                    kind,
+                    module: module_path.clone(),
+                    line,
+                },
+            );
+        };
+
+        insert(module_path.clone(), DefinitionKind::Module, 1);
+        for alias in &module.aliases {
+            insert(
+                format!("{}.{}", module_path, alias.name),
+                DefinitionKind::TypeAlias,
+                alias.line,
+            );
+        }
+        // Re-exported definitions are bound at their import. Later bindings
+        // shadow earlier ones, as they do at runtime.
+        let mut bindings: Vec<(usize, &Item)> = module
+            .items
+            .iter()
+            .map(|item| (item.line(), item))
+            .chain(
+                module
+                    .reexports
+                    .iter()
+                    .map(|reexport| (reexport.line, &reexport.item)),
+            )
+            .collect();
+        bindings.sort_by_key(|(line, _)| *line);
+        for (line, item) in bindings {
+            match item {
+                Item::Class(class) => {
+                    let mut classes =
+                        vec![(format!("{}.{}", module_path, class.name), class, line)];
+                    while let Some((class_path, class, line)) = classes.pop() {
+                        for method in &class.methods {
+                            insert(
+                                format!("{}.{}", class_path, method.name),
+                                DefinitionKind::Method,
+                                method.line,
+                            );
+                        }
+                        for property in &class.properties {
+                            insert(
+                                format!("{}.{}", class_path, property.name),
+                                DefinitionKind::Property,
+                                property.line,
+                            );
+                        }
+                        for nested in &class.classes {
+                            classes.push((
+                                format!("{}.{}", class_path, nested.name),
+                                nested,
+                                nested.line,
+                            ));
+                        }
+                        insert(class_path, DefinitionKind::Class, line);
+                    }
+                }
+                Item::Function(function) => insert(
+                    format!("{}.{}", module_path, function.name),
+                    DefinitionKind::Function,
+                    line,
+                ),
+            }
+        }
+    }
+
+    pub fn modules(&self) -> &[ModuleDoc] {
+        &self.modules
+    }
+
+    /// Looks up a module by import path.
+    pub fn module(&self, path: &str) -> Option<&ModuleDoc> {
+        self.modules
+            .binary_search_by(|module| module.import_path().cmp(path))
+            .ok()
+            .map(|index| &self.modules[index])
+    }
+
+    /// Looks up a symbol by its full dotted path.
+    pub fn symbol(&self, path: &str) -> Option<&Symbol> {
+        self.symbols.get(path)
+    }
+
+    /// Looks up a documented module-level class by its full dotted path.
+    pub fn class(&self, path: &str) -> Option<&ClassDoc> {
+        let symbol = self
+            .symbol(path)
+            .filter(|symbol| symbol.kind == DefinitionKind::Class)?;
+        let name = path.strip_prefix(&symbol.module)?.strip_prefix('.')?;
+        bindings(self.module(&symbol.module)?).find_map(|(item, _)| match item {
+            Item::Class(class) if class.name == name => Some(class),
+            _ => None,
+        })
+    }
+
+    /// All symbols, ordered by dotted path.
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values()
+    }
+
+    /// Documented classes the class at `path` derives from directly.
+    pub fn bases(&self, path: &str) -> &[String] {
+        self.bases.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Documented classes deriving directly from the class at `path`,
+    /// across every module of the project.
+    pub fn subclasses(&self, path: &str) -> &[String] {
+        self.subclasses.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Symbols naming the class or function at `path` in an annotation, a
+    /// base class list or a decorator, ordered by dotted path.
+    pub fn referenced_by(&self, path: &str) -> &[String] {
+        self.references.get(path).map_or(&[], Vec::as_slice)
+    }
+
+    /// Problems found while building the project, such as module collisions.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Resolves `name` as written in module `module` (e.g. `Stack`,
+    /// `models.Stack` or `zenml.models.Stack`) to the documented definition
+    /// it refers to, following imports, aliases and re-exports.
+    pub fn resolve(&self, module: &str, name: &str) -> Option<&Symbol> {
+        self.resolve_in(module, name, 0)
+    }
+
+    fn resolve_in(&self, module_path: &str, name: &str, depth: usize) -> Option<&Symbol> {
+        if depth > MAX_REEXPORT_DEPTH {
+            return None;
+        }
+        let Some(module) = self.module(module_path) else {
+            return self.lookup(name, depth);
+        };
+        let (head, rest) = match name.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (name, None),
+        };
+
+        // The binding that comes last in the module wins.
+        let definition_line = module
+            .items
+            .iter()
+            .filter(|item| item.name() == head)
+            .map(Item::line)
+            .chain(
+                module
+                    .reexports
+                    .iter()
+                    .filter(|reexport| reexport.item.name() == head)
+                    .map(|reexport| reexport.line),
+            )
+            .chain(
+                module
+                    .aliases
+                    .iter()
+                    .filter(|alias| alias.name == head)
+                    .map(|alias| alias.line),
+            )
+            .max();
+        let import = module
+            .imports
+            .iter()
+            .filter(|import| import.local_name == head)
+            .max_by_key(|import| import.line);
+        let target = match (definition_line, import) {
+            (Some(line), Some(import)) if import.line > line => import.target(module),
+            (Some(_), _) => format!("{}.{}", module_path, head),
+            (None, Some(import)) => import.target(module),
+            (None, None) => return self.lookup(name, depth),
+        };
+
+        let full = match rest {
+            Some(rest) => format!("{}.{}", target, rest),
+            None => target,
+        };
+        self.lookup(&full, depth)
+    }
+
+    /// Looks up an absolute dotted path, resolving the remainder inside the
+    /// longest documented module prefix when it is not a symbol itself.
+    fn lookup(&self, path: &str, depth: usize) -> Option<&Symbol> {
+        if let Some(symbol) = self.symbols.get(path) {
+            return Some(symbol);
+        }
+        let mut end = path.len();
+        while let Some(dot) = path[..end].rfind('.') {
+            let (module, rest) = (&path[..dot], &path[dot + 1..]);
+            if self.module(module).is_some() {
+                return self.resolve_in(module, rest, depth + 1);
+            }
+            end = dot;
+        }
+        None
+    }
+
+    /// Path of a module's page relative to the output root, without the file
+    /// extension and with `/` separators.
+    pub fn page(&self, module: &str) -> Option<String> {
+        self.module(module).map(|module| module.page.clone())
+    }
+
+    /// Link from the page of module `from` to the entry of `symbol`.
+    pub fn link(&self, from: &str, symbol: &Symbol) -> String {
+        let anchor = if symbol.kind == DefinitionKind::Module {
+            String::new()
+        } else {
+            format!("#{}", symbol.path)
+        };
+        match (self.page(from), self.page(&symbol.module)) {
+            (Some(from_page), Some(to_page)) if from_page == to_page => anchor,
+            (Some(from_page), Some(to_page)) => {
+                format!("{}{}", relative_page(&from_page, &to_page), anchor)
+            }
+            _ => anchor,
+        }
+    }
+}
+
+/// A module's own definitions and its re-exported ones, with the module
+/// each re-exported definition comes from.
+fn bindings(module: &ModuleDoc) -> impl Iterator<Item = (&Item, Option<&str>)> {
+    module.items.iter().map(|item| (item, None)).chain(
+        module
+            .reexports
+            .iter()
+            .map(|reexport| (&reexport.item, Some(reexport.module.as_str()))),
+    )
+}
+
+/// Collects the annotations and decorators of a function as uses by the
+/// symbol at `path`.
+fn function_uses<'a>(path: String, function: &'a FunctionDoc, uses: &mut Vec<(String, &'a str)>) {
+    let annotations = function
+        .parameters
+        .iter()
+        .filter_map(|parameter| parameter.annotation.as_deref())
+        .chain(function.returns.as_deref());
+    for expression in annotations.chain(function.decorators.iter().map(String::as_str)) {
+        uses.push((path.clone(), expression));
+    }
+}
+
+/// The dotted names in a rendered expression, e.g. `Optional` and
+/// `models.Stack` in `Optional[models.Stack]`.
+fn dotted_names(expression: &str) -> impl Iterator<Item = &str> {
+    expression
+        .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .map(|name| name.trim_matches('.'))
+        .filter(|name| name.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+}
+
+impl Import {
+    /// Absolute dotted path the import refers to, resolving relative imports
+    /// against the importing module's package.
+    pub fn target(&self, importer: &ModuleDoc) -> String {
+        let base = if self.level == 0 {
+            self.module.clone()
+        } else {
+            let mut package: Vec<&str> = importer.import_path().split('.').collect();
+            if !importer.is_package() {
+                package.pop();
+            }
+            for _ in 1..self.level {
+                package.pop();
+            }
+            if !self.module.is_empty() {
+                package.push(&self.module);
+            }
+            package.join(".")
+        };
+        match &self.name {
+            Some(name) if base.is_empty() => name.clone(),
+            Some(name) => format!("{}.{}", base, name),
+            None => base,
+        }
+    }
+}
+
+/// Relative link between two pages given as `/`-separated paths.
+pub(crate) fn relative_page(from: &str, to: &str) -> String {
+    let from_dirs: Vec<&str> = from.split('/').collect();
+    let from_dirs = &from_dirs[..from_dirs.len() - 1];
+    let to_parts: Vec<&str> = to.split('/').collect();
+    let common = from_dirs
+        .iter()
+        .zip(&to_parts)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(to_parts.len() - 1);
+    let mut parts: Vec<&str> = vec![".."; from_dirs.len() - common];
+    parts.extend(&to_parts[common..]);
+    parts.join("/")
+}
@@ -0,0 +1,121 @@
+//! Documenting symbols that a module re-exports from private modules.
+//!
+//! `from ._models import Stack` in a public module promises `Stack` as part
+//! of that module's API, but `_models` gets no page of its own. The
+//! definition is parsed out of the private module and attached to the
+//! public one as a [`Reexport`].
+
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{Category, Diagnostic, Severity};
+use crate::encoding::read_source;
+use crate::model::{Import, ModuleDoc, Reexport};
+use crate::options::{is_private, RenderOptions};
+use crate::parse::parse_module;
+
+/// How many private modules are followed when one re-exports from another.
+const MAX_DEPTH: usize = 8;
+
+/// Attaches the definitions `module` imports by name from private modules
+/// with a relative import, e.g. `from ._models import Stack`. Private modules
+/// are located next to `module.path` and read from disk; imports whose
+/// module cannot be found are left alone, and names the module does not
+/// define produce an unresolved-reference diagnostic.
+pub fn inline_private_reexports(module: &mut ModuleDoc, options: &RenderOptions) {
+    let _span = tracing::debug_span!("reexports").entered();
+
+    let mut reexports = Vec::new();
+    let mut diagnostics = Vec::new();
+    for import in &module.imports {
+        let Some(name) = &import.name else {
+            continue;
+        };
+        if import.level == 0 || !import.module.split('.').any(is_private) {
+            continue;
+        }
+        let Some(file) = module_file(&module.path, import) else {
+            continue;
+        };
+        let defining_module = import
+            .target(module)
+            .rsplit_once('.')
+            .map_or_else(String::new, |(module, _)| module.to_string());
+
+        match find_definition(&file, &defining_module, name, options, 0) {
+            Some(mut reexport) => {
+                reexport.item = reexport.item.renamed(&import.local_name);
+                reexport.line = import.line;
+                reexports.push(reexport);
+            }
+            None => diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                category: Category::UnresolvedReference,
+                file: module.path.clone(),
+                line: import.line,
+                message: format!("`{}` is not defined in `{}`", name, defining_module),
+            }),
+        }
+    }
+    module.reexports.extend(reexports);
+    module.diagnostics.extend(diagnostics);
+}
+
+/// Finds `name` in the module at `file`, following the module's own
+/// private re-exports.
+fn find_definition(
+    file: &Path,
+    module_path: &str,
+    name: &str,
+    options: &RenderOptions,
+    depth: usize,
+) -> Option<Reexport> {
+    let source = read_source(file).ok()?;
+    let mut module = parse_module(&source, file, options).ok()?;
+    if let Some(item) = module.items.iter().rev().find(|item| item.name() == name) {
+        return Some(Reexport {
+            item: item.clone(),
+            original_name: name.to_string(),
+            module: module_path.to_string(),
+            path: file.to_path_buf(),
+            line: 0,
+        });
+    }
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+
+    // Resolve the import against the private module's real dotted path.
+    module.qualified_name = module_path.to_string();
+    let import = module
+        .imports
+        .iter()
+        .rev()
+        .find(|import| import.local_name == name && import.level > 0)?;
+    let target = import.target(&module);
+    let (next_module, next_name) = target.rsplit_once('.')?;
+    let next_file = module_file(file, import)?;
+    find_definition(&next_file, next_module, next_name, options, depth + 1)
+}
+
+/// Locates the file of the module a relative import refers to.
+fn module_file(importer: &Path, import: &Import) -> Option<PathBuf> {
+    let mut dir = importer.parent()?.to_path_buf();
+    for _ in 1..import.level {
+        dir = dir.parent()?.to_path_buf();
+    }
+    for segment in import
+        .module
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+    {
+        dir.push(segment);
+    }
+    [
+        dir.with_extension("py"),
+        dir.with_extension("pyi"),
+        dir.join("__init__.py"),
+        dir.join("__init__.pyi"),
+    ]
+    .into_iter()
+    .find(|candidate| candidate.is_file())
+}
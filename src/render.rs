@@ -0,0 +1,1661 @@
+//! The render stage: turns a [`ModuleDoc`] into an MDX or Markdown page.
+
+use crate::docstring::{clean_docstring, VersionKind, VersionMarker};
+use crate::locale::fill;
+use crate::model::{
+    AttributeDoc, Badge, ClassDoc, DataClassDoc, DataClassKind, FunctionDoc, Item, MethodKind,
+    ModuleDoc, Parameter, ParameterKind, PropertyDoc, Reexport, TypeAlias, TypeParameter,
+};
+use crate::options::{AliasStyle, NestedFunctions, RenderOptions};
+use crate::paths::normalize_path;
+use crate::project::Project;
+use crate::source::synthesized_init;
+
+/// State shared by the rendering helpers for one page.
+struct Page<'a> {
+    module: &'a ModuleDoc,
+    project: &'a Project,
+    options: &'a RenderOptions,
+}
+
+impl Page<'_> {
+    /// Anchor placed before the entry of a class, function or method.
+    fn anchor(&self, symbol: &str) -> String {
+        format!("<a id=\"{}.{}\"></a>\n", self.module.import_path(), symbol)
+    }
+
+    /// Renders a type for a table cell, where `|` in a union such as
+    /// `int | None` must be escaped.
+    fn type_cell(&self, type_name: &str) -> String {
+        self.type_text(type_name).replace('|', "\\|")
+    }
+
+    /// Renders a type, showing types that name a type alias of this module
+    /// the way [`RenderOptions::alias_style`] asks for.
+    fn type_text(&self, type_name: &str) -> String {
+        let expanded = self.expand_aliases(type_name, 0);
+        match self.options.alias_style {
+            AliasStyle::Link => self.linked_type(type_name),
+            AliasStyle::Expand => self.linked_type(&expanded),
+            AliasStyle::Both if expanded != type_name => {
+                format!("{} (`{}`)", self.linked_type(type_name), expanded)
+            }
+            AliasStyle::Both => self.linked_type(type_name),
+        }
+    }
+
+    /// Renders a type, linking it when it names a documented symbol.
+    fn linked_type(&self, type_name: &str) -> String {
+        let is_dotted_name = type_name
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_'));
+        match self.project.resolve(self.module.import_path(), type_name) {
+            Some(symbol) if is_dotted_name => format!(
+                "[`{}`]({})",
+                type_name,
+                self.project.link(self.module.import_path(), symbol)
+            ),
+            _ => format!("`{}`", type_name),
+        }
+    }
+
+    /// Replaces the names of this module's type aliases in `type_name` with
+    /// the types they stand for, including aliases of aliases.
+    fn expand_aliases(&self, type_name: &str, depth: usize) -> String {
+        if depth > MAX_ALIAS_DEPTH || self.module.aliases.is_empty() {
+            return type_name.to_string();
+        }
+        let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+        let mut expanded = String::new();
+        let mut rest = type_name;
+        while !rest.is_empty() {
+            let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            let (name, after) = rest.split_at(end.max(1));
+            match self.module.aliases.iter().find(|alias| alias.name == name) {
+                Some(alias) => expanded.push_str(&self.expand_aliases(&alias.value, depth + 1)),
+                None => expanded.push_str(name),
+            }
+            rest = after;
+        }
+        expanded
+    }
+}
+
+/// The YAML block at the top of a module's page. Keys from the options
+/// replace the built-in ones of the same name, and the others follow them
+/// in key order.
+fn frontmatter(module: &ModuleDoc, options: &RenderOptions) -> String {
+    let title = options.page_title(module);
+    let mut entries = vec![("title".to_string(), yaml_scalar(&title))];
+    if options.page_id {
+        let id = module.page.rsplit('/').next().unwrap_or(&module.page);
+        entries.push(("id".to_string(), yaml_scalar(id)));
+    }
+    if let Some(version) = &options.doc_version {
+        // Quoted so that versions such as `1.10` stay strings.
+        entries.push(("version".to_string(), yaml_quoted(version)));
+    }
+    if let Some(timestamp) = &options.timestamp {
+        entries.push(("generated".to_string(), timestamp.clone()));
+    }
+    let custom = options.frontmatter.iter().chain(
+        options
+            .module_frontmatter
+            .get(&module.qualified_name)
+            .into_iter()
+            .flatten(),
+    );
+    for (key, value) in custom {
+        match entries.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = yaml_scalar(value),
+            None => entries.push((key.clone(), yaml_scalar(value))),
+        }
+    }
+
+    let mut block = String::from("---\n");
+    for (key, value) in entries {
+        block.push_str(&format!("{}: {}\n", key, value));
+    }
+    block.push_str("---\n\n");
+    block
+}
+
+/// `value` as a YAML scalar: as it is when it reads back as the same
+/// string, e.g. `Client`, and double-quoted otherwise. Values YAML would
+/// read as a number, bool or null, such as `1.10`, `yes` or `null`, are
+/// quoted too.
+pub(crate) fn yaml_scalar(value: &str) -> String {
+    let plain = value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && !value.ends_with(' ')
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || " _-./()".contains(c))
+        && !matches!(
+            value.to_ascii_lowercase().as_str(),
+            "null" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+        );
+    if plain {
+        value.to_string()
+    } else {
+        yaml_quoted(value)
+    }
+}
+
+fn yaml_quoted(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// How deep aliases of aliases are expanded before giving up.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Renders a parsed module as an MDX page. `project` is used to link to
+/// other documented symbols and must contain `module`.
+#[tracing::instrument(name = "render", skip_all, fields(file = %normalize_path(&module.path)))]
+pub fn render_module(module: &ModuleDoc, project: &Project, options: &RenderOptions) -> String {
+    let page = Page {
+        module,
+        project,
+        options,
+    };
+
+    let mut markdown_content = String::new();
+
+    // Add the module header
+    markdown_content.push_str(&frontmatter(module, options));
+    markdown_content.push_str(&format!("## `{}`", module.qualified_name));
+    if options.special_tag {
+        markdown_content.push_str(&format!(" `{}`", options.strings.special));
+    }
+    markdown_content.push_str("\n\n");
+
+    // Add the module docstring if it exists
+    if let Some(docstring) = &module.docstring {
+        markdown_content.push_str(&format!("{}\n\n", docstring));
+    }
+
+    let mut entries: Vec<(&Item, Option<&Reexport>)> = module
+        .items
+        .iter()
+        .map(|item| (item, None))
+        .chain(
+            module
+                .reexports
+                .iter()
+                .map(|reexport| (&reexport.item, Some(reexport))),
+        )
+        .filter(|(item, _)| options.documents_export(module, item.name()))
+        .collect();
+    if options.deprecated_last {
+        entries.sort_by_key(|(item, _)| item.deprecated().is_some());
+    }
+    let aliases: Vec<&TypeAlias> = module
+        .aliases
+        .iter()
+        .filter(|alias| options.documents_export(module, &alias.name))
+        .collect();
+    let documented: Vec<&Item> = entries.iter().map(|(item, _)| *item).collect();
+    markdown_content.push_str(&format_summary(&page, &aliases, &documented));
+    markdown_content.push_str(&format_reexports(&page, &aliases, &documented));
+
+    for alias in aliases {
+        ensure_blank_line(&mut markdown_content);
+        markdown_content.push_str(&format_alias_doc(&page, alias));
+    }
+    let attributes = format_module_attributes(&page);
+    if !attributes.is_empty() {
+        ensure_blank_line(&mut markdown_content);
+        markdown_content.push_str(&attributes);
+    }
+
+    let mut prose = module.prose.iter().peekable();
+    for (item, origin) in entries {
+        if origin.is_none() {
+            while let Some(text) = prose.next_if(|prose| prose.line <= item.line()) {
+                ensure_blank_line(&mut markdown_content);
+                markdown_content.push_str(&format!("{}\n", text.text));
+            }
+        }
+        ensure_blank_line(&mut markdown_content);
+        match item {
+            Item::Class(class) => {
+                markdown_content.push_str(&format_class_doc(&page, class, &class.name, 3, origin))
+            }
+            Item::Function(function) => {
+                markdown_content.push_str(&format_function_doc(&page, function, origin))
+            }
+        }
+    }
+    for text in prose {
+        ensure_blank_line(&mut markdown_content);
+        markdown_content.push_str(&format!("{}\n", text.text));
+    }
+
+    markdown_content
+}
+
+/// Table of the documented module-level variables with their values.
+fn format_module_attributes(page: &Page) -> String {
+    let attributes: Vec<&AttributeDoc> = page
+        .module
+        .attributes
+        .iter()
+        .filter(|attribute| {
+            page.options
+                .documents_module_attribute(page.module, &attribute.name)
+        })
+        .collect();
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let strings = &page.options.strings;
+    let mut table = format!(
+        "**{}:**\n\n| {} | {} | {} | {} |\n| --- | --- | --- | --- |\n",
+        strings.module_attributes,
+        strings.name,
+        strings.type_name,
+        strings.description,
+        strings.value
+    );
+    for attribute in attributes {
+        let value = attribute.default.as_deref().map_or_else(
+            || "–".to_string(),
+            |value| format!("`{}`", table_cell(value)),
+        );
+        table.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            attribute.name,
+            page.type_cell(attribute.type_name.as_deref().unwrap_or("Any")),
+            first_line(attribute.docstring.as_deref()),
+            value
+        ));
+    }
+    table
+}
+
+/// Keeps text on one line and out of the way of the table syntax.
+pub(crate) fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+/// The first line of a docstring, for summary tables.
+fn first_line(docstring: Option<&str>) -> String {
+    let cleaned = docstring.map(clean_docstring).unwrap_or_default();
+    table_cell(cleaned.lines().next().unwrap_or(""))
+}
+
+/// A table of the type aliases, classes and functions on the page, linked
+/// to their entries, followed by a table of each class's methods and
+/// properties.
+fn format_summary(page: &Page, aliases: &[&TypeAlias], items: &[&Item]) -> String {
+    if aliases.is_empty() && items.is_empty() {
+        return String::new();
+    }
+    let strings = &page.options.strings;
+    let header = format!(
+        "| {} | {} | {} |\n| --- | --- | --- |\n",
+        strings.name, strings.kind, strings.description
+    );
+
+    let row = |symbol: &str, label: &str, kind: &str, docstring: Option<&str>| {
+        format!(
+            "| [`{}`](#{}.{}) | {} | {} |\n",
+            label,
+            page.module.import_path(),
+            symbol,
+            kind,
+            first_line(docstring)
+        )
+    };
+    let mut summary = header.clone();
+    for alias in aliases {
+        summary.push_str(&row(
+            &alias.name,
+            &alias.name,
+            &strings.type_alias,
+            alias.docstring.as_deref(),
+        ));
+    }
+    for item in items {
+        summary.push_str(&match item {
+            Item::Class(class) => row(
+                &class.name,
+                &class.name,
+                &strings.class,
+                class.docstring.as_deref(),
+            ),
+            Item::Function(function) => row(
+                &function.name,
+                &function.name,
+                &strings.function,
+                function.docstring.as_deref(),
+            ),
+        });
+    }
+    summary.push('\n');
+
+    for item in items {
+        let Item::Class(class) = item else {
+            continue;
+        };
+        let mut rows = String::new();
+        for property in class
+            .properties
+            .iter()
+            .filter(|property| page.options.documents(&property.name))
+        {
+            rows.push_str(&row(
+                &format!("{}.{}", class.name, property.name),
+                &property.name,
+                property_label(page, property),
+                property.docstring.as_deref(),
+            ));
+        }
+        for method in class
+            .methods
+            .iter()
+            .filter(|method| documents_member(page, class, method))
+        {
+            rows.push_str(&row(
+                &format!("{}.{}", class.name, method.name),
+                &format!("{}()", method.name),
+                &strings.method,
+                method.docstring.as_deref(),
+            ));
+        }
+        if !rows.is_empty() {
+            let class_name = format!("`{}`", class.name);
+            summary.push_str(&format!(
+                "**{}:**\n\n{}{}\n",
+                fill(&strings.members, &[("class", &class_name)]),
+                header,
+                rows
+            ));
+        }
+    }
+    summary
+}
+
+/// A table of the names a package's `__init__.py` imports from other
+/// modules, linked to the entries documenting them. Names documented on the
+/// page itself and names it does not export are skipped; names that are not
+/// documented in the project, such as those of other packages, are listed
+/// without a link.
+fn format_reexports(page: &Page, aliases: &[&TypeAlias], items: &[&Item]) -> String {
+    let module = page.module;
+    if !module.is_package() {
+        return String::new();
+    }
+    let mut rows = String::new();
+    for import in &module.imports {
+        if !matches!(import.name.as_deref(), Some(name) if name != "*") {
+            continue;
+        }
+        let name = import.local_name.as_str();
+        let documented = items.iter().any(|item| item.name() == name)
+            || aliases.iter().any(|alias| alias.name == name);
+        if documented || !page.options.documents_export(module, name) {
+            continue;
+        }
+        let cell = match page.project.resolve(module.import_path(), name) {
+            Some(symbol) if symbol.module != module.import_path() => format!(
+                "[`{}`]({})",
+                name,
+                page.project.link(module.import_path(), symbol)
+            ),
+            _ => format!("`{}`", name),
+        };
+        rows.push_str(&format!("| {} | `{}` |\n", cell, import.target(module)));
+    }
+    if rows.is_empty() {
+        return String::new();
+    }
+    let strings = &page.options.strings;
+    format!(
+        "**{}:**\n\n| {} | {} |\n| --- | --- |\n{}\n",
+        strings.reexports, strings.name, strings.imported_from, rows
+    )
+}
+
+/// Terminates the current block so the next anchor is not swallowed by a
+/// preceding table or paragraph.
+fn ensure_blank_line(out: &mut String) {
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn source_accordion_title(page: &Page, origin: Option<&Reexport>) -> String {
+    let path = match origin {
+        Some(origin) => format!("`{}`", normalize_path(&origin.path)),
+        None => format!("`{}`", normalize_path(&page.module.path)),
+    };
+    fill(&page.options.strings.source_code, &[("path", &path)])
+}
+
+/// Notes where a re-exported definition really lives.
+fn defined_in(page: &Page, origin: Option<&Reexport>) -> String {
+    let strings = &page.options.strings;
+    let Some(origin) = origin else {
+        return String::new();
+    };
+    let module = format!("`{}`", origin.module);
+    let note = if origin.original_name != origin.item.name() {
+        let name = format!("`{}`", origin.original_name);
+        fill(
+            &strings.defined_in_as,
+            &[("module", &module), ("name", &name)],
+        )
+    } else {
+        fill(&strings.defined_in, &[("module", &module)])
+    };
+    format!("*{}*\n\n", note)
+}
+
+fn format_alias_doc(page: &Page, alias: &TypeAlias) -> String {
+    let mut doc = String::new();
+    doc.push_str(&page.anchor(&alias.name));
+    let strings = &page.options.strings;
+    doc.push_str(&format!(
+        "### `{}` `{}`\n\n",
+        alias.name, strings.type_alias
+    ));
+    doc.push_str(&format!(
+        "**{}:** {}\n\n",
+        strings.type_name,
+        page.linked_type(&alias.value)
+    ));
+    if let Some(docstring) = &alias.docstring {
+        doc.push_str(&format!("{}\n\n", clean_docstring(docstring)));
+    }
+    doc
+}
+
+/// Labels appended to a heading, one code span per badge.
+fn badge_labels(badges: &[Badge]) -> String {
+    badges
+        .iter()
+        .map(|badge| format!(" `{}`", badge.label))
+        .collect()
+}
+
+/// Version badges shown under a heading, e.g. `Added in 0.40.0`.
+fn format_versions(page: &Page, versions: &[VersionMarker]) -> String {
+    if versions.is_empty() {
+        return String::new();
+    }
+    let strings = &page.options.strings;
+    let badges: Vec<String> = versions
+        .iter()
+        .map(|marker| {
+            let template = match marker.kind {
+                VersionKind::Added => &strings.added_in,
+                VersionKind::Changed => &strings.changed_in,
+            };
+            let badge = fill(template, &[("version", &marker.version)]);
+            match &marker.note {
+                Some(note) => format!("`{}` ({})", badge, note),
+                None => format!("`{}`", badge),
+            }
+        })
+        .collect();
+    format!("{}\n\n", badges.join(" "))
+}
+
+/// The callout at the top of the entry of a deprecated definition, with
+/// the message of its decorator.
+fn format_deprecation(page: &Page, deprecated: Option<&str>) -> String {
+    let Some(message) = deprecated else {
+        return String::new();
+    };
+    let label = &page.options.strings.deprecated;
+    let text = if message.is_empty() {
+        format!("**{}**", label)
+    } else {
+        format!("**{}:** {}", label, message)
+    };
+    page.options.format.warning(&text)
+}
+
+/// The base classes and class keywords of a class, each linked when it
+/// names a documented class. Empty for a class without either.
+fn format_bases(page: &Page, class: &ClassDoc) -> String {
+    // The metaclass is among the implementation notes instead.
+    let bases: Vec<String> = class
+        .bases
+        .iter()
+        .map(|base| page.type_text(base))
+        .chain(
+            class
+                .keywords
+                .iter()
+                .filter(|keyword| !keyword.starts_with("metaclass="))
+                .map(|keyword| format!("`{}`", keyword)),
+        )
+        .collect();
+    if bases.is_empty() {
+        return String::new();
+    }
+    format!(
+        "**{}:** {}\n\n",
+        page.options.strings.bases,
+        bases.join(", ")
+    )
+}
+
+/// The slots and metaclass of a class, linked when the metaclass is
+/// documented. Empty when it has neither.
+fn format_implementation_notes(page: &Page, class: &ClassDoc) -> String {
+    let strings = &page.options.strings;
+    let mut notes = String::new();
+    if let Some(slots) = &class.slots {
+        let names: Vec<String> = slots.iter().map(|name| format!("`{}`", name)).collect();
+        // `__slots__ = ()` leaves instances without any attributes.
+        let names = if names.is_empty() {
+            "–".to_string()
+        } else {
+            names.join(", ")
+        };
+        notes.push_str(&format!("- {}: {}\n", strings.slots, names));
+    }
+    if let Some(metaclass) = class.metaclass() {
+        notes.push_str(&format!(
+            "- {}: {}\n",
+            strings.metaclass,
+            page.type_text(metaclass)
+        ));
+    }
+    if notes.is_empty() {
+        return String::new();
+    }
+    format!("**{}:**\n\n{}\n", strings.implementation_notes, notes)
+}
+
+/// The type variables of a generic class or function, each with its
+/// bound or constraints, e.g. `T` (bound=`BaseModel`).
+fn format_type_params(page: &Page, type_params: &[TypeParameter]) -> String {
+    if type_params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<String> = type_params
+        .iter()
+        .map(|param| {
+            let restriction = match &param.bound {
+                Some(bound) => format!(" (bound={})", page.type_text(bound)),
+                None if !param.constraints.is_empty() => {
+                    let constraints: Vec<String> = param
+                        .constraints
+                        .iter()
+                        .map(|constraint| page.type_text(constraint))
+                        .collect();
+                    format!(" ({})", constraints.join(", "))
+                }
+                None => String::new(),
+            };
+            format!("`{}`{}", param.name, restriction)
+        })
+        .collect();
+    format!(
+        "**{}:** {}\n\n",
+        page.options.strings.type_parameters,
+        params.join(", ")
+    )
+}
+
+/// An admonition for each badge that carries a note.
+fn badge_notes(page: &Page, badges: &[Badge]) -> String {
+    badges
+        .iter()
+        .filter_map(|badge| badge.note.as_ref())
+        .map(|note| page.options.format.warning(note))
+        .collect()
+}
+
+/// Mermaid class diagram of the class, its documented ancestors and its
+/// direct documented subclasses. Empty when the class has neither.
+fn format_inheritance_diagram(page: &Page, name: &str) -> String {
+    let path = format!("{}.{}", page.module.import_path(), name);
+    let documented = |path: &String| {
+        page.options
+            .documents(path.rsplit('.').next().unwrap_or(path))
+    };
+
+    let mut nodes = vec![path.clone()];
+    let mut edges: Vec<(String, String)> = Vec::new();
+    let mut pending = vec![path.clone()];
+    while let Some(current) = pending.pop() {
+        for base in page
+            .project
+            .bases(&current)
+            .iter()
+            .filter(|base| documented(base))
+        {
+            edges.push((base.clone(), current.clone()));
+            if !nodes.contains(base) {
+                nodes.push(base.clone());
+                pending.push(base.clone());
+            }
+        }
+    }
+    for subclass in page
+        .project
+        .subclasses(&path)
+        .iter()
+        .filter(|subclass| documented(subclass))
+    {
+        let edge = (path.clone(), subclass.clone());
+        if !edges.contains(&edge) {
+            edges.push(edge);
+        }
+        if !nodes.contains(subclass) {
+            nodes.push(subclass.clone());
+        }
+    }
+    if edges.is_empty() {
+        return String::new();
+    }
+
+    // Dotted paths are not valid node ids; the short name is the label.
+    let id = |path: &str| path.replace('.', "_");
+    let mut doc = String::from("```mermaid\nclassDiagram\n");
+    for node in &nodes {
+        let name = node.rsplit('.').next().unwrap_or(node);
+        doc.push_str(&format!("    class {}[\"{}\"]\n", id(node), name));
+    }
+    for (base, subclass) in &edges {
+        doc.push_str(&format!("    {} <|-- {}\n", id(base), id(subclass)));
+    }
+    for node in nodes.iter().filter(|node| **node != path) {
+        if let Some(symbol) = page.project.symbol(node) {
+            let link = page.project.link(page.module.import_path(), symbol);
+            doc.push_str(&format!("    click {} href \"{}\"\n", id(node), link));
+        }
+    }
+    doc.push_str("```\n\n");
+    doc
+}
+
+/// Import line and call skeleton at the top of a class or function entry.
+/// Required arguments are passed by keyword with `...` as their value.
+fn format_usage(page: &Page, name: &str, call: &str) -> String {
+    if !page.options.usage_snippets {
+        return String::new();
+    }
+    format!(
+        "```py\nfrom {} import {}\n\n{}\n```\n\n",
+        page.module.import_path(),
+        name,
+        call
+    )
+}
+
+/// Call skeleton for a method, on an instance named after the class or, for
+/// class and static methods, on the class itself. Dunder methods are not
+/// called directly and get none; `__init__` is shown with the class.
+fn format_method_usage(page: &Page, class: &str, method: &FunctionDoc) -> String {
+    let is_dunder = method.name.starts_with("__") && method.name.ends_with("__");
+    if !page.options.usage_snippets || is_dunder {
+        return String::new();
+    }
+    let receiver = match method.method_kind() {
+        MethodKind::Class | MethodKind::Static => class.to_string(),
+        MethodKind::Instance => snake_case(class.rsplit('.').next().unwrap_or(class)),
+    };
+    format!(
+        "```py\n{}.{}\n```\n\n",
+        receiver,
+        call_skeleton(&method.name, required(method.arguments(true)))
+    )
+}
+
+/// `name(a=..., b=...)` with the given required arguments.
+fn call_skeleton(name: &str, required: impl IntoIterator<Item = String>) -> String {
+    let arguments: Vec<String> = required.into_iter().collect();
+    format!("{}({})", name, arguments.join(", "))
+}
+
+/// The arguments a caller must pass: `...` for a positional-only
+/// parameter, `name=...` for the others.
+fn required(parameters: &[Parameter]) -> impl Iterator<Item = String> + '_ {
+    parameters
+        .iter()
+        .filter(|parameter| parameter.is_required())
+        .map(|parameter| match parameter.kind {
+            ParameterKind::PositionalOnly => "...".to_string(),
+            _ => format!("{}=...", parameter.name),
+        })
+}
+
+/// Variable name for an instance of a class, e.g. `http_client` for
+/// `HTTPClient`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.trim_start_matches('_').chars().collect();
+    let mut snake = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = index > 0 && chars[index - 1].is_lowercase();
+            let before_lower = index > 0
+                && chars[index - 1].is_uppercase()
+                && chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || before_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+/// How many referring symbols are listed before the rest are only counted.
+const MAX_REFERENCES: usize = 10;
+
+/// Collapsed list of the documented symbols referring to the class or
+/// function `name` of this page. Empty unless asked for or when there are
+/// none.
+fn format_referenced_by(page: &Page, name: &str) -> String {
+    if !page.options.referenced_by {
+        return String::new();
+    }
+    let module_path = page.module.import_path();
+    let path = format!("{}.{}", module_path, name);
+    let referrers: Vec<_> = page
+        .project
+        .referenced_by(&path)
+        .iter()
+        .filter_map(|referrer| page.project.symbol(referrer))
+        .filter(|symbol| {
+            symbol.path[symbol.module.len()..]
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .all(|part| page.options.documents(part))
+        })
+        .collect();
+    if referrers.is_empty() {
+        return String::new();
+    }
+
+    let strings = &page.options.strings;
+    let mut list = String::from("\n");
+    for symbol in referrers.iter().take(MAX_REFERENCES) {
+        list.push_str(&format!(
+            "- [`{}`]({})\n",
+            symbol.path,
+            page.project.link(module_path, symbol)
+        ));
+    }
+    if referrers.len() > MAX_REFERENCES {
+        let count = (referrers.len() - MAX_REFERENCES).to_string();
+        list.push_str(&format!(
+            "\n{}\n",
+            fill(&strings.and_more, &[("count", &count)])
+        ));
+    }
+    list.push('\n');
+    page.options
+        .format
+        .collapsible(&strings.referenced_by, &list)
+}
+
+/// Markdown heading marks for `level`, capped at `######` for deeply
+/// nested classes.
+fn heading(level: usize) -> String {
+    "#".repeat(level.min(6))
+}
+
+/// Documents the class at the dotted `path` below the module, e.g.
+/// `Outer.Inner` for a nested class, under a heading of `level`. Its
+/// members and nested classes get the levels below.
+fn format_class_doc(
+    page: &Page,
+    class: &ClassDoc,
+    path: &str,
+    level: usize,
+    origin: Option<&Reexport>,
+) -> String {
+    let mut doc = String::new();
+
+    doc.push_str(&page.anchor(path));
+    let strings = &page.options.strings;
+    let kind_labels: String = [
+        class.is_protocol().then_some(&strings.protocol),
+        class.is_abstract().then_some(&strings.abstract_class),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|label| format!(" `{}`", label))
+    .collect();
+    doc.push_str(&format!(
+        "{} `{}`{}{}{}\n\n",
+        heading(level),
+        path,
+        kind_labels,
+        badge_labels(&class.badges),
+        data_class_labels(page, class.data.as_ref())
+    ));
+    doc.push_str(&format_deprecation(page, class.deprecated.as_deref()));
+    doc.push_str(&format_bases(page, class));
+    doc.push_str(&format_implementation_notes(page, class));
+    doc.push_str(&format_versions(page, &class.versions));
+    doc.push_str(&format_type_params(page, &class.type_params));
+    doc.push_str(&defined_in(page, origin));
+    doc.push_str(&badge_notes(page, &class.badges));
+    if class.is_protocol() {
+        doc.push_str(
+            &page
+                .options
+                .format
+                .note(&page.options.strings.protocol_note),
+        );
+    }
+    if class.is_abstract() {
+        doc.push_str(&page.options.format.note(&abstract_note(page, class)));
+    }
+    let constructor = match &class.data {
+        Some(data) => call_skeleton(
+            path,
+            data.fields
+                .iter()
+                .filter(|field| field.default.is_none())
+                .map(|field| format!("{}=...", field.name)),
+        ),
+        None if class.typed_dict.is_some() => call_skeleton(
+            path,
+            class
+                .typed_dict
+                .iter()
+                .flat_map(|typed_dict| &typed_dict.keys)
+                .filter(|key| key.required)
+                .map(|key| format!("{}=...", key.name)),
+        ),
+        None => {
+            let init = merged_constructor(page, class, path).or_else(|| {
+                class
+                    .methods
+                    .iter()
+                    .find(|method| method.name == "__init__")
+            });
+            let arguments = init.map_or(&[][..], |init| init.arguments(true));
+            call_skeleton(path, required(arguments))
+        }
+    };
+    // An abstract class or a protocol is used by implementing it, not by
+    // calling it, and an enum by naming one of its members.
+    let usage = if class.is_abstract() || class.is_protocol() {
+        format!("class Custom{}({}):\n    ...", class.name, path)
+    } else if let Some(member) = class.enum_members.first() {
+        format!("{} = {}.{}", snake_case(&class.name), path, member.name)
+    } else {
+        format!("{} = {}", snake_case(&class.name), constructor)
+    };
+    doc.push_str(&format_usage(
+        page,
+        path.split('.').next().unwrap_or(path),
+        &usage,
+    ));
+
+    if let Some(docstring) = &class.docstring {
+        doc.push_str(&format!("{}\n", docstring));
+    }
+    if let Some(constructor) = merged_constructor(page, class, path) {
+        if let Some(docstring) = &constructor.docstring {
+            ensure_blank_line(&mut doc);
+            doc.push_str(&format!("{}\n", docstring));
+        }
+        doc.push_str(&format_args_table(page, constructor.arguments(true)));
+        doc.push('\n');
+    }
+
+    if let Some(data) = &class.data {
+        doc.push_str(&format_fields_table(page, data));
+    }
+    for table in [
+        format_enum_members_table(page, class),
+        format_keys_table(page, class),
+        format_attributes_table(page, class),
+    ] {
+        if !table.is_empty() {
+            ensure_blank_line(&mut doc);
+            doc.push_str(table.trim_start_matches('\n'));
+        }
+    }
+
+    if page.options.inheritance_diagrams {
+        let diagram = format_inheritance_diagram(page, path);
+        if !diagram.is_empty() {
+            ensure_blank_line(&mut doc);
+            doc.push_str(&diagram);
+        }
+    }
+
+    let source = format!("```py\n{}```\n", class_source(page, class));
+    doc.push_str(
+        &page
+            .options
+            .format
+            .collapsible(&source_accordion_title(page, origin), &source),
+    );
+    doc.push_str(&format_referenced_by(page, path));
+
+    for property in class
+        .properties
+        .iter()
+        .filter(|property| page.options.documents(&property.name))
+    {
+        ensure_blank_line(&mut doc);
+        doc.push_str(&format_property_doc(
+            page,
+            path,
+            level + 1,
+            property,
+            origin,
+        ));
+    }
+
+    for method in class
+        .methods
+        .iter()
+        .filter(|method| documents_member(page, class, method))
+    {
+        ensure_blank_line(&mut doc);
+        doc.push_str(&page.anchor(&format!("{}.{}", path, method.name)));
+        doc.push_str(&format!(
+            "{} `{}()`{}{}\n\n",
+            heading(level + 1),
+            method.name,
+            method_labels(page, method),
+            badge_labels(&method.badges)
+        ));
+        doc.push_str(&format_deprecation(page, method.deprecated.as_deref()));
+        doc.push_str(&format_versions(page, &method.versions));
+        doc.push_str(&format_type_params(page, &method.type_params));
+        doc.push_str(&badge_notes(page, &method.badges));
+        doc.push_str(&format_method_usage(page, path, method));
+
+        // Add the arguments table
+        doc.push_str(&format_overloads(page, method));
+        doc.push_str(&format_args_table(page, method.arguments(true)));
+
+        if let Some(docstring) = &method.docstring {
+            doc.push_str(&format!("{}\n", docstring));
+        }
+        doc.push_str(&format_example(page, method));
+
+        doc.push_str(&page.options.format.collapsible(
+            &source_accordion_title(page, origin),
+            &format!("```py\n{}```\n", method.source),
+        ));
+
+        // Add the returns table
+        doc.push_str(&format_returns_table(page, method.returns.as_deref()));
+        doc.push_str(&format_raises_table(page, method));
+        let nested = format_nested_functions(
+            page,
+            &format!("{}.{}", path, method.name),
+            method,
+            level + 2,
+        );
+        if !nested.is_empty() {
+            ensure_blank_line(&mut doc);
+            doc.push_str(&nested);
+        }
+    }
+
+    for nested in class
+        .classes
+        .iter()
+        .filter(|nested| page.options.documents(&nested.name))
+    {
+        ensure_blank_line(&mut doc);
+        doc.push_str(&format_class_doc(
+            page,
+            nested,
+            &format!("{}.{}", path, nested.name),
+            level + 1,
+            origin,
+        ));
+    }
+
+    doc
+}
+
+/// The note under an abstract class, naming the abstract methods and
+/// properties its subclasses must implement.
+fn abstract_note(page: &Page, class: &ClassDoc) -> String {
+    let strings = &page.options.strings;
+    let mut members: Vec<(usize, &str)> = class
+        .methods
+        .iter()
+        .filter(|method| method.is_abstract())
+        .map(|method| (method.line, method.name.as_str()))
+        .collect();
+    members.extend(
+        class
+            .properties
+            .iter()
+            .filter(|property| {
+                property
+                    .getter
+                    .as_ref()
+                    .is_some_and(FunctionDoc::is_abstract)
+            })
+            .map(|property| (property.line, property.name.as_str())),
+    );
+    if members.is_empty() {
+        return strings.abstract_note.clone();
+    }
+    members.sort_by_key(|(line, _)| *line);
+    let names: Vec<String> = members
+        .iter()
+        .map(|(_, name)| format!("`{}`", name))
+        .collect();
+    format!(
+        "{} {}",
+        strings.abstract_note,
+        fill(&strings.must_implement, &[("members", &names.join(", "))])
+    )
+}
+
+/// The `__init__` of a class, or its `__new__` when it has none.
+fn own_constructor(class: &ClassDoc) -> Option<&FunctionDoc> {
+    let method = |name: &str| class.methods.iter().find(|method| method.name == name);
+    method("__init__").or_else(|| method("__new__"))
+}
+
+/// The constructor documented with the class at `path` under
+/// [`RenderOptions::merge_init`]: its own, or else the first one along its
+/// first documented bases. A dataclass or `TypedDict` without one of its
+/// own has its fields documented instead.
+fn merged_constructor<'a>(
+    page: &Page<'a>,
+    class: &'a ClassDoc,
+    path: &str,
+) -> Option<&'a FunctionDoc> {
+    if !page.options.merge_init {
+        return None;
+    }
+    if let Some(constructor) = own_constructor(class) {
+        return Some(constructor);
+    }
+    if class.data.is_some() || class.typed_dict.is_some() {
+        return None;
+    }
+    let mut seen = vec![format!("{}.{}", page.module.import_path(), path)];
+    while let Some(base) = page.project.bases(seen.last()?).first() {
+        if seen.contains(base) {
+            return None;
+        }
+        let base_class = page.project.class(base)?;
+        if let Some(constructor) = own_constructor(base_class) {
+            return Some(constructor);
+        }
+        seen.push(base.clone());
+    }
+    None
+}
+
+/// Whether a method gets an entry of its own: not when it is the
+/// constructor documented with the class under
+/// [`RenderOptions::merge_init`].
+fn documents_member(page: &Page, class: &ClassDoc, method: &FunctionDoc) -> bool {
+    let merged = page.options.merge_init
+        && own_constructor(class).is_some_and(|constructor| constructor.name == method.name);
+    page.options.documents_method(&method.name) && !merged
+}
+
+/// The class definition reconstructed from its documented members, with
+/// nested classes indented in it.
+fn class_source(page: &Page, class: &ClassDoc) -> String {
+    let mut source = format!("class {}:\n", class.name);
+    let mut definitions: Vec<(usize, String)> = class
+        .methods
+        .iter()
+        .filter(|method| page.options.documents_method(&method.name))
+        .map(|method| (method.line, method.source.clone()))
+        .collect();
+    for property in class
+        .properties
+        .iter()
+        .filter(|property| page.options.documents(&property.name))
+    {
+        definitions.extend(
+            [&property.getter, &property.setter, &property.deleter]
+                .into_iter()
+                .flatten()
+                .map(|accessor| (accessor.line, accessor.source.clone())),
+        );
+    }
+    for nested in class
+        .classes
+        .iter()
+        .filter(|nested| page.options.documents(&nested.name))
+    {
+        definitions.push((nested.line, class_source(page, nested)));
+    }
+    definitions.sort_by_key(|(line, _)| *line);
+    for key in class
+        .typed_dict
+        .iter()
+        .flat_map(|typed_dict| &typed_dict.keys)
+    {
+        source.push_str(&format!("    {}: {}\n", key.name, key.annotation));
+    }
+    let has_init = class.methods.iter().any(|method| method.name == "__init__");
+    if let Some(data) = class.data.as_ref().filter(|_| !has_init) {
+        source.push_str(&indent(&synthesized_init(data)));
+    }
+    for (_, definition) in definitions {
+        source.push_str(&indent(&definition));
+    }
+    if source.lines().count() == 1 {
+        source.push_str("    ...\n");
+    }
+    source
+}
+
+/// Indents each line of `source` by one level, leaving blank lines empty.
+fn indent(source: &str) -> String {
+    source
+        .lines()
+        .map(|line| match line.trim().is_empty() {
+            true => "\n".to_string(),
+            false => format!("    {}\n", line),
+        })
+        .collect()
+}
+
+/// The labels after a method's heading, each preceded by a space: how it is
+/// bound, whether it is abstract, and whether it is `async`.
+fn method_labels(page: &Page, method: &FunctionDoc) -> String {
+    let strings = &page.options.strings;
+    let kind = match method.method_kind() {
+        MethodKind::Instance => None,
+        MethodKind::Class => Some(&strings.classmethod),
+        MethodKind::Static => Some(&strings.staticmethod),
+    };
+    [
+        kind,
+        method.is_abstract().then_some(&strings.abstractmethod),
+        method.is_async.then_some(&strings.asynchronous),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|label| format!(" `{}`", label))
+    .collect()
+}
+
+fn format_property_doc(
+    page: &Page,
+    class: &str,
+    level: usize,
+    property: &PropertyDoc,
+    origin: Option<&Reexport>,
+) -> String {
+    let mut doc = String::new();
+
+    doc.push_str(&page.anchor(&format!("{}.{}", class, property.name)));
+    let strings = &page.options.strings;
+    doc.push_str(&format!(
+        "{} `{}` `{}`\n\n",
+        heading(level),
+        property.name,
+        property_label(page, property)
+    ));
+    if let Some(docstring) = &property.docstring {
+        doc.push_str(&format!("{}\n\n", docstring));
+    }
+    if let Some(type_name) = &property.type_name {
+        doc.push_str(&format!(
+            "**{}:** {}\n\n",
+            strings.type_name,
+            page.type_text(type_name)
+        ));
+    }
+    if property.setter.is_some() {
+        doc.push_str(&format!("*{}*\n\n", strings.writable));
+        if let Some(value_type) = property.value_type() {
+            doc.push_str(&format!(
+                "**{}:** {}\n\n",
+                strings.setter_type,
+                page.type_text(value_type)
+            ));
+        }
+    } else if !property.cached {
+        doc.push_str(&format!("*{}*\n\n", strings.read_only));
+    }
+
+    let accessors: Vec<&FunctionDoc> = [&property.getter, &property.setter, &property.deleter]
+        .into_iter()
+        .flatten()
+        .collect();
+    if !accessors.is_empty() {
+        let mut source = String::from("```py\n");
+        for accessor in accessors {
+            source.push_str(&accessor.source);
+        }
+        source.push_str("```\n");
+        doc.push_str(
+            &page
+                .options
+                .format
+                .collapsible(&source_accordion_title(page, origin), &source),
+        );
+    }
+
+    doc
+}
+
+/// The kind shown for a property, telling a cached one apart.
+fn property_label<'a>(page: &'a Page, property: &PropertyDoc) -> &'a str {
+    if property.cached {
+        &page.options.strings.cached_property
+    } else {
+        &page.options.strings.property
+    }
+}
+
+/// Heading and header of a table with a row per parameter or field.
+fn parameters_header(page: &Page, heading: &str) -> String {
+    let strings = &page.options.strings;
+    format!(
+        "\n**{}:**\n\n| {} | {} | {} | {} |\n| --- | --- | --- | --- |\n",
+        heading, strings.name, strings.type_name, strings.description, strings.default
+    )
+}
+
+/// Heading and header of a table with a row per returned or raised type.
+fn types_header(page: &Page, heading: &str) -> String {
+    let strings = &page.options.strings;
+    format!(
+        "\n**{}:**\n\n| {} | {} |\n| --- | --- |\n",
+        heading, strings.type_name, strings.description
+    )
+}
+
+/// The default cell of a required parameter or field.
+fn required_cell(page: &Page) -> String {
+    format!("_{}_", page.options.strings.required)
+}
+
+fn format_args_table(page: &Page, parameters: &[Parameter]) -> String {
+    let mut table = parameters_header(page, &page.options.strings.parameters);
+    let required = required_cell(page);
+
+    for parameter in parameters {
+        let type_cell = match (
+            parameter.annotation.as_deref(),
+            parameter.docstring_type.as_deref(),
+            parameter.inferred_type,
+        ) {
+            (Some(annotation), ..) => page.type_cell(annotation),
+            (None, Some(docstring_type), _) => page.type_cell(docstring_type),
+            // Italics mark the type as a guess from the default value
+            (None, None, Some(inferred)) => format!("*`{}`*", inferred),
+            (None, None, None) => page.type_cell("Any"),
+        };
+        let description = table_cell(parameter.description.as_deref().unwrap_or(""));
+        let default = match (parameter.kind, parameter.default.as_deref()) {
+            // Variadic parameters default to an empty tuple or dict.
+            (ParameterKind::VarPositional | ParameterKind::VarKeyword, _) => "–".to_string(),
+            (_, Some(default)) => format!("`{}`", table_cell(default)),
+            (_, None) => required.clone(),
+        };
+
+        table.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            parameter.display_name(),
+            type_cell,
+            description,
+            default
+        ));
+    }
+
+    table
+}
+
+/// Fields of a dataclass-like class, as taken by its constructor.
+fn format_fields_table(page: &Page, data: &DataClassDoc) -> String {
+    if data.fields.is_empty() {
+        return String::new();
+    }
+    let mut table = parameters_header(page, &page.options.strings.fields);
+    for field in &data.fields {
+        let default = field.default.as_deref().map_or_else(
+            || required_cell(page),
+            |default| format!("`{}`", table_cell(default)),
+        );
+        table.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            field.name,
+            page.type_cell(field.type_name.as_deref().unwrap_or("Any")),
+            first_line(field.docstring.as_deref()),
+            default
+        ));
+    }
+    table.push('\n');
+    table
+}
+
+/// Table of the members of an enum with their values, in declaration
+/// order.
+fn format_enum_members_table(page: &Page, class: &ClassDoc) -> String {
+    if class.enum_members.is_empty() {
+        return String::new();
+    }
+    let strings = &page.options.strings;
+    let mut table = format!(
+        "**{}:**\n\n| {} | {} | {} |\n| --- | --- | --- |\n",
+        strings.enum_members, strings.name, strings.value, strings.description
+    );
+    for member in &class.enum_members {
+        table.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            member.name,
+            table_cell(&member.value),
+            first_line(member.docstring.as_deref())
+        ));
+    }
+    table.push('\n');
+    table
+}
+
+/// Table of the keys of a `TypedDict`, telling which are required.
+fn format_keys_table(page: &Page, class: &ClassDoc) -> String {
+    let Some(typed_dict) = class
+        .typed_dict
+        .as_ref()
+        .filter(|typed_dict| !typed_dict.keys.is_empty())
+    else {
+        return String::new();
+    };
+    let strings = &page.options.strings;
+    let mut table = format!(
+        "**{}:**\n\n| {} | {} | {} | {} |\n| --- | --- | --- | --- |\n",
+        strings.keys, strings.name, strings.type_name, strings.description, strings.status
+    );
+    for key in &typed_dict.keys {
+        let status = if key.required {
+            &strings.required
+        } else {
+            &strings.optional
+        };
+        table.push_str(&format!(
+            "| `{}` | {} | {} | _{}_ |\n",
+            key.name,
+            page.type_cell(&key.type_name),
+            first_line(key.docstring.as_deref()),
+            status
+        ));
+    }
+    table.push('\n');
+    table
+}
+
+/// Table of the documented attributes of a class, tagging those annotated
+/// with `ClassVar`.
+fn format_attributes_table(page: &Page, class: &ClassDoc) -> String {
+    let attributes: Vec<&AttributeDoc> = class
+        .attributes
+        .iter()
+        .filter(|attribute| page.options.documents_method(&attribute.name))
+        .collect();
+    if attributes.is_empty() {
+        return String::new();
+    }
+    let mut table = parameters_header(page, &page.options.strings.attributes);
+    for attribute in attributes {
+        let name = if attribute.class_var {
+            format!(
+                "`{}` `{}`",
+                attribute.name, page.options.strings.class_attribute
+            )
+        } else {
+            format!("`{}`", attribute.name)
+        };
+        let default = attribute.default.as_deref().map_or_else(
+            || "–".to_string(),
+            |default| format!("`{}`", table_cell(default)),
+        );
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            name,
+            page.type_cell(attribute.type_name.as_deref().unwrap_or("Any")),
+            first_line(attribute.docstring.as_deref()),
+            default
+        ));
+    }
+    table.push('\n');
+    table
+}
+
+/// Tags after the heading of a frozen or keyword-only dataclass-like class.
+fn data_class_labels(page: &Page, data: Option<&DataClassDoc>) -> String {
+    let Some(data) = data else {
+        return String::new();
+    };
+    let strings = &page.options.strings;
+    let mut labels = String::new();
+    if data.frozen {
+        labels.push_str(&format!(" `{}`", strings.frozen));
+    }
+    // Every pydantic model is keyword-only, so that goes without saying.
+    if data.kw_only && data.kind != DataClassKind::Pydantic {
+        labels.push_str(&format!(" `{}`", strings.kw_only));
+    }
+    labels
+}
+
+fn format_returns_table(page: &Page, returns: Option<&str>) -> String {
+    let strings = &page.options.strings;
+    let mut table = types_header(page, &strings.returns);
+
+    if let Some(ret_type) = returns {
+        let description = ""; // You'd need to extract this from the docstring
+        table.push_str(&format!(
+            "| {} | {} |\n",
+            page.type_cell(ret_type),
+            description
+        ));
+    } else {
+        table.push_str(&format!("| None | {} |\n", strings.no_return_value));
+    }
+
+    table
+}
+
+/// Lists the documented exceptions, or with `infer_raises` the ones raised
+/// in the body when the docstring has no `Raises:` section.
+fn format_raises_table(page: &Page, function: &FunctionDoc) -> String {
+    let documented = &function.parsed_docstring.raises;
+    let strings = &page.options.strings;
+    let rows: Vec<(String, String)> = if !documented.is_empty() {
+        documented
+            .iter()
+            .map(|entry| (page.type_cell(&entry.name), entry.description.clone()))
+            .collect()
+    } else if page.options.infer_raises {
+        let detected = format!("*{}*", strings.detected_from_source);
+        function
+            .raises
+            .iter()
+            .map(|raised| (page.type_cell(raised), detected.clone()))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if rows.is_empty() {
+        return String::new();
+    }
+
+    let mut table = types_header(page, &strings.raises);
+    for (type_cell, description) in rows {
+        table.push_str(&format!("| {} | {} |\n", type_cell, description));
+    }
+    table
+}
+
+/// Shows the test attached as an example, if any.
+fn format_example(page: &Page, function: &FunctionDoc) -> String {
+    match &function.example {
+        Some(code) => format!(
+            "\n**{}:**\n\n```py\n{}```\n\n",
+            page.options.strings.example, code
+        ),
+        None => String::new(),
+    }
+}
+
+fn format_function_doc(page: &Page, function: &FunctionDoc, origin: Option<&Reexport>) -> String {
+    let mut doc = String::new();
+
+    doc.push_str(&page.anchor(&function.name));
+    // Clean the function name and add it to the documentation
+    let clean_name = function.name.trim_matches('`');
+    doc.push_str(&format!(
+        "### `{}`{}{}\n\n",
+        clean_name,
+        if function.is_async {
+            format!(" `{}`", page.options.strings.asynchronous)
+        } else {
+            String::new()
+        },
+        badge_labels(&function.badges)
+    ));
+    doc.push_str(&format_deprecation(page, function.deprecated.as_deref()));
+    doc.push_str(&format_versions(page, &function.versions));
+    doc.push_str(&format_type_params(page, &function.type_params));
+    doc.push_str(&defined_in(page, origin));
+    doc.push_str(&badge_notes(page, &function.badges));
+    doc.push_str(&format_usage(
+        page,
+        clean_name,
+        &call_skeleton(clean_name, required(&function.parameters)),
+    ));
+
+    // Add docstring if available
+    if let Some(docstring) = &function.docstring {
+        // Remove any leading/trailing whitespace and quotes from the docstring
+        let cleaned_docstring = docstring.trim().trim_matches('"').trim_matches('\'');
+        doc.push_str(&format!("{}\n\n", cleaned_docstring));
+    }
+    doc.push_str(&format_example(page, function));
+
+    // Add parameters table
+    doc.push_str(&format_overloads(page, function));
+    doc.push_str(&format_args_table(page, &function.parameters));
+
+    // Add returns table
+    doc.push_str(&format_returns_table(page, function.returns.as_deref()));
+    doc.push_str(&format_raises_table(page, function));
+
+    // Add prose description (extracted from docstring)
+    doc.push_str(&format!("\n**{}:**\n\n", page.options.strings.description));
+    if let Some(docstring) = &function.docstring {
+        // Extract description from docstring (assuming it's after the first empty line)
+        let description = docstring
+            .split_once("\n\n")
+            .map_or("", |(_, description)| description);
+        doc.push_str(&format!("{}\n", description.trim()));
+    }
+
+    let referenced_by = format_referenced_by(page, &function.name);
+    if !referenced_by.is_empty() {
+        ensure_blank_line(&mut doc);
+        doc.push_str(&referenced_by);
+    }
+
+    let nested = format_nested_functions(page, &function.name, function, 4);
+    if !nested.is_empty() {
+        ensure_blank_line(&mut doc);
+        doc.push_str(&nested);
+    }
+
+    doc
+}
+
+/// The signatures of the `@overload` declarations grouped with a function,
+/// followed by its own when it is one of them, as in a stub.
+fn format_overloads(page: &Page, function: &FunctionDoc) -> String {
+    if function.overloads.is_empty() {
+        return String::new();
+    }
+    let mut signatures = function.overloads.join("\n");
+    if function.is_overload() {
+        signatures.push('\n');
+        signatures.push_str(function.signature());
+    }
+    format!(
+        "\n**{}:**\n\n```py\n{}\n```\n",
+        page.options.strings.overloads, signatures
+    )
+}
+
+/// The functions defined inside `function`, whose dotted path below the
+/// module is `path`: named in a list, or each documented under a heading
+/// of `level`, as [`RenderOptions::nested_functions`] asks.
+fn format_nested_functions(
+    page: &Page,
+    path: &str,
+    function: &FunctionDoc,
+    level: usize,
+) -> String {
+    let nested: Vec<&FunctionDoc> = function
+        .nested
+        .iter()
+        .filter(|nested| page.options.documents(&nested.name))
+        .collect();
+    if nested.is_empty() {
+        return String::new();
+    }
+    match page.options.nested_functions {
+        NestedFunctions::Hide | NestedFunctions::Omit => String::new(),
+        NestedFunctions::List => {
+            let names: Vec<String> = nested
+                .iter()
+                .map(|nested| format!("`{}()`", nested.name))
+                .collect();
+            format!(
+                "**{}:** {}\n\n",
+                page.options.strings.inner_functions,
+                names.join(", ")
+            )
+        }
+        NestedFunctions::Document => {
+            let mut doc = String::new();
+            for nested in nested {
+                let nested_path = format!("{}.{}", path, nested.name);
+                ensure_blank_line(&mut doc);
+                doc.push_str(&page.anchor(&nested_path));
+                doc.push_str(&format!(
+                    "{} `{}()`{}\n\n",
+                    heading(level),
+                    nested_path,
+                    method_labels(page, nested)
+                ));
+                if let Some(docstring) = &nested.docstring {
+                    doc.push_str(&format!("{}\n\n", docstring));
+                }
+                doc.push_str(&format_args_table(page, &nested.parameters));
+                doc.push_str(&format_returns_table(page, nested.returns.as_deref()));
+                doc.push_str(&format_raises_table(page, nested));
+                doc.push_str(&format_nested_functions(
+                    page,
+                    &nested_path,
+                    nested,
+                    level + 1,
+                ));
+            }
+            doc
+        }
+    }
+}
@@ -0,0 +1,737 @@
+//! Reference pages for HTTP endpoints defined with FastAPI or Flask.
+//!
+//! A route handler is documented by the request a client sends and the
+//! response it gets back, not by its Python signature. Applications and
+//! routers are recognized by the variables `FastAPI()`, `APIRouter()`,
+//! `Flask()` and `Blueprint()` are assigned to, endpoints by the route
+//! decorators on them (`@app.get("/items")`, `@bp.route("/", methods=[...])`),
+//! and prefixes by `include_router(...)` and `register_blueprint(...)` calls.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use rustpython_parser::{ast, Parse, ParseError};
+
+use crate::docstring::{parse_docstring, Docstring};
+use crate::render::table_cell;
+use crate::source::{keyword, string, SourceText};
+use crate::types::{extract_type, infer_type};
+
+/// An HTTP endpoint: one route decorator on a handler function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    /// HTTP methods in upper case, e.g. `GET`.
+    pub methods: Vec<String>,
+    /// Full path including router and mount prefixes, in the framework's
+    /// own syntax (`/items/{id}` or `/items/<int:id>`).
+    pub path: String,
+    /// Python function handling the request.
+    pub function: String,
+    /// One-based line of the handler's `def` statement.
+    pub line: usize,
+    pub help: Option<String>,
+    /// Request parameters in signature order.
+    pub parameters: Vec<EndpointParameter>,
+    /// Type of the response body, from `response_model=` or the return
+    /// annotation.
+    pub response: Option<String>,
+    /// Status code of a successful response, as written.
+    pub status_code: Option<String>,
+}
+
+/// Where a request parameter is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterLocation {
+    Path,
+    Query,
+    Header,
+    Cookie,
+    Body,
+    Form,
+    File,
+}
+
+impl fmt::Display for ParameterLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParameterLocation::Path => "path",
+            ParameterLocation::Query => "query",
+            ParameterLocation::Header => "header",
+            ParameterLocation::Cookie => "cookie",
+            ParameterLocation::Body => "body",
+            ParameterLocation::Form => "form",
+            ParameterLocation::File => "file",
+        })
+    }
+}
+
+/// A path, query, header, cookie or body parameter of an endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointParameter {
+    pub name: String,
+    pub location: ParameterLocation,
+    pub type_name: String,
+    /// Default value as written in the source.
+    pub default: Option<String>,
+    pub required: bool,
+    /// From `description=` or the docstring's `Args:` section.
+    pub description: Option<String>,
+}
+
+/// Finds the endpoints defined in `source`, in source order.
+pub fn extract_routes(source: &str, path: &Path) -> Result<Vec<Endpoint>, ParseError> {
+    let suite = ast::Suite::parse(source, &path.to_string_lossy())?;
+    let mut extractor = Extractor {
+        source: SourceText::new(source),
+        modules: BTreeMap::new(),
+        names: BTreeMap::new(),
+        routers: BTreeMap::new(),
+        endpoints: Vec::new(),
+    };
+    extractor.stmts(&suite);
+    Ok(extractor
+        .endpoints
+        .iter()
+        .map(|(router, endpoint)| Endpoint {
+            path: join_paths(&extractor.prefix(router, 0), &endpoint.path),
+            ..endpoint.clone()
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framework {
+    FastApi,
+    Flask,
+}
+
+/// An application, router or blueprint variable.
+struct Router {
+    framework: Framework,
+    /// `prefix=` of an `APIRouter` or `url_prefix=` of a `Blueprint`.
+    prefix: String,
+    /// Router it is included in, with the prefix given when including it.
+    parent: Option<(String, Option<String>)>,
+}
+
+/// The handler function of a route, async or not.
+struct Handler<'a> {
+    name: &'a str,
+    args: &'a ast::Arguments,
+    decorators: &'a [ast::Expr],
+    body: &'a [ast::Stmt],
+    returns: Option<&'a ast::Expr>,
+    line: usize,
+}
+
+struct Extractor<'a> {
+    source: SourceText<'a>,
+    /// Names `fastapi` and `flask` are imported as.
+    modules: BTreeMap<String, Framework>,
+    /// Names imported from `fastapi` or `flask`, mapped to the imported name.
+    names: BTreeMap<String, (Framework, String)>,
+    routers: BTreeMap<String, Router>,
+    /// Endpoints with the router variable they are defined on; paths do not
+    /// include prefixes yet since routers are usually included afterwards.
+    endpoints: Vec<(String, Endpoint)>,
+}
+
+impl Extractor<'_> {
+    /// The framework and imported name a callee like `fastapi.APIRouter`
+    /// or an imported `Blueprint` refers to.
+    fn resolve(&self, callee: &str) -> Option<(Framework, String)> {
+        if let Some(resolved) = self.names.get(callee) {
+            return Some(resolved.clone());
+        }
+        let (base, name) = callee.rsplit_once('.')?;
+        let framework = *self.modules.get(base)?;
+        Some((framework, name.to_string()))
+    }
+
+    /// Full prefix of a router's routes, following the routers it is
+    /// included in.
+    fn prefix(&self, router: &str, depth: usize) -> String {
+        let Some(router) = self.routers.get(router) else {
+            return String::new();
+        };
+        match &router.parent {
+            // Guards against routers including each other.
+            _ if depth > 16 => router.prefix.clone(),
+            None => router.prefix.clone(),
+            Some((parent, mount_prefix)) => {
+                let parent_prefix = self.prefix(parent, depth + 1);
+                // A blueprint's `url_prefix` is replaced by the one given when
+                // it is registered; a router's `prefix` is appended to it.
+                let own_prefix = match (router.framework, mount_prefix) {
+                    (Framework::Flask, Some(mount_prefix)) => mount_prefix.clone(),
+                    (_, mount_prefix) => {
+                        join_paths(mount_prefix.as_deref().unwrap_or(""), &router.prefix)
+                    }
+                };
+                join_paths(&parent_prefix, &own_prefix)
+            }
+        }
+    }
+
+    fn stmts(&mut self, body: &[ast::Stmt]) {
+        for stmt in body {
+            match stmt {
+                ast::Stmt::Import(import) => {
+                    for alias in &import.names {
+                        let local_name = alias.asname.as_ref().unwrap_or(&alias.name).to_string();
+                        if let Some(framework) = framework(alias.name.as_str()) {
+                            self.modules.insert(local_name, framework);
+                        }
+                    }
+                }
+                ast::Stmt::ImportFrom(import) => {
+                    let module = import.module.as_ref().map_or("", |module| module.as_str());
+                    let Some(framework) = framework(module.split('.').next().unwrap_or_default())
+                    else {
+                        continue;
+                    };
+                    for alias in &import.names {
+                        let local_name = alias.asname.as_ref().unwrap_or(&alias.name).to_string();
+                        self.names
+                            .insert(local_name, (framework, alias.name.to_string()));
+                    }
+                }
+                ast::Stmt::FunctionDef(func_def) => {
+                    self.handler(&Handler {
+                        name: &func_def.name,
+                        args: &func_def.args,
+                        decorators: &func_def.decorator_list,
+                        body: &func_def.body,
+                        returns: func_def.returns.as_deref(),
+                        line: self.source.line(func_def),
+                    });
+                    self.stmts(&func_def.body);
+                }
+                ast::Stmt::AsyncFunctionDef(func_def) => {
+                    self.handler(&Handler {
+                        name: &func_def.name,
+                        args: &func_def.args,
+                        decorators: &func_def.decorator_list,
+                        body: &func_def.body,
+                        returns: func_def.returns.as_deref(),
+                        line: self.source.line(func_def),
+                    });
+                    self.stmts(&func_def.body);
+                }
+                ast::Stmt::Assign(assign) => {
+                    if let ([ast::Expr::Name(target)], ast::Expr::Call(call)) =
+                        (assign.targets.as_slice(), &*assign.value)
+                    {
+                        self.constructor(call, target.id.as_str());
+                    }
+                }
+                ast::Stmt::AnnAssign(assign) => {
+                    if let (ast::Expr::Name(target), Some(ast::Expr::Call(call))) =
+                        (&*assign.target, assign.value.as_deref())
+                    {
+                        self.constructor(call, target.id.as_str());
+                    }
+                }
+                ast::Stmt::Expr(expr) => {
+                    if let ast::Expr::Call(call) = &*expr.value {
+                        self.mount(call);
+                    }
+                }
+                ast::Stmt::If(stmt) => {
+                    self.stmts(&stmt.body);
+                    self.stmts(&stmt.orelse);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Records `target = FastAPI()`, `APIRouter(...)`, `Flask(...)` or
+    /// `Blueprint(...)`.
+    fn constructor(&mut self, call: &ast::ExprCall, target: &str) {
+        let Some((framework, name)) = self.resolve(&extract_type(&call.func)) else {
+            return;
+        };
+        let prefix_keyword = match name.as_str() {
+            "FastAPI" | "Flask" => None,
+            "APIRouter" => Some("prefix"),
+            "Blueprint" => Some("url_prefix"),
+            _ => return,
+        };
+        let prefix = prefix_keyword
+            .and_then(|name| keyword(call, name))
+            .and_then(string)
+            .unwrap_or_default();
+        self.routers.insert(
+            target.to_string(),
+            Router {
+                framework,
+                prefix: prefix.to_string(),
+                parent: None,
+            },
+        );
+    }
+
+    /// Records `app.include_router(router, prefix=...)` and
+    /// `app.register_blueprint(bp, url_prefix=...)`.
+    fn mount(&mut self, call: &ast::ExprCall) {
+        let callee = extract_type(&call.func);
+        let Some((parent, method)) = callee.rsplit_once('.') else {
+            return;
+        };
+        let prefix_keyword = match method {
+            "include_router" => "prefix",
+            "register_blueprint" => "url_prefix",
+            _ => return,
+        };
+        if !self.routers.contains_key(parent) {
+            return;
+        }
+        let Some(ast::Expr::Name(child)) = call.args.first() else {
+            return;
+        };
+        let prefix = keyword(call, prefix_keyword)
+            .and_then(string)
+            .map(str::to_string);
+        if let Some(router) = self.routers.get_mut(child.id.as_str()) {
+            router.parent = Some((parent.to_string(), prefix));
+        }
+    }
+
+    /// Adds an endpoint for each route decorator of a function.
+    fn handler(&mut self, handler: &Handler) {
+        for decorator in handler.decorators {
+            let ast::Expr::Call(call) = decorator else {
+                continue;
+            };
+            let callee = extract_type(&call.func);
+            let Some((router, method)) = callee.rsplit_once('.') else {
+                continue;
+            };
+            let Some(framework) = self.routers.get(router).map(|router| router.framework) else {
+                continue;
+            };
+            let methods = match (framework, method) {
+                (_, "get" | "post" | "put" | "patch" | "delete") => {
+                    vec![method.to_uppercase()]
+                }
+                (Framework::FastApi, "head" | "options" | "trace") => vec![method.to_uppercase()],
+                (Framework::FastApi, "api_route") | (Framework::Flask, "route") => {
+                    match keyword(call, "methods") {
+                        Some(ast::Expr::List(ast::ExprList { elts, .. }))
+                        | Some(ast::Expr::Tuple(ast::ExprTuple { elts, .. })) => elts
+                            .iter()
+                            .filter_map(string)
+                            .map(str::to_uppercase)
+                            .collect(),
+                        _ => vec!["GET".to_string()],
+                    }
+                }
+                _ => continue,
+            };
+            let path_keyword = match framework {
+                Framework::FastApi => "path",
+                Framework::Flask => "rule",
+            };
+            let Some(path) = call
+                .args
+                .first()
+                .or_else(|| keyword(call, path_keyword))
+                .and_then(string)
+            else {
+                continue;
+            };
+
+            let endpoint = match framework {
+                Framework::FastApi => self.fastapi_endpoint(handler, call, methods, path),
+                Framework::Flask => self.flask_endpoint(handler, methods, path),
+            };
+            self.endpoints.push((router.to_string(), endpoint));
+        }
+    }
+
+    fn fastapi_endpoint(
+        &self,
+        handler: &Handler,
+        call: &ast::ExprCall,
+        methods: Vec<String>,
+        path: &str,
+    ) -> Endpoint {
+        let docstring = docstring(handler.body);
+        let path_names = path_parameters(path);
+        let args = handler
+            .args
+            .posonlyargs
+            .iter()
+            .chain(&handler.args.args)
+            .chain(&handler.args.kwonlyargs);
+        let parameters = args
+            .filter_map(|arg| {
+                let mut parameter = self.fastapi_parameter(arg, &path_names)?;
+                parameter.description = parameter
+                    .description
+                    .or_else(|| argument_description(&docstring, &parameter.name));
+                Some(parameter)
+            })
+            .collect();
+
+        let help = match keyword(call, "summary").and_then(string) {
+            Some(summary) => Some(summary.to_string()),
+            None => keyword(call, "description")
+                .and_then(string)
+                .map(str::to_string)
+                .or_else(|| help(&docstring)),
+        };
+        Endpoint {
+            methods,
+            path: path.to_string(),
+            function: handler.name.to_string(),
+            line: handler.line,
+            help,
+            parameters,
+            response: keyword(call, "response_model")
+                .or(handler.returns)
+                .map(|response| self.source.one_line(response)),
+            status_code: keyword(call, "status_code").map(|status| self.source.one_line(status)),
+        }
+    }
+
+    /// Classifies a handler argument the way FastAPI does: by an explicit
+    /// `Query()`, `Body()`, ... marker in its default or `Annotated` type,
+    /// then by whether the path names it, then by whether its type is a
+    /// plain value or a model. Dependencies and injected objects such as
+    /// `Request` are not request parameters and are skipped.
+    fn fastapi_parameter(
+        &self,
+        arg: &ast::ArgWithDefault,
+        path_names: &BTreeSet<String>,
+    ) -> Option<EndpointParameter> {
+        let mut annotation = arg.def.annotation.as_deref();
+        let mut marker = arg.default.as_deref().and_then(parameter_marker);
+        if let Some(ast::Expr::Subscript(subscript)) = annotation {
+            if extract_type(&subscript.value).ends_with("Annotated") {
+                if let ast::Expr::Tuple(tuple) = &*subscript.slice {
+                    annotation = tuple.elts.first();
+                    marker =
+                        marker.or_else(|| tuple.elts.iter().skip(1).find_map(parameter_marker));
+                }
+            }
+        }
+        if annotation
+            .is_some_and(|annotation| INJECTED.contains(&last_segment(&extract_type(annotation))))
+        {
+            return None;
+        }
+
+        let (location, marker_default, description) = match marker {
+            Some((name, call)) => {
+                let location = match name {
+                    "Path" => ParameterLocation::Path,
+                    "Query" => ParameterLocation::Query,
+                    "Header" => ParameterLocation::Header,
+                    "Cookie" => ParameterLocation::Cookie,
+                    "Body" => ParameterLocation::Body,
+                    "Form" => ParameterLocation::Form,
+                    "File" => ParameterLocation::File,
+                    _ => return None,
+                };
+                let default = call.args.first().or_else(|| keyword(call, "default"));
+                let description = keyword(call, "description")
+                    .and_then(string)
+                    .map(str::to_string);
+                (location, Some(default), description)
+            }
+            None if path_names.contains(arg.def.arg.as_str()) => {
+                (ParameterLocation::Path, None, None)
+            }
+            None if annotation.is_none_or(is_plain_value) => (ParameterLocation::Query, None, None),
+            None => (ParameterLocation::Body, None, None),
+        };
+        // A marker's default replaces the parameter's own; `...` means the
+        // parameter is required.
+        let default = match marker_default {
+            Some(default) => default,
+            None => arg.default.as_deref(),
+        }
+        .filter(|default| !is_ellipsis(default));
+
+        let type_name = match annotation {
+            Some(annotation) => self.source.one_line(annotation),
+            None => default.and_then(infer_type).unwrap_or("Any").to_string(),
+        };
+        Some(EndpointParameter {
+            name: arg.def.arg.to_string(),
+            location,
+            type_name,
+            default: default.map(|default| self.source.one_line(default)),
+            required: location == ParameterLocation::Path || default.is_none(),
+            description,
+        })
+    }
+
+    fn flask_endpoint(&self, handler: &Handler, methods: Vec<String>, path: &str) -> Endpoint {
+        let docstring = docstring(handler.body);
+        // Flask passes the rule's variables as arguments; everything else
+        // comes from `request`, which is not visible in the signature.
+        let parameters = flask_variables(path)
+            .into_iter()
+            .map(|(converter, name)| EndpointParameter {
+                description: argument_description(&docstring, name),
+                name: name.to_string(),
+                location: ParameterLocation::Path,
+                type_name: match converter {
+                    "int" => "int",
+                    "float" => "float",
+                    "uuid" => "UUID",
+                    _ => "str",
+                }
+                .to_string(),
+                default: None,
+                required: true,
+            })
+            .collect();
+        Endpoint {
+            methods,
+            path: path.to_string(),
+            function: handler.name.to_string(),
+            line: handler.line,
+            help: help(&docstring),
+            parameters,
+            response: handler
+                .returns
+                .map(|response| self.source.one_line(response)),
+            status_code: None,
+        }
+    }
+}
+
+/// Types FastAPI passes to a handler itself instead of reading them from
+/// the request.
+const INJECTED: &[&str] = &[
+    "Request",
+    "Response",
+    "WebSocket",
+    "HTTPConnection",
+    "BackgroundTasks",
+    "SecurityScopes",
+];
+
+/// Types FastAPI reads from the query string when no marker says otherwise.
+const PLAIN_VALUES: &[&str] = &[
+    "str",
+    "int",
+    "float",
+    "bool",
+    "bytes",
+    "UUID",
+    "Decimal",
+    "datetime",
+    "date",
+    "time",
+    "timedelta",
+    "Any",
+];
+
+fn framework(module: &str) -> Option<Framework> {
+    match module {
+        "fastapi" => Some(Framework::FastApi),
+        "flask" => Some(Framework::Flask),
+        _ => None,
+    }
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// A `Query(...)`, `Depends(...)` or similar call, with the marker's name.
+fn parameter_marker(expr: &ast::Expr) -> Option<(&'static str, &ast::ExprCall)> {
+    const MARKERS: &[&str] = &[
+        "Path", "Query", "Header", "Cookie", "Body", "Form", "File", "Depends", "Security",
+    ];
+    let ast::Expr::Call(call) = expr else {
+        return None;
+    };
+    let callee = extract_type(&call.func);
+    let name = MARKERS
+        .iter()
+        .find(|marker| **marker == last_segment(&callee))?;
+    Some((name, call))
+}
+
+/// Whether a parameter of this type is a single value or a list of values
+/// rather than a request body model.
+fn is_plain_value(annotation: &ast::Expr) -> bool {
+    match annotation {
+        ast::Expr::Name(_) | ast::Expr::Attribute(_) => {
+            PLAIN_VALUES.contains(&last_segment(&extract_type(annotation)))
+        }
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::None,
+            ..
+        }) => true,
+        ast::Expr::BinOp(binop) => is_plain_value(&binop.left) && is_plain_value(&binop.right),
+        ast::Expr::Subscript(subscript) => {
+            let elements = match &*subscript.slice {
+                ast::Expr::Tuple(tuple) => tuple.elts.iter().collect(),
+                slice => vec![slice],
+            };
+            match last_segment(&extract_type(&subscript.value)) {
+                "Literal" => true,
+                "Optional" | "Union" | "List" | "list" | "Set" | "set" | "Sequence" | "Tuple"
+                | "tuple" | "FrozenSet" | "frozenset" => elements.into_iter().all(is_plain_value),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_ellipsis(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Ellipsis,
+            ..
+        })
+    )
+}
+
+/// Names of the `{name}` or `{name:converter}` segments of a FastAPI path.
+fn path_parameters(path: &str) -> BTreeSet<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}'))
+        .map(|(name, _)| name.split(':').next().unwrap_or(name).to_string())
+        .collect()
+}
+
+/// Converters and names of the `<converter:name>` variables of a Flask rule.
+fn flask_variables(path: &str) -> Vec<(&str, &str)> {
+    path.split('<')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('>'))
+        .map(|(variable, _)| variable.split_once(':').unwrap_or(("string", variable)))
+        .collect()
+}
+
+/// Joins a prefix and a path without doubling or dropping the `/` between
+/// them.
+fn join_paths(prefix: &str, path: &str) -> String {
+    match (prefix.trim_end_matches('/'), path) {
+        ("", path) => path.to_string(),
+        (prefix, "") => prefix.to_string(),
+        (prefix, path) => format!("{}/{}", prefix, path.trim_start_matches('/')),
+    }
+}
+
+fn docstring(body: &[ast::Stmt]) -> Option<Docstring> {
+    let ast::Stmt::Expr(expr) = body.first()? else {
+        return None;
+    };
+    string(&expr.value).map(parse_docstring)
+}
+
+/// The summary and description of a handler's docstring, without its
+/// sections.
+fn help(docstring: &Option<Docstring>) -> Option<String> {
+    let docstring = docstring.as_ref()?;
+    let help = format!("{}\n\n{}", docstring.summary, docstring.description);
+    Some(help.trim().to_string()).filter(|help| !help.is_empty())
+}
+
+fn argument_description(docstring: &Option<Docstring>, name: &str) -> Option<String> {
+    docstring
+        .as_ref()?
+        .params
+        .iter()
+        .find(|entry| entry.name == name)
+        .map(|entry| entry.description.clone())
+}
+
+/// Renders the endpoints of a module as one MDX page titled `title`.
+pub fn render_routes_page(title: &str, endpoints: &[Endpoint]) -> String {
+    let mut page = String::new();
+    page.push_str("---\n");
+    page.push_str(&format!("title: {}\n", title));
+    page.push_str("---\n\n");
+
+    let anchors = anchors(endpoints);
+    page.push_str("| Method | Path | Description |\n| --- | --- | --- |\n");
+    for (endpoint, anchor) in endpoints.iter().zip(&anchors) {
+        let summary = endpoint
+            .help
+            .as_deref()
+            .and_then(|help| help.lines().next())
+            .unwrap_or("");
+        page.push_str(&format!(
+            "| {} | [`{}`](#{}) | {} |\n",
+            endpoint.methods.join(", "),
+            table_cell(&endpoint.path),
+            anchor,
+            table_cell(summary)
+        ));
+    }
+    page.push('\n');
+
+    for (endpoint, anchor) in endpoints.iter().zip(&anchors) {
+        page.push_str(&format!("<a id=\"{}\"></a>\n", anchor));
+        page.push_str(&format!(
+            "### `{}` `{}`\n\n",
+            endpoint.methods.join(", "),
+            endpoint.path
+        ));
+        if let Some(help) = &endpoint.help {
+            page.push_str(&format!("{}\n\n", help));
+        }
+
+        if !endpoint.parameters.is_empty() {
+            page.push_str(
+                "**Parameters:**\n\n| Name | In | Type | Default | Required | Description |\n| --- | --- | --- | --- | --- | --- |\n",
+            );
+            for parameter in &endpoint.parameters {
+                page.push_str(&format!(
+                    "| `{}` | {} | `{}` | {} | {} | {} |\n",
+                    parameter.name,
+                    parameter.location,
+                    table_cell(&parameter.type_name),
+                    parameter.default.as_deref().map_or_else(
+                        || "-".to_string(),
+                        |default| format!("`{}`", table_cell(default))
+                    ),
+                    if parameter.required { "yes" } else { "no" },
+                    table_cell(parameter.description.as_deref().unwrap_or(""))
+                ));
+            }
+            page.push('\n');
+        }
+
+        match (&endpoint.response, &endpoint.status_code) {
+            (Some(response), Some(status)) => {
+                page.push_str(&format!("**Response:** `{}` `{}`\n\n", status, response))
+            }
+            (Some(response), None) => page.push_str(&format!("**Response:** `{}`\n\n", response)),
+            (None, Some(status)) => page.push_str(&format!("**Response:** `{}`\n\n", status)),
+            (None, None) => {}
+        }
+    }
+    page
+}
+
+/// One anchor per endpoint, named after its handler; a handler with
+/// several routes gets numbered anchors after the first.
+fn anchors(endpoints: &[Endpoint]) -> Vec<String> {
+    let mut seen: BTreeMap<&str, usize> = BTreeMap::new();
+    endpoints
+        .iter()
+        .map(|endpoint| {
+            let count = seen.entry(&endpoint.function).or_default();
+            *count += 1;
+            match *count {
+                1 => format!("routes.{}", endpoint.function),
+                count => format!("routes.{}-{}", endpoint.function, count),
+            }
+        })
+        .collect()
+}
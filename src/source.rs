@@ -0,0 +1,606 @@
+use rustpython_parser::ast::{self, Ranged};
+use rustpython_parser::source_code::LineIndex;
+use rustpython_parser::text_size::TextSize;
+
+use crate::diagnostics::Category;
+use crate::model::{DataClassDoc, DataClassKind};
+use crate::options::NestedFunctions;
+use crate::parse::{Context, FunctionDef};
+
+/// Source text with its line index, for the extractors that read a module
+/// without building a [`Context`].
+pub(crate) struct SourceText<'a> {
+    source: &'a str,
+    line_index: LineIndex,
+}
+
+impl<'a> SourceText<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            line_index: LineIndex::from_source_text(source),
+        }
+    }
+
+    /// Returns the one-based line number a node starts on.
+    pub(crate) fn line(&self, node: &impl Ranged) -> usize {
+        self.line_index
+            .source_location(node.start(), self.source)
+            .row
+            .to_usize()
+    }
+
+    /// Source text of an expression on a single line.
+    pub(crate) fn one_line(&self, expr: &ast::Expr) -> String {
+        let text = &self.source[expr.range()];
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// The value of the keyword argument `name` of `call`.
+pub(crate) fn keyword<'e>(call: &'e ast::ExprCall, name: &str) -> Option<&'e ast::Expr> {
+    call.keywords
+        .iter()
+        .find(|keyword| keyword.arg.as_deref() == Some(name))
+        .map(|keyword| &keyword.value)
+}
+
+/// The value of a string literal.
+pub(crate) fn string(expr: &ast::Expr) -> Option<&str> {
+    match expr {
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(value),
+            ..
+        }) => Some(value),
+        _ => None,
+    }
+}
+
+/// Whether `expr` is the literal `True`.
+pub(crate) fn is_true(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Bool(true),
+            ..
+        })
+    )
+}
+
+/// Renders a decorator as Python source: `name`, `module.name`, or a call
+/// such as `validator("name", pre=True)` with its arguments as written.
+pub(crate) fn render_decorator(ctx: &Context, decorator: &ast::Expr) -> String {
+    match decorator {
+        ast::Expr::Name(name) => name.id.to_string(),
+        ast::Expr::Attribute(attribute) => format!(
+            "{}.{}",
+            render_decorator(ctx, &attribute.value),
+            attribute.attr
+        ),
+        ast::Expr::Call(call) => {
+            let mut arguments: Vec<(TextSize, String)> = call
+                .args
+                .iter()
+                .map(|arg| (arg.start(), ctx.text(arg.range()).to_string()))
+                .collect();
+            arguments.extend(call.keywords.iter().map(|keyword| {
+                let value = ctx.text(keyword.value.range());
+                let argument = match &keyword.arg {
+                    Some(name) => format!("{}={}", name, value),
+                    None => format!("**{}", value),
+                };
+                (keyword.start(), argument)
+            }));
+            arguments.sort_by_key(|(start, _)| *start);
+            let arguments: Vec<String> = arguments.into_iter().map(|(_, arg)| arg).collect();
+            format!(
+                "{}({})",
+                render_decorator(ctx, &call.func),
+                arguments.join(", ")
+            )
+        }
+        _ => ctx.text(decorator.range()).to_string(),
+    }
+}
+
+pub(crate) fn reconstruct_function_def(ctx: &mut Context, func_def: &FunctionDef) -> String {
+    let mut func_str = String::new();
+
+    // Add decorators
+    for decorator in func_def.decorator_list {
+        func_str.push_str(&format!("@{}\n", render_decorator(ctx, decorator)));
+    }
+
+    // Function signature
+    if func_def.is_async {
+        func_str.push_str("async ");
+    }
+    func_str.push_str(&format!("def {}", func_def.name));
+    if !func_def.type_params.is_empty() {
+        let type_params: Vec<&str> = func_def
+            .type_params
+            .iter()
+            .map(|type_param| ctx.text(type_param.range()))
+            .collect();
+        func_str.push_str(&format!("[{}]", type_params.join(", ")));
+    }
+    func_str.push('(');
+
+    // Arguments
+    let arg = |prefix: &str, arg: &ast::Arg, default: Option<&ast::Expr>| {
+        let mut arg_str = format!("{}{}", prefix, arg.arg);
+        if let Some(annotation) = &arg.annotation {
+            arg_str.push_str(&format!(": {}", expr_to_source(annotation)));
+        }
+        if let Some(default) = default {
+            // PEP 8 spaces the `=` only after an annotation.
+            let separator = if arg.annotation.is_some() { " = " } else { "=" };
+            arg_str.push_str(&format!("{}{}", separator, expr_to_source(default)));
+        }
+        arg_str
+    };
+    let with_default = |a: &ast::ArgWithDefault| arg("", &a.def, a.default.as_deref());
+    let arguments = func_def.args;
+    let mut args: Vec<String> = arguments.posonlyargs.iter().map(with_default).collect();
+    if !arguments.posonlyargs.is_empty() {
+        args.push("/".to_string());
+    }
+    args.extend(arguments.args.iter().map(with_default));
+    match &arguments.vararg {
+        Some(vararg) => args.push(arg("*", vararg, None)),
+        // A bare `*` marks the rest as keyword-only.
+        None if !arguments.kwonlyargs.is_empty() => args.push("*".to_string()),
+        None => {}
+    }
+    args.extend(arguments.kwonlyargs.iter().map(with_default));
+    args.extend(arguments.kwarg.iter().map(|kwarg| arg("**", kwarg, None)));
+    func_str.push_str(&args.join(", "));
+    func_str.push(')');
+
+    // Return annotation
+    if let Some(returns) = func_def.returns {
+        func_str.push_str(&format!(" -> {}", expr_to_source(returns)));
+    }
+
+    func_str.push_str(":\n");
+
+    // Docstring (if available)
+    if let Some(ast::Stmt::Expr(expr)) = func_def.body.first() {
+        if let ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(docstring),
+            ..
+        }) = &*expr.value
+        {
+            func_str.push_str(&format!(
+                "    \"\"\"\n    {}\n    \"\"\"\n",
+                docstring.trim()
+            ));
+        }
+    }
+
+    // Function body
+    for stmt in func_def.body {
+        match stmt {
+            ast::Stmt::Expr(expr) => {
+                // Skip the docstring, as it's already handled
+                if let ast::Expr::Constant(ast::ExprConstant {
+                    value: ast::Constant::Str(_),
+                    ..
+                }) = &*expr.value
+                {
+                    continue;
+                }
+                func_str.push_str(&format!("    {}\n", expr_to_source(&expr.value)));
+            }
+            ast::Stmt::Pass(_) => func_str.push_str("    pass\n"),
+            ast::Stmt::Return(ret) => {
+                if let Some(value) = &ret.value {
+                    func_str.push_str(&format!("    return {}\n", expr_to_source(value)));
+                } else {
+                    func_str.push_str("    return\n");
+                }
+            }
+            ast::Stmt::If(if_stmt) => {
+                func_str.push_str(&format!("    if {}:\n", expr_to_source(&if_stmt.test)));
+                for body_stmt in &if_stmt.body {
+                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(ctx, body_stmt)));
+                }
+                if !if_stmt.orelse.is_empty() {
+                    func_str.push_str("    else:\n");
+                    for else_stmt in &if_stmt.orelse {
+                        func_str
+                            .push_str(&format!("        {}\n", reconstruct_stmt(ctx, else_stmt)));
+                    }
+                }
+            }
+            ast::Stmt::Assign(assign) => {
+                let targets: Vec<String> = assign.targets.iter().map(expr_to_source).collect();
+                func_str.push_str(&format!(
+                    "    {} = {}\n",
+                    targets.join(", "),
+                    expr_to_source(&assign.value)
+                ));
+            }
+            ast::Stmt::AugAssign(aug_assign) => {
+                func_str.push_str(&format!(
+                    "    {} {}= {}\n",
+                    expr_to_source(&aug_assign.target),
+                    operator(aug_assign.op),
+                    expr_to_source(&aug_assign.value)
+                ));
+            }
+            ast::Stmt::For(for_stmt) => {
+                func_str.push_str(&format!(
+                    "    for {} in {}:\n",
+                    expr_to_source(&for_stmt.target),
+                    expr_to_source(&for_stmt.iter)
+                ));
+                for body_stmt in &for_stmt.body {
+                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(ctx, body_stmt)));
+                }
+            }
+            ast::Stmt::While(while_stmt) => {
+                func_str.push_str(&format!(
+                    "    while {}:\n",
+                    expr_to_source(&while_stmt.test)
+                ));
+                for body_stmt in &while_stmt.body {
+                    func_str.push_str(&format!("        {}\n", reconstruct_stmt(ctx, body_stmt)));
+                }
+            }
+            ast::Stmt::Raise(raise) => {
+                if let Some(exc) = &raise.exc {
+                    func_str.push_str(&format!("    raise {}\n", expr_to_source(exc)));
+                } else {
+                    func_str.push_str("    raise\n");
+                }
+            }
+            ast::Stmt::FunctionDef(_) | ast::Stmt::AsyncFunctionDef(_) => {
+                if ctx.options.nested_functions == NestedFunctions::Omit {
+                    continue;
+                }
+                if let Some(nested) = FunctionDef::from_stmt(stmt) {
+                    for line in reconstruct_function_def(ctx, &nested).lines() {
+                        func_str.push_str(&format!("    {}\n", line));
+                    }
+                }
+            }
+            _ => {
+                warn_unhandled(ctx, stmt);
+                func_str.push_str(&format!("    # Unhandled statement: {:?}\n", stmt))
+            }
+        }
+    }
+
+    func_str
+}
+
+/// The `__init__` a dataclass-like class gets from its fields, written as a
+/// stub since no such method exists in the source. Named tuples get a
+/// `__new__` instead.
+pub(crate) fn synthesized_init(data: &DataClassDoc) -> String {
+    let (method, receiver, returns) = match data.kind {
+        DataClassKind::NamedTuple => ("__new__", "cls", "Self"),
+        _ => ("__init__", "self", "None"),
+    };
+    let mut parameters = vec![receiver.to_string()];
+    if data.kw_only && !data.fields.is_empty() {
+        parameters.push("*".to_string());
+    }
+    parameters.extend(data.fields.iter().map(|field| {
+        let mut parameter = field.name.clone();
+        if let Some(type_name) = &field.type_name {
+            parameter.push_str(&format!(": {}", type_name));
+        }
+        if let Some(default) = &field.default {
+            let separator = if field.type_name.is_some() {
+                " = "
+            } else {
+                "="
+            };
+            parameter.push_str(&format!("{}{}", separator, default));
+        }
+        parameter
+    }));
+    format!(
+        "def {}({}) -> {}: ...\n",
+        method,
+        parameters.join(", "),
+        returns
+    )
+}
+
+fn reconstruct_stmt(ctx: &mut Context, stmt: &ast::Stmt) -> String {
+    match stmt {
+        ast::Stmt::Expr(expr) => expr_to_source(&expr.value),
+        ast::Stmt::Pass(_) => "pass".to_string(),
+        ast::Stmt::Return(ret) => {
+            if let Some(value) = &ret.value {
+                format!("return {}", expr_to_source(value))
+            } else {
+                "return".to_string()
+            }
+        }
+        ast::Stmt::If(if_stmt) => {
+            let mut if_str = format!("if {}:\n", expr_to_source(&if_stmt.test));
+            for body_stmt in &if_stmt.body {
+                if_str.push_str(&format!("    {}\n", reconstruct_stmt(ctx, body_stmt)));
+            }
+            if !if_stmt.orelse.is_empty() {
+                if_str.push_str("else:\n");
+                for else_stmt in &if_stmt.orelse {
+                    if_str.push_str(&format!("    {}\n", reconstruct_stmt(ctx, else_stmt)));
+                }
+            }
+            if_str
+        }
+        ast::Stmt::Assign(assign) => {
+            let targets: Vec<String> = assign.targets.iter().map(expr_to_source).collect();
+            format!("{} = {}", targets.join(", "), expr_to_source(&assign.value))
+        }
+        ast::Stmt::AugAssign(aug_assign) => {
+            format!(
+                "{} {}= {}",
+                expr_to_source(&aug_assign.target),
+                operator(aug_assign.op),
+                expr_to_source(&aug_assign.value)
+            )
+        }
+        ast::Stmt::Raise(raise) => {
+            if let Some(exc) = &raise.exc {
+                format!("raise {}", expr_to_source(exc))
+            } else {
+                "raise".to_string()
+            }
+        }
+        _ => {
+            warn_unhandled(ctx, stmt);
+            format!("# Unhandled statement: {:?}", stmt)
+        }
+    }
+}
+
+fn warn_unhandled(ctx: &mut Context, stmt: &ast::Stmt) {
+    let debug = format!("{:?}", stmt);
+    let kind = debug.split('(').next().unwrap_or_default();
+    ctx.warn(
+        Category::UnhandledNode,
+        stmt.start(),
+        format!(
+            "`{}` statements are not supported in source reconstruction",
+            kind
+        ),
+    );
+}
+
+/// Renders a default value or other simple expression as Python source:
+/// literals, names, attributes, calls, containers, arithmetic and
+/// f-strings. Anything else, such as a lambda, is shown as `…`.
+pub(crate) fn expr_to_source(expr: &ast::Expr) -> String {
+    let join = |exprs: &[ast::Expr]| {
+        exprs
+            .iter()
+            .map(expr_to_source)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match expr {
+        ast::Expr::Constant(constant) => constant_to_source(&constant.value),
+        ast::Expr::Name(name) => name.id.to_string(),
+        ast::Expr::Attribute(attribute) => {
+            format!("{}.{}", expr_to_source(&attribute.value), attribute.attr)
+        }
+        ast::Expr::Call(call) => {
+            let mut arguments: Vec<String> = call.args.iter().map(expr_to_source).collect();
+            arguments.extend(call.keywords.iter().map(|keyword| match &keyword.arg {
+                Some(name) => format!("{}={}", name, expr_to_source(&keyword.value)),
+                None => format!("**{}", expr_to_source(&keyword.value)),
+            }));
+            format!("{}({})", expr_to_source(&call.func), arguments.join(", "))
+        }
+        ast::Expr::Subscript(subscript) => format!(
+            "{}[{}]",
+            expr_to_source(&subscript.value),
+            match &*subscript.slice {
+                ast::Expr::Tuple(tuple) if !tuple.elts.is_empty() => join(&tuple.elts),
+                slice => expr_to_source(slice),
+            }
+        ),
+        ast::Expr::Starred(starred) => format!("*{}", expr_to_source(&starred.value)),
+        ast::Expr::List(list) => format!("[{}]", join(&list.elts)),
+        ast::Expr::Tuple(tuple) if tuple.elts.len() == 1 => format!("({},)", join(&tuple.elts)),
+        ast::Expr::Tuple(tuple) => format!("({})", join(&tuple.elts)),
+        ast::Expr::Set(set) => format!("{{{}}}", join(&set.elts)),
+        ast::Expr::Dict(dict) => {
+            let entries: Vec<String> = dict
+                .keys
+                .iter()
+                .zip(&dict.values)
+                .map(|(key, value)| match key {
+                    Some(key) => format!("{}: {}", expr_to_source(key), expr_to_source(value)),
+                    None => format!("**{}", expr_to_source(value)),
+                })
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        ast::Expr::UnaryOp(unary) => {
+            let operand = operand_to_source(&unary.operand);
+            match unary.op {
+                ast::UnaryOp::USub => format!("-{}", operand),
+                ast::UnaryOp::UAdd => format!("+{}", operand),
+                ast::UnaryOp::Invert => format!("~{}", operand),
+                ast::UnaryOp::Not => format!("not {}", operand),
+            }
+        }
+        ast::Expr::BinOp(binary) => {
+            format!(
+                "{} {} {}",
+                operand_to_source(&binary.left),
+                operator(binary.op),
+                operand_to_source(&binary.right)
+            )
+        }
+        ast::Expr::Compare(compare) => {
+            let mut text = operand_to_source(&compare.left);
+            for (op, comparator) in compare.ops.iter().zip(&compare.comparators) {
+                let op = match op {
+                    ast::CmpOp::Eq => "==",
+                    ast::CmpOp::NotEq => "!=",
+                    ast::CmpOp::Lt => "<",
+                    ast::CmpOp::LtE => "<=",
+                    ast::CmpOp::Gt => ">",
+                    ast::CmpOp::GtE => ">=",
+                    ast::CmpOp::Is => "is",
+                    ast::CmpOp::IsNot => "is not",
+                    ast::CmpOp::In => "in",
+                    ast::CmpOp::NotIn => "not in",
+                };
+                text.push_str(&format!(" {} {}", op, operand_to_source(comparator)));
+            }
+            text
+        }
+        ast::Expr::BoolOp(bool_op) => {
+            let op = match bool_op.op {
+                ast::BoolOp::And => " and ",
+                ast::BoolOp::Or => " or ",
+            };
+            let values: Vec<String> = bool_op.values.iter().map(operand_to_source).collect();
+            values.join(op)
+        }
+        ast::Expr::IfExp(if_exp) => format!(
+            "{} if {} else {}",
+            operand_to_source(&if_exp.body),
+            operand_to_source(&if_exp.test),
+            operand_to_source(&if_exp.orelse)
+        ),
+        ast::Expr::NamedExpr(named) => format!(
+            "({} := {})",
+            expr_to_source(&named.target),
+            expr_to_source(&named.value)
+        ),
+        ast::Expr::JoinedStr(joined) => format!("f{}", quoted(&fstring_body(&joined.values))),
+        _ => "…".to_string(),
+    }
+}
+
+fn operator(op: ast::Operator) -> &'static str {
+    match op {
+        ast::Operator::Add => "+",
+        ast::Operator::Sub => "-",
+        ast::Operator::Mult => "*",
+        ast::Operator::MatMult => "@",
+        ast::Operator::Div => "/",
+        ast::Operator::Mod => "%",
+        ast::Operator::Pow => "**",
+        ast::Operator::LShift => "<<",
+        ast::Operator::RShift => ">>",
+        ast::Operator::BitOr => "|",
+        ast::Operator::BitXor => "^",
+        ast::Operator::BitAnd => "&",
+        ast::Operator::FloorDiv => "//",
+    }
+}
+
+/// An operand of an operator, in parentheses when it is an operation
+/// itself.
+fn operand_to_source(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::BinOp(_)
+        | ast::Expr::UnaryOp(_)
+        | ast::Expr::Compare(_)
+        | ast::Expr::BoolOp(_)
+        | ast::Expr::IfExp(_) => format!("({})", expr_to_source(expr)),
+        _ => expr_to_source(expr),
+    }
+}
+
+fn constant_to_source(constant: &ast::Constant) -> String {
+    match constant {
+        ast::Constant::None => "None".to_string(),
+        ast::Constant::Bool(true) => "True".to_string(),
+        ast::Constant::Bool(false) => "False".to_string(),
+        ast::Constant::Str(text) => quoted(&escape(text)),
+        ast::Constant::Bytes(bytes) => {
+            let text: String = bytes
+                .iter()
+                .map(|&byte| match byte {
+                    b'\\' => "\\\\".to_string(),
+                    b'\n' => "\\n".to_string(),
+                    b'\t' => "\\t".to_string(),
+                    0x20..=0x7E => char::from(byte).to_string(),
+                    _ => format!("\\x{:02x}", byte),
+                })
+                .collect();
+            format!("b{}", quoted(&text))
+        }
+        ast::Constant::Int(int) => int.to_string(),
+        ast::Constant::Float(float) => format!("{:?}", float),
+        ast::Constant::Complex { real, imag } if *real == 0.0 => format!("{:?}j", imag),
+        ast::Constant::Complex { real, imag } => format!("({:?} + {:?}j)", real, imag),
+        ast::Constant::Tuple(elements) => {
+            let elements: Vec<String> = elements.iter().map(constant_to_source).collect();
+            match elements.as_slice() {
+                [element] => format!("({},)", element),
+                _ => format!("({})", elements.join(", ")),
+            }
+        }
+        ast::Constant::Ellipsis => "...".to_string(),
+    }
+}
+
+/// The text of an f-string between its quotes, with `{` and `}` doubled in
+/// the literal parts.
+fn fstring_body(values: &[ast::Expr]) -> String {
+    values
+        .iter()
+        .map(|value| match value {
+            ast::Expr::Constant(ast::ExprConstant {
+                value: ast::Constant::Str(text),
+                ..
+            }) => escape(text).replace('{', "{{").replace('}', "}}"),
+            ast::Expr::FormattedValue(formatted) => {
+                let conversion = match formatted.conversion {
+                    ast::ConversionFlag::None => "",
+                    ast::ConversionFlag::Str => "!s",
+                    ast::ConversionFlag::Ascii => "!a",
+                    ast::ConversionFlag::Repr => "!r",
+                };
+                let spec = match formatted.format_spec.as_deref() {
+                    Some(ast::Expr::JoinedStr(spec)) => format!(":{}", fstring_body(&spec.values)),
+                    _ => String::new(),
+                };
+                format!(
+                    "{{{}{}{}}}",
+                    expr_to_source(&formatted.value),
+                    conversion,
+                    spec
+                )
+            }
+            _ => "…".to_string(),
+        })
+        .collect()
+}
+
+/// Escapes backslashes and control characters for a string literal.
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            '\r' => "\\r".to_string(),
+            '\t' => "\\t".to_string(),
+            c if c.is_control() => format!("\\x{:02x}", u32::from(c)),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Wraps escaped text in double quotes, or in single quotes when it holds a
+/// double quote but no single one.
+fn quoted(text: &str) -> String {
+    if text.contains('"') && !text.contains('\'') {
+        format!("'{}'", text)
+    } else {
+        format!("\"{}\"", text.replace('"', "\\\""))
+    }
+}
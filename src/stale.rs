@@ -0,0 +1,239 @@
+//! Comparing generated pages with the files already in the output
+//! directory, for checking in CI that committed documentation is up to
+//! date.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::paths::{files_under, normalize_path};
+
+/// Lines of unchanged context shown around each change in a diff.
+const CONTEXT: usize = 3;
+
+/// Changed regions whose old and new line counts multiply to more than
+/// this are not diffed line by line: the old lines are all shown as removed
+/// and the new ones as added.
+const MAX_DIFF_CELLS: usize = 16_000_000;
+
+/// How a file on disk differs from what would be generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Staleness {
+    /// The page would be generated but there is no file for it.
+    Missing,
+    /// The file exists with different content; `diff` is a unified diff
+    /// from the file to the generated page.
+    Changed { diff: String },
+    /// The file is in the output directory but no page would be generated
+    /// for it.
+    Extra,
+}
+
+/// A file in the output directory that does not match the generated pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalePage {
+    pub path: PathBuf,
+    pub staleness: Staleness,
+}
+
+impl fmt::Display for StalePage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = normalize_path(&self.path);
+        match &self.staleness {
+            Staleness::Missing => write!(f, "{}: missing", path),
+            Staleness::Changed { diff } => write!(f, "{}: out of date\n{}", path, diff),
+            Staleness::Extra => write!(f, "{}: not generated from any source", path),
+        }
+    }
+}
+
+/// Compares `pages`, given as output file and content, with the files
+/// under `output_path`. Files with the `extension` of the pages that none
+/// of them would write are reported as extra. Diffs show at most
+/// `max_diff_lines` changed lines per file. Nothing is written.
+pub fn stale_pages(
+    pages: &[(PathBuf, String)],
+    output_path: &Path,
+    extension: &str,
+    max_diff_lines: usize,
+) -> Vec<StalePage> {
+    let _span = tracing::info_span!("check").entered();
+
+    let mut stale = Vec::new();
+    for (path, content) in pages {
+        let staleness = match fs::read_to_string(path) {
+            Err(_) => Staleness::Missing,
+            Ok(existing) if existing == *content => continue,
+            Ok(existing) => Staleness::Changed {
+                diff: unified_diff(&existing, content, max_diff_lines),
+            },
+        };
+        stale.push(StalePage {
+            path: path.clone(),
+            staleness,
+        });
+    }
+
+    let generated: BTreeSet<&Path> = pages.iter().map(|(path, _)| path.as_path()).collect();
+    let existing = files_under(output_path, false).unwrap_or_default();
+    for path in existing {
+        let has_extension = path.extension().is_some_and(|found| found == extension);
+        if has_extension && !generated.contains(path.as_path()) {
+            stale.push(StalePage {
+                path,
+                staleness: Staleness::Extra,
+            });
+        }
+    }
+    stale
+}
+
+/// One line of an edit script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// A unified diff from `old` to `new` with [`CONTEXT`] lines of context,
+/// cut off after `max_lines` removed or added lines.
+pub fn unified_diff(old: &str, new: &str, max_lines: usize) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let edits = edit_script(&old, &new);
+
+    // Line numbers in the old and new text before each edit.
+    let mut positions = Vec::with_capacity(edits.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for edit in &edits {
+        positions.push((old_line, new_line));
+        match edit {
+            Edit::Keep => {
+                old_line += 1;
+                new_line += 1;
+            }
+            Edit::Remove => old_line += 1,
+            Edit::Add => new_line += 1,
+        }
+    }
+
+    let changes: Vec<usize> = (0..edits.len())
+        .filter(|&index| edits[index] != Edit::Keep)
+        .collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &index in &changes {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(edits.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut diff = String::new();
+    let mut shown = 0;
+    for (start, end) in hunks {
+        if shown == max_lines {
+            break;
+        }
+        let span = &edits[start..end];
+        let old_count = span.iter().filter(|edit| **edit != Edit::Add).count();
+        let new_count = span.iter().filter(|edit| **edit != Edit::Remove).count();
+        let (old_start, new_start) = positions[start];
+        diff.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + 1,
+            old_count,
+            new_start + 1,
+            new_count
+        ));
+        for index in start..end {
+            let (old_line, new_line) = positions[index];
+            let line = match edits[index] {
+                Edit::Keep => format!(" {}", old[old_line]),
+                Edit::Remove => format!("-{}", old[old_line]),
+                Edit::Add => format!("+{}", new[new_line]),
+            };
+            if edits[index] != Edit::Keep {
+                if shown == max_lines {
+                    break;
+                }
+                shown += 1;
+            }
+            diff.push_str(&line);
+            diff.push('\n');
+        }
+    }
+    if changes.len() > shown {
+        diff.push_str(&format!(
+            "... {} more changed line(s)\n",
+            changes.len() - shown
+        ));
+    }
+    diff
+}
+
+/// The shortest edit script turning `old` into `new`, from the longest
+/// common subsequence of the lines between their common prefix and suffix.
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut edits = vec![Edit::Keep; prefix];
+    if old_middle.len() * new_middle.len() > MAX_DIFF_CELLS {
+        edits.extend(std::iter::repeat_n(Edit::Remove, old_middle.len()));
+        edits.extend(std::iter::repeat_n(Edit::Add, new_middle.len()));
+    } else {
+        edits.extend(common_subsequence_edits(old_middle, new_middle));
+    }
+    edits.extend(std::iter::repeat_n(Edit::Keep, suffix));
+    edits
+}
+
+fn common_subsequence_edits(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    // lengths[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let width = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            edits.push(Edit::Keep);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            edits.push(Edit::Remove);
+            i += 1;
+        } else {
+            edits.push(Edit::Add);
+            j += 1;
+        }
+    }
+    edits.extend(std::iter::repeat_n(Edit::Remove, old.len() - i));
+    edits.extend(std::iter::repeat_n(Edit::Add, new.len() - j));
+    edits
+}
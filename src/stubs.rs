@@ -0,0 +1,218 @@
+//! Merging `.pyi` stub files into the modules they describe.
+//!
+//! Stubs often carry richer annotations than the implementation, while the
+//! implementation has the docstrings and bodies. Merging takes signatures
+//! from one and documentation from the other.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::{Category, Diagnostic, Severity};
+use crate::encoding::read_source;
+use crate::model::{ClassDoc, FunctionDoc, Item, ModuleDoc, Parameter};
+use crate::options::RenderOptions;
+use crate::parse::{describe_parameters, parse_module};
+
+/// Which side wins when the stub and the implementation disagree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StubPreference {
+    /// Signatures and annotations come from the stub.
+    #[default]
+    Stub,
+    /// The implementation's signature is kept; the stub only fills in
+    /// missing annotations.
+    Impl,
+}
+
+/// Merges the stub next to `module.path` (same name, `.pyi` extension) into
+/// `module`, if there is one. Returns whether a stub was merged.
+pub fn merge_adjacent_stub(
+    module: &mut ModuleDoc,
+    options: &RenderOptions,
+    prefer: StubPreference,
+) -> bool {
+    let stub_path = module.path.with_extension("pyi");
+    if module.path == stub_path {
+        return false;
+    }
+    let Some(stub) = read_stub(&stub_path, options) else {
+        return false;
+    };
+    merge_stub(module, &stub, prefer);
+    true
+}
+
+/// Path of the stub of the module at `relative` in a directory of stubs
+/// mirroring the package layout: `pkg/client.py` has the stub
+/// `stubs_dir/pkg/client.pyi`.
+pub fn stub_in_dir(stubs_dir: &Path, relative: &Path) -> PathBuf {
+    stubs_dir.join(relative).with_extension("pyi")
+}
+
+/// Merges the annotations of the stub at `stub_path` into `module`, if
+/// there is one. Unlike [`merge_adjacent_stub`], definitions that only exist
+/// in the stub are left out: the module documents what it implements.
+/// Returns whether a stub was merged.
+pub fn merge_stub_signatures(
+    module: &mut ModuleDoc,
+    stub_path: &Path,
+    options: &RenderOptions,
+    prefer: StubPreference,
+) -> bool {
+    let Some(mut stub) = read_stub(stub_path, options) else {
+        return false;
+    };
+    stub.items.retain_mut(|stub_item| {
+        let Some(item) = module
+            .items
+            .iter()
+            .find(|item| item.name() == stub_item.name())
+        else {
+            return false;
+        };
+        if let (Item::Class(class), Item::Class(stub_class)) = (item, stub_item) {
+            stub_class
+                .methods
+                .retain(|stub_method| class.methods.iter().any(|m| m.name == stub_method.name));
+        }
+        true
+    });
+    merge_stub(module, &stub, prefer);
+    true
+}
+
+/// Reads and parses the stub at `stub_path`. A stub that fails to parse is
+/// reported and ignored.
+fn read_stub(stub_path: &Path, options: &RenderOptions) -> Option<ModuleDoc> {
+    let source = read_source(stub_path).ok()?;
+    let _span = tracing::debug_span!("stub").entered();
+    match parse_module(&source, stub_path, options) {
+        Ok(stub) => Some(stub),
+        Err(err) => {
+            tracing::warn!("failed to parse {}: {}", stub_path.display(), err);
+            None
+        }
+    }
+}
+
+/// Merges the signatures of `stub` into `module`. Docstrings and sources stay
+/// those of the implementation unless it has none. Definitions that only
+/// exist in the stub are appended. Functions whose parameter lists differ
+/// get a stub-mismatch diagnostic.
+pub fn merge_stub(module: &mut ModuleDoc, stub: &ModuleDoc, prefer: StubPreference) {
+    let file = module.path.clone();
+    let mut diagnostics = Vec::new();
+    for stub_item in &stub.items {
+        let existing = module
+            .items
+            .iter_mut()
+            .find(|item| item.name() == stub_item.name());
+        match (existing, stub_item) {
+            (Some(Item::Function(function)), Item::Function(stub_function)) => {
+                merge_function(&file, function, stub_function, prefer, &mut diagnostics)
+            }
+            (Some(Item::Class(class)), Item::Class(stub_class)) => {
+                merge_class(&file, class, stub_class, prefer, &mut diagnostics)
+            }
+            (Some(_), _) => {}
+            (None, stub_item) => module.items.push(stub_item.clone()),
+        }
+    }
+    module.diagnostics.extend(diagnostics);
+}
+
+fn merge_class(
+    file: &Path,
+    class: &mut ClassDoc,
+    stub: &ClassDoc,
+    prefer: StubPreference,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if class.docstring.is_none() {
+        class.docstring = stub.docstring.clone();
+    }
+    for stub_method in &stub.methods {
+        match class
+            .methods
+            .iter_mut()
+            .find(|method| method.name == stub_method.name)
+        {
+            Some(method) => merge_function(file, method, stub_method, prefer, diagnostics),
+            None => class.methods.push(stub_method.clone()),
+        }
+    }
+}
+
+fn merge_function(
+    file: &Path,
+    function: &mut FunctionDoc,
+    stub: &FunctionDoc,
+    prefer: StubPreference,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let names = |parameters: &[Parameter]| -> Vec<String> {
+        parameters.iter().map(|p| p.name.clone()).collect()
+    };
+    let (implementation_names, stub_names) = (names(&function.parameters), names(&stub.parameters));
+    if implementation_names != stub_names {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            category: Category::StubMismatch,
+            file: file.to_path_buf(),
+            line: function.line,
+            message: format!(
+                "`{}` takes ({}) but its stub declares ({})",
+                function.name,
+                implementation_names.join(", "),
+                stub_names.join(", ")
+            ),
+        });
+    }
+
+    if prefer == StubPreference::Stub {
+        let implementation = std::mem::take(&mut function.parameters);
+        function.parameters = stub
+            .parameters
+            .iter()
+            .map(|stub_parameter| {
+                let original = implementation
+                    .iter()
+                    .find(|p| p.name == stub_parameter.name);
+                let mut parameter = stub_parameter.clone();
+                // Stubs write defaults as `...`; the real value lives in the
+                // implementation.
+                if let Some(original) = original {
+                    if parameter.default.is_some() {
+                        parameter.default = original.default.clone().or(parameter.default);
+                    }
+                    parameter.annotation = parameter.annotation.or(original.annotation.clone());
+                    parameter.inferred_type = parameter.inferred_type.or(original.inferred_type);
+                }
+                parameter
+            })
+            .collect();
+        function.returns = stub.returns.clone().or(function.returns.take());
+    } else {
+        for parameter in &mut function.parameters {
+            if parameter.annotation.is_none() {
+                parameter.annotation = stub
+                    .parameters
+                    .iter()
+                    .find(|p| p.name == parameter.name)
+                    .and_then(|p| p.annotation.clone());
+            }
+        }
+        if function.returns.is_none() {
+            function.returns = stub.returns.clone();
+        }
+    }
+
+    if function.docstring.is_none() {
+        function.docstring = stub.docstring.clone();
+        function.parsed_docstring = stub.parsed_docstring.clone();
+    }
+    // Descriptions always come from the docstring that is shown.
+    describe_parameters(&mut function.parameters, &function.parsed_docstring);
+}
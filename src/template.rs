@@ -0,0 +1,175 @@
+//! Templates naming the page written for each module.
+//!
+//! A template is a path relative to the output directory with
+//! placeholders in braces:
+//!
+//! | Placeholder     | Value for `src/zenml/client/base_client.py`      |
+//! | --------------- | ------------------------------------------------ |
+//! | `{page}`        | `client/base_client`                             |
+//! | `{stem}`        | `base_client`                                    |
+//! | `{kebab}`       | `client/base-client`                             |
+//! | `{module_path}` | `zenml.client.base_client`                       |
+//! | `{ext}`         | `mdx`, or `md` for Markdown                      |
+//!
+//! Without a template pages are named `{page}.{ext}`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::ModuleDoc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Page,
+    Stem,
+    Kebab,
+    ModulePath,
+    Ext,
+}
+
+impl Placeholder {
+    const ALL: [Placeholder; 5] = [
+        Placeholder::Page,
+        Placeholder::Stem,
+        Placeholder::Kebab,
+        Placeholder::ModulePath,
+        Placeholder::Ext,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Placeholder::Page => "page",
+            Placeholder::Stem => "stem",
+            Placeholder::Kebab => "kebab",
+            Placeholder::ModulePath => "module_path",
+            Placeholder::Ext => "ext",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A validated output file name template.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OutputTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+impl FromStr for OutputTemplate {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut rest = source;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(format!("unmatched `}}` in output template `{}`", source));
+            }
+            let close = rest[open..]
+                .find('}')
+                .map(|close| open + close)
+                .ok_or_else(|| format!("unmatched `{{` in output template `{}`", source))?;
+            let name = &rest[open + 1..close];
+            let placeholder = Placeholder::ALL
+                .into_iter()
+                .find(|placeholder| placeholder.name() == name)
+                .ok_or_else(|| {
+                    let known: Vec<String> = Placeholder::ALL
+                        .iter()
+                        .map(|placeholder| format!("{{{}}}", placeholder.name()))
+                        .collect();
+                    format!(
+                        "unknown placeholder `{{{}}}` in output template `{}`; expected one of {}",
+                        name,
+                        source,
+                        known.join(", ")
+                    )
+                })?;
+            if open > 0 {
+                parts.push(Part::Literal(rest[..open].to_string()));
+            }
+            parts.push(Part::Placeholder(placeholder));
+            rest = &rest[close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+        if source.starts_with('/') || source.split('/').any(|segment| segment == "..") {
+            return Err(format!(
+                "output template `{}` must stay inside the output directory",
+                source
+            ));
+        }
+        Ok(OutputTemplate {
+            source: source.to_string(),
+            parts,
+        })
+    }
+}
+
+impl TryFrom<String> for OutputTemplate {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        source.parse()
+    }
+}
+
+impl From<OutputTemplate> for String {
+    fn from(template: OutputTemplate) -> Self {
+        template.source
+    }
+}
+
+impl fmt::Display for OutputTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl OutputTemplate {
+    /// Whether the file names end in `.{extension}`, as the links between
+    /// pages assume.
+    pub fn ends_with_extension(&self, extension: &str) -> bool {
+        match self.parts.as_slice() {
+            [.., Part::Literal(dot), Part::Placeholder(Placeholder::Ext)] => dot.ends_with('.'),
+            [.., Part::Literal(last)] => last.ends_with(&format!(".{}", extension)),
+            _ => false,
+        }
+    }
+
+    /// Path of the page of `module` relative to the output directory, with
+    /// `/` separators and without the `.{extension}` the template ends in.
+    pub fn page(&self, module: &ModuleDoc, extension: &str) -> String {
+        let mut path = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => path.push_str(text),
+                Part::Placeholder(Placeholder::Page) => path.push_str(&module.page),
+                Part::Placeholder(Placeholder::Stem) => path.push_str(&module.name),
+                Part::Placeholder(Placeholder::Kebab) => path.push_str(&kebab_case(&module.page)),
+                Part::Placeholder(Placeholder::ModulePath) => path.push_str(&module.qualified_name),
+                Part::Placeholder(Placeholder::Ext) => path.push_str(extension),
+            }
+        }
+        let suffix = format!(".{}", extension);
+        path.strip_suffix(&suffix).unwrap_or(&path).to_string()
+    }
+}
+
+/// `client/_base_client` as `client/base-client`: every segment in lower
+/// case, without leading or trailing underscores and with `-` between words.
+fn kebab_case(page: &str) -> String {
+    page.split('/')
+        .map(|segment| segment.trim_matches('_').replace('_', "-").to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,147 @@
+//! Per-phase and per-file timings collected from the pipeline's tracing spans.
+//!
+//! Install a [`TimingLayer`] on a `tracing_subscriber` registry and call
+//! [`Timings::report`] on the shared handle once the run is finished.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Number of files listed in the "slowest files" part of the report.
+const SLOWEST_FILES: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PhaseTiming {
+    pub total: Duration,
+    pub count: usize,
+}
+
+/// Durations accumulated by a [`TimingLayer`].
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    /// Total time per span name, e.g. `parse` or `render`.
+    pub phases: BTreeMap<&'static str, PhaseTiming>,
+    /// Total time of the spans carrying a `file` field, per file.
+    pub files: BTreeMap<String, Duration>,
+}
+
+impl Timings {
+    /// Formats the phase table followed by the per-file table, slowest first.
+    pub fn report(&self) -> String {
+        let mut report = String::from("Timing by phase:\n");
+        let phase_width = self.phases.keys().map(|name| name.len()).max().unwrap_or(0);
+        for (name, timing) in &self.phases {
+            let _ = writeln!(
+                report,
+                "  {:<width$}  {:>10.2?}  ({} spans)",
+                name,
+                timing.total,
+                timing.count,
+                width = phase_width
+            );
+        }
+
+        let mut files: Vec<(&String, &Duration)> = self.files.iter().collect();
+        files.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let file_width = files.iter().map(|(file, _)| file.len()).max().unwrap_or(0);
+        let _ = writeln!(
+            report,
+            "Timing by file (slowest {} marked with *):",
+            SLOWEST_FILES
+        );
+        for (index, (file, duration)) in files.iter().enumerate() {
+            let marker = if index < SLOWEST_FILES { '*' } else { ' ' };
+            let _ = writeln!(
+                report,
+                "{} {:<width$}  {:>10.2?}",
+                marker,
+                file,
+                duration,
+                width = file_width
+            );
+        }
+        report
+    }
+}
+
+/// A tracing layer that records how long each span was open.
+pub struct TimingLayer {
+    timings: Arc<Mutex<Timings>>,
+}
+
+impl TimingLayer {
+    /// Creates the layer along with the handle its timings are written to.
+    pub fn new() -> (Self, Arc<Mutex<Timings>>) {
+        let timings = Arc::new(Mutex::new(Timings::default()));
+        (
+            Self {
+                timings: Arc::clone(&timings),
+            },
+            timings,
+        )
+    }
+}
+
+struct SpanTiming {
+    start: Instant,
+    file: Option<String>,
+}
+
+#[derive(Default)]
+struct FileVisitor(Option<String>);
+
+impl Visit for FileVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "file" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "file" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FileVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanTiming {
+                start: Instant::now(),
+                file: visitor.0,
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else {
+            return;
+        };
+        let elapsed = timing.start.elapsed();
+
+        let mut timings = self.timings.lock().unwrap();
+        let phase = timings.phases.entry(span.name()).or_default();
+        phase.total += elapsed;
+        phase.count += 1;
+        if let Some(file) = &timing.file {
+            *timings.files.entry(file.clone()).or_default() += elapsed;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Rendering of type annotations as display strings.
+
+use rustpython_parser::{ast, Parse, ParseError};
+
+use crate::options::{OptionalStyle, TypeRenderOptions};
+use crate::source::expr_to_source;
+
+/// Parses a standalone annotation expression such as `Optional[List[str]]`
+/// and renders it the same way parameter and return types are rendered in
+/// generated pages.
+pub fn render_annotation(source: &str, options: &TypeRenderOptions) -> Result<String, ParseError> {
+    let expr = ast::Expr::parse(source.trim(), "<annotation>")?;
+    Ok(render_type(&expr, options))
+}
+
+/// Renders an expression exactly as written, without any normalization.
+pub(crate) fn extract_type(annotation: &ast::Expr) -> String {
+    render_type(annotation, &TypeRenderOptions::default())
+}
+
+/// Infers the type of an unannotated parameter from its default value. Only
+/// literal constants and empty containers are recognized; anything else
+/// could be of any type.
+pub(crate) fn infer_type(default: &ast::Expr) -> Option<&'static str> {
+    match default {
+        ast::Expr::Constant(constant) => match &constant.value {
+            ast::Constant::Bool(_) => Some("bool"),
+            ast::Constant::Int(_) => Some("int"),
+            ast::Constant::Float(_) => Some("float"),
+            ast::Constant::Complex { .. } => Some("complex"),
+            ast::Constant::Str(_) => Some("str"),
+            ast::Constant::Bytes(_) => Some("bytes"),
+            _ => None,
+        },
+        ast::Expr::UnaryOp(unary)
+            if matches!(unary.op, ast::UnaryOp::USub | ast::UnaryOp::UAdd) =>
+        {
+            infer_type(&unary.operand).filter(|inferred| matches!(*inferred, "int" | "float"))
+        }
+        ast::Expr::List(list) if list.elts.is_empty() => Some("list"),
+        ast::Expr::Dict(dict) if dict.keys.is_empty() => Some("dict"),
+        ast::Expr::Tuple(tuple) if tuple.elts.is_empty() => Some("tuple"),
+        _ => None,
+    }
+}
+
+pub(crate) fn render_type(annotation: &ast::Expr, options: &TypeRenderOptions) -> String {
+    let render = |expr: &ast::Expr| render_type(expr, options);
+
+    match annotation {
+        ast::Expr::Name(name) => {
+            let resolved = options
+                .aliases
+                .get(name.id.as_str())
+                .map_or(name.id.as_str(), String::as_str);
+            strip_typing_prefix(resolved, options).to_string()
+        }
+        ast::Expr::Attribute(attr) => {
+            let value = render(&attr.value);
+            if options.strip_typing_prefix && is_typing_module(&value) {
+                attr.attr.to_string()
+            } else {
+                format!("{}.{}", value, attr.attr)
+            }
+        }
+        ast::Expr::Subscript(subscript) => {
+            let value_type = render(&subscript.value);
+            let elements = match &*subscript.slice {
+                ast::Expr::Tuple(tuple) => tuple.elts.as_slice(),
+                slice => std::slice::from_ref(slice),
+            };
+            let generic = strip_typing_prefix(&value_type, &TypeRenderOptions::stripped());
+            // The values of a `Literal`, and the metadata after the type of an
+            // `Annotated`, are Python values rather than types.
+            let members: Vec<String> = match (generic, elements) {
+                ("Literal", _) => elements.iter().map(expr_to_source).collect(),
+                ("Annotated", [annotated, ..]) if options.collapse_annotated => {
+                    return render(annotated);
+                }
+                ("Annotated", [annotated, metadata @ ..]) => std::iter::once(render(annotated))
+                    .chain(metadata.iter().map(expr_to_source))
+                    .collect(),
+                _ => elements.iter().map(render).collect(),
+            };
+            match generic {
+                "Optional" if options.optional_style != OptionalStyle::Preserve => {
+                    return format!("{} | None", members.join(", "));
+                }
+                "Union" if options.optional_style == OptionalStyle::PipeUnions => {
+                    return members.join(" | ");
+                }
+                _ => {}
+            }
+            format!("{}[{}]", value_type, members.join(", "))
+        }
+        ast::Expr::List(list) => {
+            let elements: Vec<String> = list.elts.iter().map(render).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        ast::Expr::Tuple(tuple) => {
+            let elements: Vec<String> = tuple.elts.iter().map(render).collect();
+            format!("({})", elements.join(", "))
+        }
+        ast::Expr::Call(call) => {
+            let func_name = render(&call.func);
+            let args: Vec<String> = call.args.iter().map(render).collect();
+            format!("{}[{}]", func_name, args.join(", "))
+        }
+        // A forward reference such as `"Client"` renders as the annotation
+        // it holds, or as written when that does not parse.
+        ast::Expr::Constant(ast::ExprConstant {
+            value: ast::Constant::Str(text),
+            ..
+        }) => match ast::Expr::parse(text.trim(), "<annotation>") {
+            Ok(expr) => render(&expr),
+            Err(_) => text.to_string(),
+        },
+        // `None`, and the `...` of `Callable[..., R]` or `Tuple[int, ...]`.
+        ast::Expr::Constant(_) => expr_to_source(annotation),
+        ast::Expr::BinOp(binop) => {
+            let left = render(&binop.left);
+            let right = render(&binop.right);
+            format!("{} | {}", left, right) // Assuming '|' is used for Union types
+        }
+        // If we encounter any other type that we haven't explicitly handled,
+        // we'll return it as a string representation
+        _ => format!("{:?}", annotation),
+    }
+}
+
+fn is_typing_module(name: &str) -> bool {
+    matches!(name, "typing" | "typing_extensions")
+}
+
+fn strip_typing_prefix<'a>(name: &'a str, options: &TypeRenderOptions) -> &'a str {
+    if !options.strip_typing_prefix {
+        return name;
+    }
+    name.split_once('.')
+        .filter(|(module, _)| is_typing_module(module))
+        .map_or(name, |(_, rest)| rest)
+}
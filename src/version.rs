@@ -0,0 +1,31 @@
+//! Finding the release of the documented package.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The version in the nearest `pyproject.toml` in `start` or one of its
+/// ancestors, with the file it was read from. The version is taken from
+/// `[project]`, or from `[tool.poetry]` for Poetry projects; a
+/// `pyproject.toml` without one ends the search.
+pub fn pyproject_version(start: &Path) -> Option<(PathBuf, String)> {
+    let start = fs::canonicalize(start).ok()?;
+    let file = start
+        .ancestors()
+        .map(|dir| dir.join("pyproject.toml"))
+        .find(|file| file.is_file())?;
+    let source = fs::read_to_string(&file).ok()?;
+    let pyproject: toml::Table = match source.parse() {
+        Ok(pyproject) => pyproject,
+        Err(err) => {
+            tracing::warn!("failed to parse {}: {}", file.display(), err);
+            return None;
+        }
+    };
+    let version = ["project", "tool.poetry"].iter().find_map(|table| {
+        let table = table
+            .split('.')
+            .try_fold(&pyproject, |table, key| table.get(key)?.as_table())?;
+        table.get("version")?.as_str()
+    })?;
+    Some((file, version.to_string()))
+}
@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use sdkdocgen::{content_hash, Cache, CACHE_FILE};
+
+#[test]
+fn hashes_are_stable() {
+    assert_eq!(content_hash(b""), "cbf29ce484222325");
+    assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+    assert_ne!(content_hash(b"# client\n"), content_hash(b"# client\n\n"));
+}
+
+#[test]
+fn round_trips_through_the_output_directory() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cache");
+    let _ = fs::remove_dir_all(&root);
+    assert_eq!(Cache::load(&root), None);
+
+    let cache = Cache {
+        version: "0.1.0".to_string(),
+        settings: content_hash(b"settings"),
+        inputs: BTreeMap::from([(
+            "src/client.py".to_string(),
+            content_hash(b"class Client: ..."),
+        )]),
+        pages: BTreeMap::from([("client.mdx".to_string(), content_hash(b"# client\n"))]),
+    };
+    cache.save(&root).unwrap();
+    assert!(root.join(CACHE_FILE).is_file());
+
+    let loaded = Cache::load(&root).unwrap();
+    assert_eq!(loaded, cache);
+    assert!(loaded.has_page("client.mdx", "# client\n"));
+    assert!(!loaded.has_page("client.mdx", "# client, changed\n"));
+    assert!(!loaded.has_page("stack.mdx", "# client\n"));
+
+    fs::write(root.join(CACHE_FILE), "not json").unwrap();
+    assert_eq!(Cache::load(&root), None);
+}
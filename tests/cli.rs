@@ -0,0 +1,162 @@
+use std::fs;
+use std::path::Path;
+
+use sdkdocgen::{extract_cli, render_cli_page, CliCommand, CliParameter};
+
+fn extract(fixture: &str) -> Vec<CliCommand> {
+    let path = Path::new("tests/fixtures/cli").join(fixture);
+    extract_cli(&fs::read_to_string(&path).unwrap(), &path).unwrap()
+}
+
+fn parameter(
+    flags: &[&str],
+    type_name: &str,
+    default: Option<&str>,
+    required: bool,
+    help: Option<&str>,
+) -> CliParameter {
+    CliParameter {
+        flags: flags.iter().map(|flag| flag.to_string()).collect(),
+        type_name: type_name.to_string(),
+        default: default.map(str::to_string),
+        required,
+        help: help.map(str::to_string),
+    }
+}
+
+#[test]
+fn extracts_click_groups_and_commands() {
+    let commands = extract("click_app.py");
+    assert_eq!(commands.len(), 1);
+    let cli = &commands[0];
+    assert_eq!(cli.name, "cli");
+    assert_eq!(
+        cli.help.as_deref(),
+        Some("Manage stores.\n\nEvery command talks to the configured store.")
+    );
+    assert_eq!(
+        cli.options,
+        [parameter(
+            &["--verbose", "-v"],
+            "bool",
+            Some("False"),
+            false,
+            Some("Print more output.")
+        )]
+    );
+
+    let names: Vec<&str> = cli.subcommands.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["get-value", "put"]);
+
+    let get = &cli.subcommands[0];
+    // The docstring is cut at the form feed, like Click's help output.
+    assert_eq!(get.help.as_deref(), Some("Print a value."));
+    assert_eq!(
+        get.arguments,
+        [parameter(&["KEY"], "str", None, true, None)]
+    );
+    assert_eq!(
+        get.options,
+        [
+            parameter(
+                &["--default", "-d"],
+                "str",
+                Some("\"\""),
+                false,
+                Some("Value printed when KEY is missing.")
+            ),
+            parameter(
+                &["--timeout"],
+                "click.IntRange(0, 60)",
+                Some("10"),
+                false,
+                None
+            ),
+        ]
+    );
+
+    let put = &cli.subcommands[1];
+    assert_eq!(put.function.as_deref(), Some("put_values"));
+    assert_eq!(
+        put.arguments,
+        [parameter(&["PAIRS"], "str", None, false, None)]
+    );
+    assert!(put.options[1].required);
+    assert_eq!(put.options[0].type_name, "bool");
+
+    assert_eq!(
+        cli.functions().into_iter().collect::<Vec<_>>(),
+        ["cli", "get_value", "put_values"]
+    );
+}
+
+#[test]
+fn extracts_argparse_parsers_and_subparsers() {
+    let commands = extract("argparse_app.py");
+    assert_eq!(commands.len(), 1);
+    let stores = &commands[0];
+    assert_eq!(stores.name, "stores");
+    assert_eq!(stores.help.as_deref(), Some("Manage stores."));
+    // `--debug` is hidden with `argparse.SUPPRESS`.
+    assert_eq!(
+        stores.options,
+        [parameter(
+            &["--config"],
+            "str",
+            Some("\"stores.toml\""),
+            false,
+            Some("Config file.")
+        )]
+    );
+
+    let get = &stores.subcommands[0];
+    assert_eq!(get.name, "get");
+    assert_eq!(
+        get.arguments,
+        [parameter(
+            &["key"],
+            "str",
+            None,
+            true,
+            Some("Key to look up.")
+        )]
+    );
+    assert_eq!(
+        get.options,
+        [parameter(
+            &["--retries"],
+            "int",
+            Some("3"),
+            false,
+            Some("Retries | attempts.")
+        )]
+    );
+
+    let list = &stores.subcommands[1];
+    assert_eq!(list.help.as_deref(), Some("List keys."));
+    // Arguments of a mutually exclusive group belong to its parser.
+    assert_eq!(
+        list.options,
+        [parameter(
+            &["--all", "-a"],
+            "bool",
+            Some("False"),
+            false,
+            Some("Include hidden keys.")
+        )]
+    );
+    assert_eq!(
+        list.arguments,
+        [parameter(&["prefix"], "str", None, false, None)]
+    );
+}
+
+#[test]
+fn renders_one_page_per_top_level_command() {
+    let page = render_cli_page(&extract("argparse_app.py")[0]);
+    let expected_start = "---\ntitle: stores\n---\n\n<a id=\"cli.stores\"></a>\n## `stores` `group`\n\nManage stores.\n\n";
+    assert!(page.starts_with(expected_start), "{}", page);
+    assert!(page.contains("| [`get`](#cli.stores.get) | Print a value. |\n"));
+    assert!(page.contains("<a id=\"cli.stores.get\"></a>\n### `stores get` `command`\n"));
+    assert!(page.contains("| `--retries` | `int` | `3` | no | Retries \\| attempts. |\n"));
+}
@@ -0,0 +1,234 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{
+    parse_module, AliasStyle, Badge, Config, DiagnosticsFormat, Dunders, MarkerAction,
+    NestedFunctions, OptionalStyle, RenderOptions, StubPreference,
+};
+
+#[test]
+fn markers_replace_and_extend_the_defaults() {
+    let config = Config::from_toml(
+        r#"
+[markers]
+experimental = "exclude"
+beta = { badge = "beta", note = "Feedback welcome." }
+"#,
+    )
+    .unwrap();
+    let mut options = RenderOptions::default();
+    config.apply(&mut options);
+
+    assert_eq!(options.markers["internal"], MarkerAction::Exclude);
+    assert_eq!(options.markers["experimental"], MarkerAction::Exclude);
+    assert_eq!(
+        options.markers["beta"],
+        MarkerAction::Badge(Badge {
+            label: "beta".to_string(),
+            note: Some("Feedback welcome.".to_string()),
+        })
+    );
+}
+
+#[test]
+fn rejects_unknown_sections_and_actions() {
+    let err = Config::from_toml("[marker]\ninternal = \"exclude\"\n").unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `marker`"),
+        "{}",
+        err
+    );
+    assert!(Config::from_toml("[markers]\ninternal = \"hide\"\n").is_err());
+}
+
+#[test]
+fn turns_off_usage_snippets() {
+    let mut options = RenderOptions::default();
+    Config::from_toml("").unwrap().apply(&mut options);
+    assert!(options.usage_snippets);
+
+    Config::from_toml("usage_snippets = false\n")
+        .unwrap()
+        .apply(&mut options);
+    assert!(!options.usage_snippets);
+}
+
+#[test]
+fn sets_the_command_line_options() {
+    let config = Config::from_toml(
+        r#"
+file = ["src/zenml"]
+output_path = "docs/sdk"
+exclude = ["**/tests/**"]
+package_name = "acme"
+include_private = true
+dunders = "all"
+ignore_all = true
+skip_decorator = ["command"]
+pydantic_base = ["BaseSettings"]
+show_validators = true
+skip_nested_config = true
+nested_functions = "document"
+merge_init = true
+deprecated_decorator = ["legacy"]
+hide_deprecated = true
+deprecated_last = true
+signature_from_docstring = true
+all_module_attributes = true
+only = "Client|Stack"
+expand_aliases = "both"
+no_infer_types = true
+normalize_unions = true
+collapse_annotated = true
+prefer = "impl"
+diagnostics_format = "json"
+strict = true
+jobs = 4
+"#,
+    )
+    .unwrap();
+    assert_eq!(config.file, [PathBuf::from("src/zenml")]);
+    assert_eq!(config.output_path, Some(PathBuf::from("docs/sdk")));
+    assert_eq!(config.exclude, ["**/tests/**"]);
+    assert_eq!(config.prefer, Some(StubPreference::Impl));
+    assert_eq!(config.diagnostics_format, Some(DiagnosticsFormat::Json));
+    assert_eq!(config.strict, Some(true));
+    assert_eq!(config.jobs, NonZeroUsize::new(4));
+
+    let mut options = RenderOptions::default();
+    config.apply(&mut options);
+    assert_eq!(options.module_prefix, "acme");
+    assert!(options.include_private);
+    assert_eq!(options.dunders, Dunders::All);
+    assert!(!options.respect_all);
+    assert!(options.skip_decorators.contains("command"));
+    assert!(options.pydantic_bases.contains("BaseModel"));
+    assert!(options.pydantic_bases.contains("BaseSettings"));
+    assert!(options.show_validators);
+    assert!(options.skip_nested_config);
+    assert_eq!(options.nested_functions, NestedFunctions::Document);
+    assert!(options.merge_init);
+    assert!(options.deprecated_decorators.contains("deprecated"));
+    assert!(options.deprecated_decorators.contains("legacy"));
+    assert!(options.hide_deprecated);
+    assert!(options.deprecated_last);
+    assert!(options.signature_from_docstring);
+    assert!(options.all_module_attributes);
+    assert!(options.selects("StackComponent"));
+    assert!(!options.selects("get_settings"));
+    assert_eq!(options.alias_style, AliasStyle::Both);
+    assert!(!options.infer_types);
+    assert_eq!(options.types.optional_style, OptionalStyle::PipeUnions);
+    assert!(options.types.collapse_annotated);
+}
+
+#[test]
+fn names_unknown_keys() {
+    let err = Config::from_toml("output_dir = \"docs\"\n").unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `output_dir`"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn merges_module_frontmatter() {
+    let config = Config::from_toml(
+        r#"
+[frontmatter]
+icon = "code"
+sidebarTitle = "API"
+
+[modules."zenml.client".frontmatter]
+sidebarTitle = "Client"
+"#,
+    )
+    .unwrap();
+    let mut options = RenderOptions::default();
+    config.apply(&mut options);
+
+    assert_eq!(options.frontmatter["icon"], "code");
+    assert_eq!(options.frontmatter["sidebarTitle"], "API");
+    assert_eq!(
+        options.module_frontmatter["zenml.client"]["sidebarTitle"],
+        "Client"
+    );
+    assert!(Config::from_toml("[modules.\"zenml\"]\nsidebar = \"Zenml\"\n").is_err());
+}
+
+#[test]
+fn titles_pages_by_module() {
+    let config = Config::from_toml(
+        r#"
+prettify_titles = true
+
+[modules."zenml.client"]
+title = "The ZenML client"
+"#,
+    )
+    .unwrap();
+    let mut options = RenderOptions::default();
+    config.apply(&mut options);
+    let module = |prefix: &str, file: &str| {
+        let options = RenderOptions::builder().module_prefix(prefix).build();
+        parse_module("", Path::new(file), &options).unwrap()
+    };
+
+    assert_eq!(
+        options.page_title(&module("zenml", "client.py")),
+        "The ZenML client"
+    );
+    assert_eq!(
+        options.page_title(&module("zenml", "base_orchestrator.py")),
+        "Base Orchestrator"
+    );
+    assert_eq!(
+        options.page_title(&module("zenml.model_registries", "__init__.py")),
+        "Model Registries"
+    );
+    let mut titled = module("zenml", "client.py");
+    titled.title = Some("Client".to_string());
+    assert_eq!(options.page_title(&titled), "Client");
+    assert_eq!(
+        RenderOptions::default().page_title(&module("zenml", "base_orchestrator.py")),
+        "base_orchestrator"
+    );
+    assert_eq!(
+        RenderOptions::default().page_title(&module("zenml.model_registries", "__init__.py")),
+        "model_registries"
+    );
+}
+
+#[test]
+fn rejects_invalid_only_patterns() {
+    let err = Config::from_toml("only = \"Client(\"\n").unwrap_err();
+    assert!(err.to_string().contains("unclosed group"), "{}", err);
+}
+
+#[test]
+fn round_trips_through_toml() {
+    let config = Config::from_toml(
+        r#"
+file = ["src"]
+dunders = "init-only"
+only = "^Client$"
+timing = true
+
+[markers]
+internal = "exclude"
+beta = { badge = "beta", note = "Feedback welcome." }
+
+[frontmatter]
+icon = "code"
+
+[modules."zenml.client"]
+title = "The ZenML client"
+
+[modules."zenml.client".frontmatter]
+sidebarTitle = "Client"
+"#,
+    )
+    .unwrap();
+    assert_eq!(Config::from_toml(&config.to_toml()).unwrap(), config);
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use sdkdocgen::{coverage, parse_module, Category, RenderOptions, SymbolKind};
+
+const SOURCE: &str = r#"
+"""Module docstring."""
+
+
+class Stack:
+    """A stack."""
+
+    def __init__(self, name: str, size: int) -> None:
+        """Create it.
+
+        Args:
+            name: The name.
+        """
+
+    @staticmethod
+    def build(config) -> "Stack":
+        pass
+
+    def describe(self) -> str:
+        """Describe.
+
+        Returns:
+            The description.
+        """
+
+
+class Undocumented:
+    pass
+
+
+def helper(cls, value) -> int:
+    """Help."""
+"#;
+
+#[test]
+fn counts_each_symbol_kind() {
+    let module = parse_module(SOURCE, Path::new("stacks.py"), &RenderOptions::default()).unwrap();
+    let report = coverage(&module, &RenderOptions::default());
+
+    let counts = |kind| {
+        let counts = report.counts(kind);
+        (counts.documented, counts.total)
+    };
+    assert_eq!(counts(SymbolKind::Module), (1, 1));
+    assert_eq!(counts(SymbolKind::Class), (1, 2));
+    assert_eq!(counts(SymbolKind::Method), (2, 3));
+    assert_eq!(counts(SymbolKind::Function), (1, 1));
+    // `self` is skipped, but a staticmethod's first parameter and a
+    // module-level `cls` parameter are real parameters.
+    assert_eq!(counts(SymbolKind::Parameter), (1, 5));
+    // `-> None` has no return value to document.
+    assert_eq!(counts(SymbolKind::Return), (1, 3));
+
+    let total = report.total();
+    assert_eq!((total.documented, total.total), (7, 15));
+    assert!((total.percentage() - 46.666).abs() < 0.01);
+}
+
+#[test]
+fn lists_undocumented_symbols_with_lines() {
+    let module = parse_module(SOURCE, Path::new("stacks.py"), &RenderOptions::default()).unwrap();
+    let report = coverage(&module, &RenderOptions::default());
+
+    let undocumented: Vec<(&str, usize)> = report
+        .undocumented()
+        .map(|symbol| (symbol.symbol.as_str(), symbol.line))
+        .collect();
+    assert_eq!(
+        undocumented,
+        [
+            ("Stack.__init__(size)", 8),
+            ("Stack.build", 16),
+            ("Stack.build(config)", 16),
+            ("Stack.build()", 16),
+            ("Undocumented", 27),
+            ("helper(cls)", 31),
+            ("helper(value)", 31),
+            ("helper()", 31),
+        ]
+    );
+}
+
+#[test]
+fn private_members_follow_the_render_filter() {
+    let source = r#"
+class Stack:
+    def _validate(self):
+        pass
+
+    def __init__(self):
+        pass
+
+
+def _helper():
+    pass
+"#;
+    let module = parse_module(source, Path::new("stacks.py"), &RenderOptions::default()).unwrap();
+    let symbols = |options: &RenderOptions| -> Vec<String> {
+        coverage(&module, options)
+            .symbols
+            .into_iter()
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Method | SymbolKind::Function))
+            .map(|symbol| symbol.symbol)
+            .collect()
+    };
+
+    assert_eq!(symbols(&RenderOptions::default()), ["Stack.__init__"]);
+    assert_eq!(
+        symbols(&RenderOptions::builder().include_private(true).build()),
+        ["Stack._validate", "Stack.__init__", "_helper"]
+    );
+}
+
+#[test]
+fn dunder_all_limits_the_counted_symbols() {
+    let source = r#"
+__all__ = ["Stack", "_helper", "missing"]
+
+
+class Stack:
+    pass
+
+
+class Internal:
+    pass
+
+
+def _helper():
+    pass
+"#;
+    let module = parse_module(source, Path::new("stacks.py"), &RenderOptions::default()).unwrap();
+    let symbols = |options: &RenderOptions| -> Vec<String> {
+        coverage(&module, options)
+            .symbols
+            .into_iter()
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Class | SymbolKind::Function))
+            .map(|symbol| symbol.symbol)
+            .collect()
+    };
+
+    assert_eq!(symbols(&RenderOptions::default()), ["Stack", "_helper"]);
+    assert_eq!(
+        symbols(&RenderOptions::builder().respect_all(false).build()),
+        ["Stack", "Internal"]
+    );
+
+    let warnings: Vec<(usize, &str)> = module
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.category == Category::UnresolvedReference)
+        .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(
+        warnings,
+        [(
+            2,
+            "`__all__` lists `missing`, which the module does not define"
+        )]
+    );
+}
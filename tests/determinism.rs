@@ -0,0 +1,76 @@
+//! Generating the same sources twice, or concurrently, must produce
+//! byte-identical pages.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use sdkdocgen::{normalize_path, parse_module, render_module, Project, RenderOptions};
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "py"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn generate(path: &Path) -> String {
+    let options = RenderOptions::default();
+    let source = fs::read_to_string(path).unwrap();
+    let module = parse_module(&source, Path::new(path.file_name().unwrap()), &options).unwrap();
+    let project = Project::new(vec![module]);
+    render_module(&project.modules()[0], &project, &options)
+}
+
+#[test]
+fn repeated_runs_are_identical() {
+    let fixtures = fixtures();
+    let first: Vec<String> = fixtures.iter().map(|path| generate(path)).collect();
+    let second: Vec<String> = fixtures.iter().map(|path| generate(path)).collect();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn concurrent_runs_match_sequential_runs() {
+    let fixtures = fixtures();
+    let sequential: Vec<String> = fixtures.iter().map(|path| generate(path)).collect();
+
+    for threads in [2, 4] {
+        let chunk_size = fixtures.len().div_ceil(threads);
+        let concurrent: Vec<String> = thread::scope(|scope| {
+            let handles: Vec<_> = fixtures
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || chunk.iter().map(|p| generate(p)).collect::<Vec<_>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert_eq!(
+            sequential, concurrent,
+            "output differs with {} threads",
+            threads
+        );
+    }
+}
+
+#[test]
+fn no_timestamp_by_default() {
+    for path in fixtures() {
+        assert!(!generate(&path).contains("generated:"));
+    }
+}
+
+#[test]
+fn paths_use_forward_slashes() {
+    let path: PathBuf = ["pkg", "sub", "module.py"].iter().collect();
+    assert_eq!(normalize_path(&path), "pkg/sub/module.py");
+    assert_eq!(normalize_path(Path::new("./a/../b.py")), "./a/../b.py");
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use sdkdocgen::{
+    diff_api, dotted_module_name, parse_module, render_changelog, ChangeKind, ModuleDoc,
+    RenderOptions,
+};
+
+fn module(path: &str, package: &str, source: &str) -> ModuleDoc {
+    let options = RenderOptions::builder().module_prefix(package).build();
+    parse_module(source, Path::new(path), &options).unwrap()
+}
+
+const OLD: &str = r#"
+class Client:
+    def create(self, name: str, size=1) -> int:
+        pass
+
+    def gone(self):
+        pass
+
+
+def helper(x):
+    pass
+"#;
+
+const NEW: &str = r#"
+class Client:
+    def create(self, name: str, force: bool, size=2) -> str:
+        """Create.
+
+        .. deprecated:: 1.0
+        """
+
+    def fresh(self):
+        pass
+
+
+class Other:
+    def method(self):
+        pass
+"#;
+
+#[test]
+fn reports_added_removed_changed_and_deprecated_symbols() {
+    // The module moved into a package but kept its import path.
+    let old = [module("client.py", "zenml", OLD)];
+    let new = [module("client/__init__.py", "zenml.client", NEW)];
+    let changes: Vec<(ChangeKind, String, Option<String>)> =
+        diff_api(&old, &new, &RenderOptions::default())
+            .into_iter()
+            .map(|change| (change.kind, change.symbol, change.detail))
+            .collect();
+
+    let changed = |detail: &str| {
+        (
+            ChangeKind::Changed,
+            "zenml.client.Client.create".to_string(),
+            Some(detail.to_string()),
+        )
+    };
+    assert_eq!(
+        changes,
+        [
+            changed("required parameter `force` added"),
+            changed("default of `size` changed from `1` to `2`"),
+            changed("return type changed from `int` to `str`"),
+            (
+                ChangeKind::Deprecated,
+                "zenml.client.Client.create".to_string(),
+                Some("deprecated".to_string())
+            ),
+            (
+                ChangeKind::Added,
+                "zenml.client.Client.fresh".to_string(),
+                None
+            ),
+            (
+                ChangeKind::Removed,
+                "zenml.client.Client.gone".to_string(),
+                None
+            ),
+            // `Other.method` is covered by the new class.
+            (ChangeKind::Added, "zenml.client.Other".to_string(), None),
+            (ChangeKind::Removed, "zenml.client.helper".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn moving_a_function_does_not_change_its_defaults() {
+    let old = [module("client.py", "zenml", "def f(x=1):\n    pass\n")];
+    let new = [module(
+        "client.py",
+        "zenml",
+        "\n\n\ndef f(x=1):\n    pass\n",
+    )];
+    assert_eq!(diff_api(&old, &new, &RenderOptions::default()), []);
+}
+
+#[test]
+fn renders_a_changelog_section() {
+    let old = [module("client.py", "zenml", OLD)];
+    let new = [module("client.py", "zenml", NEW)];
+    let changelog = render_changelog(&diff_api(&old, &new, &RenderOptions::default()));
+    assert!(changelog
+        .starts_with("## API changes\n\n### Added\n\n- `zenml.client.Client.fresh` (method)\n"));
+    assert!(changelog.contains(
+        "\n### Changed\n\n- `zenml.client.Client.create`: required parameter `force` added\n"
+    ));
+    assert_eq!(
+        render_changelog(&[]),
+        "## API changes\n\nNo changes to the public API.\n"
+    );
+}
+
+#[test]
+fn names_modules_by_relative_path() {
+    assert_eq!(
+        dotted_module_name(Path::new("client/base.py")),
+        "client.base"
+    );
+    assert_eq!(
+        dotted_module_name(Path::new("client/__init__.py")),
+        "client.__init__"
+    );
+    assert_eq!(dotted_module_name(Path::new("utils.py")), "utils");
+}
+
+#[test]
+fn carries_version_markers_of_changed_symbols() {
+    let old = [module("client.py", "zenml", "def helper(x):\n    pass\n")];
+    let new = [module(
+        "client.py",
+        "zenml",
+        "def helper(x, y):\n    \"\"\"Help.\n\n    .. versionchanged:: 1.2: takes `y`.\n    \"\"\"\n",
+    )];
+    let changes = diff_api(&old, &new, &RenderOptions::default());
+    let json = serde_json::to_value(&changes).unwrap();
+    assert_eq!(
+        json[0]["versions"],
+        serde_json::json!([{"kind": "changed", "version": "1.2", "note": "takes `y`."}])
+    );
+}
@@ -0,0 +1,110 @@
+use sdkdocgen::docstring::{
+    clean_docstring, extract_version_markers, parse_docstring, VersionKind,
+};
+
+#[test]
+fn cleans_indentation_like_inspect_cleandoc() {
+    let raw = "Summary line.\n\n        More text.\n            Indented.\n    ";
+    assert_eq!(
+        clean_docstring(raw),
+        "Summary line.\n\nMore text.\n    Indented."
+    );
+}
+
+#[test]
+fn parses_google_sections() {
+    let docstring = parse_docstring(
+        "Create a stack.
+
+        The stack is registered immediately.
+
+        Args:
+            name: Name of the stack.
+            components (Dict[str, str]): Mapping of component type
+                to component name.
+            **kwargs: Extra options.
+
+        Returns:
+            Stack: The new stack.
+
+        Raises:
+            ValueError: If the name is taken.
+
+        Examples:
+            >>> create('dev')
+        ",
+    );
+    assert_eq!(docstring.summary, "Create a stack.");
+    assert_eq!(
+        docstring.description,
+        "The stack is registered immediately."
+    );
+
+    let names: Vec<&str> = docstring.params.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["name", "components", "**kwargs"]);
+    assert_eq!(
+        docstring.params[1].type_name.as_deref(),
+        Some("Dict[str, str]")
+    );
+    assert_eq!(
+        docstring.params[1].description,
+        "Mapping of component type to component name."
+    );
+
+    let returns = docstring.returns.unwrap();
+    assert_eq!(returns.type_name.as_deref(), Some("Stack"));
+    assert_eq!(returns.description, "The new stack.");
+
+    assert_eq!(docstring.raises[0].name, "ValueError");
+    assert_eq!(docstring.sections[0].title, "Examples");
+    assert_eq!(docstring.sections[0].content, ">>> create('dev')");
+}
+
+#[test]
+fn returns_without_a_type() {
+    let docstring = parse_docstring("Get it.\n\n    Returns:\n        The URL: with a colon.\n");
+    let returns = docstring.returns.unwrap();
+    assert_eq!(returns.type_name, None);
+    assert_eq!(returns.description, "The URL: with a colon.");
+}
+
+#[test]
+fn text_after_a_section_is_description() {
+    let docstring = parse_docstring("Summary.\n\nArgs:\n    x: The x.\nTrailing text.");
+    assert_eq!(docstring.params.len(), 1);
+    assert_eq!(docstring.description, "Trailing text.");
+}
+
+#[test]
+fn takes_version_markers_out_in_version_order() {
+    let (markers, rest) = extract_version_markers(
+        "Create a stack.
+
+        .. versionchanged:: 0.10.0: renamed from
+            `register_stack`.
+        .. versionadded:: 0.9.1
+        Changed in version: 0.10.0, accepts components.
+        ",
+    );
+    let markers: Vec<(VersionKind, &str, Option<&str>)> = markers
+        .iter()
+        .map(|marker| (marker.kind, marker.version.as_str(), marker.note.as_deref()))
+        .collect();
+    assert_eq!(
+        markers,
+        [
+            (VersionKind::Added, "0.9.1", None),
+            (
+                VersionKind::Changed,
+                "0.10.0",
+                Some("renamed from `register_stack`.")
+            ),
+            (VersionKind::Changed, "0.10.0", Some("accepts components.")),
+        ]
+    );
+    assert_eq!(rest, "Create a stack.");
+    assert_eq!(
+        extract_version_markers("No markers.\n"),
+        (Vec::new(), "No markers.\n".to_string())
+    );
+}
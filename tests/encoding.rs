@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{coding_declaration, decode_source, parse_module, read_source, RenderOptions};
+
+fn write(name: &str, bytes: &[u8]) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("encoding");
+    fs::create_dir_all(&root).unwrap();
+    let path = root.join(name);
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn decodes_latin1_with_a_coding_cookie() {
+    let path = write(
+        "legacy.py",
+        b"# -*- coding: latin-1 -*-\ndef greet():\n    \"\"\"Says gr\xFC\xDF Gott.\"\"\"\n",
+    );
+    let source = read_source(&path).unwrap();
+    assert!(source.contains("Says grüß Gott."), "{}", source);
+
+    let module = parse_module(&source, &path, &RenderOptions::default()).unwrap();
+    assert_eq!(module.items[0].name(), "greet");
+}
+
+#[test]
+fn strips_a_byte_order_mark() {
+    let path = write("bom.py", b"\xEF\xBB\xBF\"\"\"Caf\xC3\xA9 helpers.\"\"\"\n");
+    let source = read_source(&path).unwrap();
+    assert_eq!(source, "\"\"\"Café helpers.\"\"\"\n");
+
+    let module = parse_module(&source, &path, &RenderOptions::default()).unwrap();
+    assert_eq!(module.docstring.as_deref(), Some("Café helpers."));
+}
+
+#[test]
+fn finds_the_declaration_on_the_first_two_lines() {
+    assert_eq!(
+        coding_declaration(b"#!/usr/bin/env python\n# vim: set fileencoding=cp1252 :\n"),
+        Some("cp1252".to_string())
+    );
+    assert_eq!(
+        coding_declaration(b"# coding=utf-8\n"),
+        Some("utf-8".to_string())
+    );
+    assert_eq!(coding_declaration(b"import os\n# coding: latin-1\n"), None);
+    assert_eq!(coding_declaration(b"x = 'coding: latin-1'\n"), None);
+}
+
+#[test]
+fn replaces_invalid_bytes_instead_of_failing() {
+    let path = Path::new("broken.py");
+    assert_eq!(decode_source(b"x = '\xFF'\n", path), "x = '\u{FFFD}'\n");
+    assert_eq!(
+        decode_source(b"# coding: cp1252\nx = '\x93quoted\x94'\n", path),
+        "# coding: cp1252\nx = '\u{201C}quoted\u{201D}'\n"
+    );
+    assert_eq!(
+        decode_source(b"# coding: klingon\nx = 1\n", path),
+        "# coding: klingon\nx = 1\n"
+    );
+}
@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use sdkdocgen::{
+    attach_examples, parse_module, render_module, Examples, Item, ModuleDoc, Project, RenderOptions,
+};
+
+const STORE: &str = r#"
+class Store:
+    """A key-value store."""
+
+    def get(self, key, default=None):
+        """Fetch a value."""
+
+    def put(self, key, value):
+        """Store a value.
+
+        Example:
+            store.put("key", "value")
+        """
+
+    def delete(self, key):
+        """Remove a value."""
+
+
+def open_store(url):
+    """Open a store."""
+"#;
+
+fn store_module() -> ModuleDoc {
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let mut module = parse_module(STORE, Path::new("store.py"), &options).unwrap();
+    attach_examples(
+        &mut module,
+        &Examples::collect(Path::new("tests/fixtures/examples")),
+    );
+    module
+}
+
+fn example<'a>(module: &'a ModuleDoc, path: &str) -> Option<&'a str> {
+    let (item_name, method_name) = match path.split_once('.') {
+        Some((class, method)) => (class, Some(method)),
+        None => (path, None),
+    };
+    let function = module
+        .items
+        .iter()
+        .find_map(|item| match (item, method_name) {
+            (Item::Class(class), Some(method)) if class.name == item_name => {
+                class.methods.iter().find(|m| m.name == method)
+            }
+            (Item::Function(function), None) if function.name == item_name => Some(function),
+            _ => None,
+        })?;
+    function.example.as_deref()
+}
+
+#[test]
+fn picks_the_shortest_test_calling_each_symbol() {
+    let module = store_module();
+
+    // Both plain tests call `get`; the decorated one is shorter and is shown
+    // without its decorator.
+    assert_eq!(
+        example(&module, "Store.get"),
+        Some(
+            "def test_get_returns_default():\n    store = Store()\n    assert store.get(\"missing\", default=1) == 1\n"
+        )
+    );
+    // Called through a module alias.
+    assert!(example(&module, "open_store")
+        .is_some_and(|code| code.starts_with("def test_open_store():\n")));
+}
+
+#[test]
+fn skips_documented_examples_fixtures_and_shadowed_names() {
+    let module = store_module();
+
+    // `put` has an `Example:` section of its own.
+    assert_eq!(example(&module, "Store.put"), None);
+    // `delete` is only called on a local class named `Store` and from a
+    // file that is not a test module.
+    assert_eq!(example(&module, "Store.delete"), None);
+}
+
+#[test]
+fn renders_the_example_under_its_own_heading() {
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let project = Project::new(vec![store_module()]);
+    let page = render_module(&project.modules()[0], &project, &options);
+
+    assert!(page
+        .contains("**Example:**\n\n```py\ndef test_get_returns_default():\n    store = Store()\n"));
+    assert!(!page.contains("@pytest.mark.slow"));
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn sdkdocgen(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sdkdocgen"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn write(name: &str, source: &str) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("exit_codes");
+    fs::create_dir_all(&root).unwrap();
+    let path = root.join(name);
+    fs::write(&path, source).unwrap();
+    path
+}
+
+#[test]
+fn succeeds_on_a_valid_file() {
+    let path = write("valid.py", "def greet():\n    \"\"\"Says hello.\"\"\"\n");
+    let output = sdkdocgen(&["--file", path.to_str().unwrap(), "--stdout"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn exits_with_3_when_a_file_cannot_be_read() {
+    let output = sdkdocgen(&["--file", "does/not/exist.py", "--stdout"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn exits_with_2_when_a_file_fails_to_parse() {
+    let path = write("broken.py", "def broken(:\n");
+    let output = sdkdocgen(&["--file", path.to_str().unwrap(), "--stdout"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn exits_with_1_on_invalid_arguments() {
+    let output = sdkdocgen(&["--file", "module.py", "--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = sdkdocgen(&["--stdin", "--file", "module.py"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn exits_with_0_for_help() {
+    let output = sdkdocgen(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Exit codes:"));
+}
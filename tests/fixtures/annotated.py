@@ -0,0 +1,14 @@
+from typing import Annotated, Literal
+
+from pydantic import Field
+
+
+class Exporter:
+    """Exports pipeline runs."""
+
+    def export(
+        self,
+        fmt: Literal["json", "yaml"],
+        limit: Annotated[int, Field(gt=0)],
+    ) -> None:
+        """Exports the runs."""
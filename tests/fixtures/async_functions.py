@@ -0,0 +1,18 @@
+"""Fixture module containing coroutines."""
+
+
+async def fetch(url: str, timeout: float = 10.0) -> bytes:
+    """Fetch the content behind a URL."""
+    pass
+
+
+class Client:
+    """An asynchronous API client."""
+
+    async def connect(self) -> None:
+        """Open the connection."""
+        pass
+
+    def close(self) -> None:
+        """Close the connection."""
+        pass
@@ -0,0 +1,26 @@
+"""Fixture module with class attributes."""
+
+from typing import ClassVar, Optional
+
+
+class Orchestrator:
+    """Runs pipelines."""
+
+    flavor: ClassVar[str] = "airflow"
+    """Name of the flavor."""
+
+    name: str
+    """Name of the orchestrator."""
+
+    timeout: int = 30
+    """Seconds to wait for a step
+    before giving up."""
+
+    retries = 3
+    parent: Optional["Orchestrator"] = None
+    a, b = 1, 2
+    _cache: dict = {}
+    __slots__ = ("name",)
+
+    def run(self) -> None:
+        """Runs the pipeline."""
@@ -0,0 +1,45 @@
+"""Fixture module with attrs classes in the old and new spellings."""
+
+from typing import ClassVar, Dict, List, Optional
+
+import attr
+import attrs
+
+
+@attr.s
+class Connection:
+    """A connection using the old `attr.s` API."""
+
+    host = attr.ib(type=str)
+    port: int = attr.ib(default=8080)
+    """Port the server listens on."""
+    options = attr.ib(factory=dict)
+    retries: int = 3
+    _session = attr.ib(init=False, default=None)
+
+    def __attrs_post_init__(self):
+        self._session = None
+
+
+@attrs.define(frozen=True, kw_only=True)
+class Credentials:
+    """Credentials using the new `attrs.define` API."""
+
+    username: str
+    """Name of the user."""
+    token: Optional[str] = None
+    scopes: List[str] = attrs.field(factory=list)
+    headers: Dict[str, str] = attrs.field(default=attrs.Factory(dict))
+    registry: ClassVar[Dict[str, "Credentials"]] = {}
+
+    def describe(self) -> str:
+        """Describe the credentials."""
+        return self.username
+
+
+@attrs.frozen
+class Version:
+    """An immutable version."""
+
+    major: int
+    minor: int = 0
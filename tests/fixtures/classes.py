@@ -0,0 +1,39 @@
+"""Fixture module with plain classes and methods."""
+
+from typing import Dict, List, Optional
+
+
+class Stack:
+    """A collection of stack components."""
+
+    def __init__(self, name: str, components: Dict[str, str]) -> None:
+        """Create a stack.
+
+        Args:
+            name: Name of the stack.
+            components: Mapping of component type to component name.
+        """
+        self.name = name
+        self.components = components
+
+    @classmethod
+    def from_names(cls, names: List[str]) -> "Stack":
+        """Build a stack out of component names."""
+        return cls(names[0], {})
+
+    def describe(self, verbose=False):
+        """Describe the stack."""
+        if verbose:
+            return self.name
+        return self.name
+
+    def _validate(self) -> bool:
+        return True
+
+
+def get_stack(name: Optional[str] = None) -> Stack:
+    """Look up a stack by name.
+
+    Falls back to the active stack when no name is given.
+    """
+    pass
@@ -0,0 +1,19 @@
+import argparse
+
+
+def build_parser():
+    """Build the parser."""
+    parser = argparse.ArgumentParser(prog="stores", description="Manage stores.")
+    parser.add_argument("--config", help="Config file.", default="stores.toml")
+    parser.add_argument("--debug", action="store_true", help=argparse.SUPPRESS)
+    subparsers = parser.add_subparsers(dest="command")
+
+    get = subparsers.add_parser("get", help="Print a value.")
+    get.add_argument("key", help="Key to look up.")
+    get.add_argument("--retries", type=int, default=3, help="Retries | attempts.")
+
+    listing = subparsers.add_parser("list", description="List keys.")
+    options = listing.add_mutually_exclusive_group()
+    options.add_argument("--all", "-a", action="store_true", help="Include hidden keys.")
+    listing.add_argument("prefix", nargs="?")
+    return parser
@@ -0,0 +1,41 @@
+"""Command-line interface of the store."""
+
+import click
+
+
+@click.group()
+@click.option("--verbose", "-v", is_flag=True, help="Print more output.")
+def cli(verbose):
+    """Manage stores.
+
+    Every command talks to the configured store.
+    """
+
+
+@cli.command()
+@click.argument("key")
+@click.option("--default", "-d", default="", help="Value printed when KEY is missing.")
+@click.option("--timeout", type=click.IntRange(0, 60), default=10)
+@click.option("--debug-only", hidden=True)
+def get_value(key, default, timeout, debug_only):
+    """Print a value.
+
+    \f
+    Args:
+        key: Internal documentation Click does not show.
+    """
+
+
+@click.command(name="put")
+@click.argument("pairs", nargs=-1)
+@click.option("--overwrite/--no-overwrite", help="Replace existing values.")
+@click.option("--store", required=True, help="Store URL.")
+def put_values(pairs, overwrite, store):
+    """Store values."""
+
+
+cli.add_command(put_values)
+
+
+def helper():
+    """Not a command."""
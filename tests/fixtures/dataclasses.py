@@ -0,0 +1,34 @@
+"""Fixture module with dataclasses."""
+
+import dataclasses
+from dataclasses import dataclass, field
+from typing import List
+
+
+@dataclass
+class Settings:
+    """Settings for a pipeline run."""
+
+    name: str
+    retries: int = 3
+    tags: List[str] = field(default_factory=list)
+
+    def summary(self) -> str:
+        """Summarize the settings."""
+        return self.name
+
+
+@dataclass(frozen=True)
+class Point:
+    """An immutable point."""
+
+    x: float
+    y: float
+
+
+@dataclasses.dataclass(frozen=True, kw_only=True)
+class Credentials:
+    """Credentials passed by keyword."""
+
+    username: str
+    scopes: List[str] = field(default_factory=list)
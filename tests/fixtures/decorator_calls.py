@@ -0,0 +1,23 @@
+import functools
+
+import pydantic
+
+from zenml import api
+
+
+class Settings(pydantic.BaseModel):
+    """Settings of a stack."""
+
+    @pydantic.validator("name", "flavor", pre=True)
+    @classmethod
+    def check_name(cls, value: str) -> str:
+        """Checks a name."""
+        return value
+
+    @functools.lru_cache(maxsize=None)
+    def resolve(self) -> str:
+        """Resolves the settings."""
+
+    @api.experimental(reason="Still settling.")
+    def preview(self) -> str:
+        """Previews the settings."""
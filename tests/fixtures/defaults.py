@@ -0,0 +1,25 @@
+import os
+from enum import Enum
+
+
+class Mode(Enum):
+    FAST = "fast"
+
+
+def configure(
+    mode: str = 'auto',
+    quote: str = "it's",
+    retries: int = -3,
+    ratio: float = 0.5,
+    data: bytes = b"\x00ok",
+    path: str = os.path.join("a", "b"),
+    kind: Mode = Mode.FAST,
+    timeout: int = 60 * 60,
+    tags: tuple = ("a",),
+    options: dict = {"level": 2, **os.environ},
+    names: set = {"x", "y"},
+    label: str = f"{os.sep}run-{Mode.FAST!r:>10}",
+    key=lambda item: item,
+    flag: bool = not True,
+) -> None:
+    """Configures the run."""
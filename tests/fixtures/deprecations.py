@@ -0,0 +1,32 @@
+"""Deprecated classes, functions and methods."""
+
+import warnings
+
+from typing_extensions import deprecated
+
+from acme.compat import legacy
+
+
+@deprecated("Use `get_client` instead.")
+def client() -> None:
+    """Returns the client."""
+
+
+def get_client() -> None:
+    """Returns the active client."""
+
+
+@legacy
+class OldStack:
+    """A stack of the old kind."""
+
+
+class Stack:
+    """A stack of components."""
+
+    @warnings.deprecated(reason="Pass the flavor to `register` instead.")
+    def set_flavor(self, flavor: str) -> None:
+        """Sets the flavor."""
+
+    def register(self) -> None:
+        """Registers the stack."""
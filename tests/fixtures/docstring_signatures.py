@@ -0,0 +1,36 @@
+"""Functions whose signature is hidden by a decorator."""
+
+import functools
+
+from acme.models import Run, Stack
+
+
+def step(func):
+    """Turns a function into a pipeline step."""
+
+    @functools.wraps(func)
+    def wrapper(*args, **kwargs):
+        return func(*args, **kwargs)
+
+    return wrapper
+
+
+@step
+def run(*args, **kwargs):
+    """run(pipeline_name: str, stack: Stack = None) -> Run
+
+    Runs a pipeline on a stack.
+
+    Args:
+        pipeline_name: Name of the pipeline.
+    """
+
+
+@step
+def stop(*args, **kwargs):
+    """stop(run: Run, force: bool = False"""
+
+
+@step
+def describe(*args, **kwargs):
+    """Describes the pipeline."""
@@ -0,0 +1,32 @@
+"""Fixture module with an `__all__` naming its public API."""
+
+from typing import List
+
+__all__ = ["Pipeline", "run", "_bootstrap", "StepList", "missing"]
+
+StepList = List[str]
+
+RunResult = List[int]
+
+
+class Pipeline:
+    """A pipeline listed in `__all__`."""
+
+    def run(self) -> None:
+        """Run the pipeline."""
+
+
+class Helper:
+    """A public class left out of `__all__`."""
+
+
+def run(pipeline: Pipeline) -> None:
+    """Run a pipeline."""
+
+
+def _bootstrap() -> None:
+    """A private function listed in `__all__`."""
+
+
+def configure() -> None:
+    """A public function left out of `__all__`."""
@@ -0,0 +1,27 @@
+"""Fixture module with dunder methods."""
+
+
+class Artifact:
+    """A stored artifact."""
+
+    def __init__(self, uri: str, version: int = 1) -> None:
+        """Create an artifact.
+
+        Args:
+            uri: Where the artifact is stored.
+            version: Version of the artifact.
+        """
+        self.uri = uri
+        self.version = version
+
+    def __repr__(self) -> str:
+        """Show the artifact's URI."""
+        return f"Artifact({self.uri!r})"
+
+    def __eq__(self, other: object) -> bool:
+        """Compare two artifacts by URI."""
+        return isinstance(other, Artifact) and other.uri == self.uri
+
+    def load(self) -> bytes:
+        """Load the artifact's contents."""
+        return b""
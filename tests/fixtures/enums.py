@@ -0,0 +1,39 @@
+"""Fixture module with enums."""
+
+import enum
+from enum import Enum, IntEnum, auto
+
+
+class ExecutionStatus(str, Enum):
+    """Status of a pipeline run."""
+
+    INITIALIZING = "initializing"
+    """The run is being set up."""
+
+    RUNNING = "running"
+    COMPLETED = "completed"
+    _ignore_ = ["internal"]
+
+    @property
+    def is_finished(self) -> bool:
+        """Whether the run has finished."""
+        return self == ExecutionStatus.COMPLETED
+
+    def describe(self) -> str:
+        """Describes the status."""
+        return self.value
+
+
+class Priority(IntEnum):
+    """Scheduling priority."""
+
+    LOW = auto()
+    HIGH = auto()
+
+
+class Color(enum.Flag):
+    """Colors that combine."""
+
+    RED = 1
+    BLUE = 1 << 1
+    PURPLE = RED | BLUE
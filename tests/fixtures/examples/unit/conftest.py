@@ -0,0 +1,5 @@
+from zenml.store import Store
+
+
+def test_in_conftest_is_ignored():
+    Store().delete("key")
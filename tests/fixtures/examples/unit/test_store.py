@@ -0,0 +1,39 @@
+import pytest
+
+from zenml.store import Store, open_store
+from zenml import store as store_module
+
+
+def helper():
+    return Store()
+
+
+@pytest.mark.slow
+def test_get_returns_default():
+    store = Store()
+    assert store.get("missing", default=1) == 1
+
+
+def test_get_after_put_roundtrips():
+    store = Store()
+    store.put("key", "value")
+    value = store.get("key")
+    assert value == "value"
+
+
+def test_put_with_fixture(tmp_path):
+    Store().put("key", tmp_path)
+
+
+def test_open_store():
+    with store_module.open_store("memory") as opened:
+        opened.put("a", 1)
+        assert opened.get("a") == 1
+
+
+def test_local_name_is_not_the_store():
+    class Store:
+        def delete(self, key):
+            pass
+
+    Store().delete("key")
@@ -0,0 +1,42 @@
+"""Generic repositories."""
+
+from typing import Generic, Protocol, TypeVar
+
+from pydantic import BaseModel
+
+T = TypeVar("T", bound=BaseModel)
+AnyStr = TypeVar("AnyStr", str, bytes)
+K = TypeVar("K")
+
+
+class Repository(Generic[T]):
+    """Stores models of one type."""
+
+    def get(self, key: str) -> T:
+        """Loads a model."""
+        ...
+
+
+class Codec(Protocol[AnyStr, K]):
+    """Encodes keys."""
+
+    def encode(self, key: K) -> AnyStr:
+        """Encodes a key."""
+        ...
+
+
+class Cache[V: BaseModel, S: (str, bytes), *Ts, **P]:
+    """A cache declared with type parameter syntax."""
+
+
+def first[T](items: list[T]) -> T:
+    """Returns the first item."""
+    return items[0]
+
+
+class Pool:
+    """Hands out connections."""
+
+    def acquire[C: Connection](self, kind: type[C]) -> C:
+        """Opens a connection of `kind`."""
+        ...
@@ -0,0 +1,7 @@
+def configure(retries=3, offset=-1.5, name="prod", debug=True, tags=[], extra={}, token=None, size: int = 4, factor=2 * 2):
+    """Configure the client.
+
+    Args:
+        retries: How often to retry.
+        name (Optional[str]): Environment name.
+    """
@@ -0,0 +1,34 @@
+"""Stack components with a small class hierarchy."""
+
+from typing import Generic, TypeVar
+
+from pydantic import BaseModel
+
+T = TypeVar("T")
+
+
+class StackComponent(BaseModel):
+    """Base class for all stack components."""
+
+    def validate(self) -> None:
+        """Checks the component configuration."""
+
+
+class BaseOrchestrator(StackComponent):
+    """Base class for orchestrators."""
+
+
+class LocalOrchestrator(BaseOrchestrator):
+    """Runs pipelines on the local machine."""
+
+
+class KubernetesOrchestrator(BaseOrchestrator, Generic[T]):
+    """Runs pipelines on a Kubernetes cluster."""
+
+
+class _PrivateOrchestrator(BaseOrchestrator):
+    """Not documented."""
+
+
+class Standalone:
+    """Has no documented relatives, so gets no diagram."""
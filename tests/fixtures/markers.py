@@ -0,0 +1,46 @@
+"""Symbols carrying marker decorators and docstring tags."""
+
+
+def internal(func):
+    """Marks a function as internal."""
+    return func
+
+
+def experimental(reason=None):
+    """Marks a function as experimental."""
+    return lambda func: func
+
+
+def beta(func):
+    """Marks a function as beta."""
+    return func
+
+
+@internal
+def hidden_helper() -> None:
+    """Not part of the public API."""
+
+
+def tagged_helper() -> None:
+    """Not part of the public API either.
+
+    :internal:
+    """
+
+
+@experimental("new in this release")
+def preview(value: int) -> int:
+    """Preview a value."""
+    return value
+
+
+@beta
+class Pipeline:
+    """A pipeline."""
+
+    def run(self) -> None:
+        """Run the pipeline. :experimental:"""
+
+    @internal
+    def _reset(self) -> None:
+        """Reset internal state."""
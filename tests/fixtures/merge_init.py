@@ -0,0 +1,34 @@
+"""Classes whose constructor is documented with the class."""
+
+from dataclasses import dataclass
+from typing import Optional
+
+
+class Client:
+    """Talks to the server."""
+
+    def __init__(self, url: str, timeout: Optional[int] = None) -> None:
+        """Connects to the server at `url`."""
+        self.url = url
+
+    def close(self) -> None:
+        """Closes the connection."""
+
+
+class LocalClient(Client):
+    """Talks to a server on this machine."""
+
+
+class Token:
+    """An access token."""
+
+    def __new__(cls, value: str) -> "Token":
+        """Wraps a raw token."""
+        return super().__new__(cls)
+
+
+@dataclass
+class Settings:
+    """Client settings."""
+
+    url: str
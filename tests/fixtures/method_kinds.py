@@ -0,0 +1,32 @@
+import abc
+import typing
+from abc import ABC, abstractmethod
+
+
+class Flavor(ABC):
+    """A stack component flavor."""
+
+    @staticmethod
+    def validate(name: str) -> bool:
+        """Checks a flavor name."""
+
+    @classmethod
+    @abstractmethod
+    def from_config(cls, config: dict) -> "Flavor":
+        """Builds the flavor from its configuration."""
+
+    @abc.abstractmethod
+    def implementation(self) -> str:
+        """The import path of the implementation."""
+
+    @property
+    @abstractmethod
+    def config_schema(self) -> dict:
+        """The schema of the configuration."""
+
+    def describe(self) -> str:
+        """Describes the flavor."""
+
+
+class Registry(typing.Dict[str, Flavor], metaclass=abc.ABCMeta):
+    """Flavors by name."""
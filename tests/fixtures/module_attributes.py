@@ -0,0 +1,23 @@
+"""Fixture module with module-level variables."""
+
+import logging
+from typing import Final, List, TypeVar
+
+T = TypeVar("T")
+PathList = List[str]
+
+DEFAULT_TIMEOUT: int = 30
+"""Seconds to wait for a server."""
+
+STACK_COMPONENT_TYPES = ["orchestrator", "artifact_store"]
+MAX_RETRIES: Final = 3
+_INTERNAL_LIMIT = 10
+__version__ = "1.0.0"
+
+logger = logging.getLogger(__name__)
+default_client = None
+"""The client used when none is given."""
+
+
+def connect(timeout: int = DEFAULT_TIMEOUT) -> None:
+    """Connects to the server."""
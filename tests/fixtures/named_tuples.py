@@ -0,0 +1,24 @@
+"""Fixture module with named tuples."""
+
+import collections
+from typing import NamedTuple, Optional
+
+
+class Version(NamedTuple):
+    """A semantic version."""
+
+    major: int
+    """The major version."""
+
+    minor: int = 0
+    label: Optional[str] = None
+
+    def bump(self) -> "Version":
+        """Returns the next major version."""
+        return Version(self.major + 1)
+
+
+Point = collections.namedtuple("Point", "x, y z", defaults=[0])
+"""A point in space."""
+
+Range = NamedTuple("Range", [("start", int), ("end", int)])
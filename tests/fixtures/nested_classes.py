@@ -0,0 +1,52 @@
+"""Classes defined inside other classes."""
+
+from enum import Enum
+
+from pydantic import BaseModel
+
+
+class StepSettings(BaseModel):
+    """Settings of a pipeline step."""
+
+    retries: int = 0
+
+    class Config:
+        """Pydantic configuration."""
+
+        extra = "forbid"
+
+
+class Pipeline:
+    """A pipeline of steps."""
+
+    class Status(Enum):
+        """Where a run is at."""
+
+        RUNNING = "running"
+        DONE = "done"
+
+    class Run:
+        """A single run of the pipeline."""
+
+        class Step:
+            """A step of the run."""
+
+            class Log:
+                """The output of a step."""
+
+                class Line:
+                    """One line of output."""
+
+                    text: str = ""
+
+                def tail(self) -> "Pipeline.Run.Step.Log.Line":
+                    """Returns the last line."""
+
+        def status(self) -> "Pipeline.Status":
+            """Returns the status of the run."""
+
+    class _Cache:
+        """Not documented."""
+
+    def run(self) -> Run:
+        """Starts a run."""
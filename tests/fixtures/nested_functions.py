@@ -0,0 +1,48 @@
+"""Functions defined inside other functions."""
+
+import functools
+from typing import Callable, List
+
+
+def retry(func: Callable) -> Callable:
+    """Retries `func` once when it fails."""
+
+    @functools.wraps(func)
+    def wrapper(*args, **kwargs):
+        try:
+            return func(*args, **kwargs)
+        except Exception:
+            return func(*args, **kwargs)
+
+    return wrapper
+
+
+def build_steps(names: List[str]) -> List[str]:
+    """Builds a step for each name."""
+
+    def make_step(name: str) -> str:
+        """Builds one step."""
+
+        def _slug() -> str:
+            return name.lower()
+
+        return _slug()
+
+    async def validate(step: str) -> bool:
+        """Checks that a step can run."""
+        return True
+
+    return [make_step(name) for name in names]
+
+
+class Pipeline:
+    """A pipeline of steps."""
+
+    def run(self) -> None:
+        """Runs each step."""
+
+        def on_failure(error: Exception) -> None:
+            """Reports a failed step."""
+            raise RuntimeError(error)
+
+        pass
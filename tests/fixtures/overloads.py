@@ -0,0 +1,48 @@
+"""Functions with `@overload` signatures."""
+
+import typing
+from typing import List, Optional, Union, overload
+
+
+@overload
+def get_stack(name: str) -> "Stack": ...
+
+
+@overload
+def get_stack(name: None = None) -> Optional["Stack"]: ...
+
+
+def get_stack(name: Optional[str] = None) -> Optional["Stack"]:
+    """Fetches a stack by name, or the active one.
+
+    Args:
+        name: Name of the stack.
+    """
+    return None
+
+
+def get_stack_ids() -> List[str]:
+    """Lists the IDs of the stacks."""
+    return []
+
+
+@overload
+def parse_id(value: str) -> int: ...
+
+
+@overload
+def parse_id(value: bytes) -> int: ...
+
+
+class Stack:
+    """A stack of components."""
+
+    @typing.overload
+    def component(self, key: int) -> str: ...
+
+    @typing.overload
+    def component(self, key: str, *, default: str = "") -> str: ...
+
+    def component(self, key: Union[int, str], *, default: str = "") -> str:
+        """Looks up a component by position or name."""
+        return ""
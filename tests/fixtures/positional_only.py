@@ -0,0 +1,21 @@
+from typing import Any
+
+
+class Registry:
+    """A registry of stack components."""
+
+    def register(
+        self,
+        flavor: str,
+        name: str = None,
+        /,
+        config: dict = None,
+        *components: str,
+        replace: bool,
+        **labels: Any,
+    ) -> None:
+        """Registers a component."""
+
+
+def lookup(key: str, /) -> Any:
+    """Looks up a component."""
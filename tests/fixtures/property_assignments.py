@@ -0,0 +1,22 @@
+class Temperature:
+    """A temperature reading."""
+
+    def __init__(self, celsius: float):
+        self._celsius = celsius
+
+    def get_celsius(self) -> float:
+        """The temperature in degrees Celsius."""
+        return self._celsius
+
+    def set_celsius(self, value: float) -> None:
+        self._celsius = value
+
+    celsius = property(get_celsius, set_celsius)
+
+    def _kelvin(self) -> float:
+        return self._celsius + 273.15
+
+    kelvin = property(fget=_kelvin, doc="The temperature in Kelvin.")
+
+    def describe(self) -> str:
+        """Describe the reading."""
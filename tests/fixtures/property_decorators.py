@@ -0,0 +1,32 @@
+from functools import cached_property
+
+
+class Pipeline:
+    """A pipeline of steps."""
+
+    def __init__(self, name: str):
+        self._name = name
+
+    @property
+    def name(self) -> str:
+        """The name of the pipeline."""
+        return self._name
+
+    @name.setter
+    def name(self, value: str) -> None:
+        self._name = value
+
+    @name.deleter
+    def name(self) -> None:
+        self._name = None
+
+    @property
+    def steps(self) -> int:
+        """How many steps the pipeline has."""
+
+    @cached_property
+    def spec(self) -> dict:
+        """The compiled pipeline spec."""
+
+    def run(self) -> None:
+        """Runs the pipeline."""
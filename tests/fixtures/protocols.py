@@ -0,0 +1,26 @@
+"""Fixture module with protocols."""
+
+import typing
+from typing import Protocol, TypeVar, runtime_checkable
+
+T = TypeVar("T")
+
+
+@runtime_checkable
+class Materializer(Protocol):
+    """Saves artifacts."""
+
+    uri: str
+    """Where artifacts go."""
+
+    def save(self, data: object) -> None:
+        """Saves data."""
+        ...
+
+    def load(self) -> object: ...
+
+
+class Serializer(typing.Protocol[T]):
+    """Turns values into bytes."""
+
+    def dumps(self, value: T) -> bytes: ...
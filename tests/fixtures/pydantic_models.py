@@ -0,0 +1,36 @@
+"""Fixture module with pydantic models."""
+
+from typing import ClassVar, Dict, List, Optional
+
+from pydantic import BaseModel, ConfigDict, Field, field_validator
+
+
+class StackSettings(BaseModel):
+    """Settings of a stack."""
+
+    model_config = ConfigDict(extra="forbid")
+
+    name: str = Field(..., description="Name of the stack.")
+    retries: int = Field(3, description="How often to retry a step.")
+    labels: Dict[str, str] = Field(default_factory=dict)
+    parent: Optional[str] = None
+    """Name of the stack this one extends."""
+    components: List[str]
+    flavor: ClassVar[str] = "default"
+    _client: Optional[object] = None
+
+    @field_validator("name")
+    @classmethod
+    def check_name(cls, value: str) -> str:
+        """Checks the name."""
+        return value
+
+    def describe(self) -> str:
+        """Describes the stack."""
+        return self.name
+
+
+class Secret(BaseSettings, frozen=True):
+    """A secret read from the environment."""
+
+    value: str
@@ -0,0 +1,32 @@
+class Registry:
+    """Keeps track of stacks."""
+
+    def register(self, name: str) -> None:
+        """Register a stack.
+
+        Raises:
+            KeyError: If the name is taken.
+        """
+        if name in self.names:
+            raise KeyError(name)
+
+
+def load(path: str) -> str:
+    """Load a file."""
+    if not path:
+        raise ValueError("empty path")
+    try:
+        return read(path)
+    except OSError:
+        raise
+    except LookupError as err:
+        raise err
+    finally:
+        for attempt in range(3):
+            if attempt > 1:
+                raise errors.RetryError()
+
+    def nested():
+        raise RuntimeError("not raised by load")
+
+    raise ValueError("again")
@@ -0,0 +1,13 @@
+"""Public API of the package."""
+
+from ._internal import Component
+from ._internal.models import Stack
+from ._internal.models import build_stack as make_stack
+from ._internal.models import missing
+from .public import Client
+
+__all__ = ["Client", "Component", "Stack", "make_stack", "get_stack"]
+
+
+def get_stack(name: str) -> Stack:
+    """Look up a stack by name."""
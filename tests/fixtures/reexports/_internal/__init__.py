@@ -0,0 +1 @@
+from .components import Component
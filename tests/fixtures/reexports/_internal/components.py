@@ -0,0 +1,5 @@
+class Component:
+    """A stack component."""
+
+    def configure(self, flavor: str) -> None:
+        """Configure the component."""
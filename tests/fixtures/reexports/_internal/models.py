@@ -0,0 +1,9 @@
+class Stack:
+    """A collection of components."""
+
+    def describe(self) -> str:
+        """Describe the stack."""
+
+
+def build_stack(name: str) -> Stack:
+    """Build a stack from its name."""
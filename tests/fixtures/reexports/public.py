@@ -0,0 +1,2 @@
+class Client:
+    """Documented on its own page."""
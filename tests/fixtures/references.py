@@ -0,0 +1,77 @@
+"""Symbols referring to each other in annotations, bases and decorators."""
+
+from typing import List, Optional
+
+
+def register(cls):
+    """Registers a flavor."""
+    return cls
+
+
+class Stack:
+    """A stack of components."""
+
+    def copy(self) -> "Stack":
+        """Refers to its own class, which is not a reference."""
+
+    def components(self) -> List["Component"]:
+        """Lists the components."""
+
+
+@register
+class Component:
+    """A stack component."""
+
+    @property
+    def stack(self) -> Optional[Stack]:
+        """The stack the component belongs to."""
+
+
+class Orchestrator(Component):
+    """Runs pipelines."""
+
+    def run(self, stack: Stack) -> None:
+        """Runs on a stack."""
+
+    def _prepare(self, stack: Stack) -> None:
+        """Private, so not listed."""
+
+
+def create_stack(name: str) -> Stack:
+    """Creates a stack."""
+
+
+def get_stack(name: str) -> Optional[Stack]:
+    """Gets a stack."""
+
+
+def list_stacks() -> List[Stack]:
+    """Lists stacks."""
+
+
+def delete_stack(stack: Stack) -> None:
+    """Deletes a stack."""
+
+
+def update_stack(stack: Stack, name: str) -> Stack:
+    """Updates a stack."""
+
+
+def activate_stack(stack: Stack) -> None:
+    """Activates a stack."""
+
+
+def describe_stack(stack: Stack) -> str:
+    """Describes a stack."""
+
+
+def export_stack(stack: Stack, path: str) -> None:
+    """Exports a stack."""
+
+
+def import_stack(path: str) -> Stack:
+    """Imports a stack."""
+
+
+def copy_stack(stack: Stack, name: str) -> Stack:
+    """Copies a stack."""
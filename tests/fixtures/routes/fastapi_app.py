@@ -0,0 +1,49 @@
+"""HTTP API of the store."""
+
+from typing import Annotated, List, Optional
+
+from fastapi import APIRouter, Depends, FastAPI, Header, Query, Request
+from pydantic import BaseModel
+
+
+class Item(BaseModel):
+    name: str
+    price: float
+
+
+app = FastAPI()
+router = APIRouter(prefix="/items")
+
+
+def get_db():
+    """Not an endpoint."""
+
+
+@router.get("/{item_id}", response_model=Item)
+async def read_item(
+    item_id: int,
+    q: Optional[str] = None,
+    db=Depends(get_db),
+    request: Request = None,
+):
+    """Read an item.
+
+    Looks the item up by its id.
+
+    Args:
+        item_id: Id of the item.
+        q: Free-text filter.
+    """
+
+
+@router.post("/", status_code=201)
+def create_item(item: Item, x_token: Annotated[str, Header(description="API token.")]) -> Item:
+    """Create an item."""
+
+
+@app.api_route("/health", methods=["GET", "HEAD"], summary="Check the service.")
+def health(verbose: bool = Query(False, description="Include details."), tags: List[str] = Query(...)):
+    pass
+
+
+app.include_router(router, prefix="/api/v1")
@@ -0,0 +1,26 @@
+from flask import Blueprint, Flask
+
+app = Flask(__name__)
+users = Blueprint("users", __name__, url_prefix="/users")
+
+
+@users.route("/<int:user_id>", methods=["GET", "DELETE"])
+def user(user_id):
+    """Read or delete a user.
+
+    Args:
+        user_id: Id of the user.
+    """
+
+
+@users.post("/")
+def create_user():
+    """Create a user."""
+
+
+@app.route("/")
+def index():
+    return "ok"
+
+
+app.register_blueprint(users, url_prefix="/api/users")
@@ -0,0 +1,37 @@
+"""Functions and methods some of which carry skipped decorators."""
+
+import click
+
+
+def helper(func):
+    """Marks a function as a helper."""
+    return func
+
+
+@click.command(name="up")
+def up() -> None:
+    """Starts the server."""
+
+
+@helper
+def assist() -> None:
+    """Assists the server."""
+
+
+def start(port: int) -> None:
+    """Starts the server on a port."""
+
+
+class Server:
+    """A server."""
+
+    def run(self) -> None:
+        """Runs the server."""
+
+    @helper
+    def tune(self) -> None:
+        """Tunes the server."""
+
+    @click.command
+    def serve(self) -> None:
+        """Serves from the command line."""
@@ -0,0 +1,29 @@
+"""Classes with slots and metaclasses."""
+
+import abc
+
+
+class PluginMeta(type):
+    """Registers every plugin class."""
+
+
+class Plugin(metaclass=PluginMeta):
+    """A plugin loaded at startup."""
+
+    __slots__ = ("name", "id")
+
+    def __init__(self, name: str, id: str) -> None:
+        self.name = name
+        self.id = id
+
+
+class Marker:
+    """A marker without any state."""
+
+    __slots__ = ()
+
+
+class Tagged(metaclass=abc.ABCMeta):
+    """Something carrying a tag."""
+
+    __slots__ = "tag"
@@ -0,0 +1,21 @@
+"""A module with a stub."""
+
+
+class Store:
+    """Stores values."""
+
+    def get(self, key, default=None):
+        """Fetch a value.
+
+        Args:
+            key: The key to look up.
+            default: Returned when the key is missing.
+        """
+        return self.values.get(key, default)
+
+    def put(self, key, value, overwrite=False):
+        """Store a value."""
+
+
+def connect(url, timeout=10.0):
+    """Connect to a store."""
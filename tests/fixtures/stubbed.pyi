@@ -0,0 +1,9 @@
+from typing import Any, Optional
+
+class Store:
+    def get(self, key: str, default: Optional[Any] = ...) -> Optional[Any]: ...
+    def put(self, key: str, value: Any) -> None: ...
+
+def connect(url: str, timeout: float = ...) -> Store: ...
+def native_helper(flag: bool) -> int:
+    """Implemented in C."""
@@ -0,0 +1,31 @@
+"""Functions annotated with locally defined type aliases."""
+
+from pathlib import Path
+from typing import Any, Dict, List, Optional, TypeAlias, Union
+from uuid import UUID
+
+PathLike = Union[str, Path]
+"""Anything accepted as a file system path."""
+
+Paths: TypeAlias = List[PathLike]
+
+Labels = Dict[str, str] | None
+
+JSONDict = Dict[str, Any]
+
+type StackID = UUID
+"""The ID of a registered stack."""
+
+DEFAULT_PATHS = ["a", "b"]
+
+
+def read(path: PathLike, labels: Labels = None) -> Optional[PathLike]:
+    """Read a file."""
+
+
+def read_all(paths: Paths) -> List[str]:
+    """Read several files."""
+
+
+def stack_config(stack: StackID) -> JSONDict:
+    """Fetch the configuration of a stack."""
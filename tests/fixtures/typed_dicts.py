@@ -0,0 +1,26 @@
+"""Fixture module with typed dictionaries."""
+
+from typing import List, TypedDict
+
+from typing_extensions import NotRequired, Required
+
+
+class StepConfig(TypedDict):
+    """Configuration of a step."""
+
+    name: str
+    """Name of the step."""
+
+    retries: NotRequired[int]
+    tags: List[str]
+
+
+class RunOptions(TypedDict, total=False):
+    """Options of a pipeline run."""
+
+    run_name: Required[str]
+    schedule: str
+
+
+Credentials = TypedDict("Credentials", {"username": str, "token": NotRequired[str]})
+"""Credentials of a service connector."""
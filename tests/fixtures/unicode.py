@@ -0,0 +1,17 @@
+"""Módulo con caracteres Unicode — 日本語のドキュメント."""
+
+
+def grüßen(name: str = "Welt") -> str:
+    """Grüßt jemanden. 👋
+
+    Gibt eine Begrüßung zurück.
+    """
+    return name
+
+
+class Über:
+    """Eine Klasse mit Umlauten: äöü."""
+
+    def größe(self) -> int:
+        """Größe in Bytes."""
+        return 0
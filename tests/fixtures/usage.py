@@ -0,0 +1,25 @@
+"""Entries with import-and-call snippets."""
+
+from typing import List, Optional
+
+
+class HTTPClient:
+    """Talks to the server."""
+
+    def __init__(self, url: str, token: Optional[str] = None):
+        """Connects to `url`."""
+
+    def create_stack(self, name: str, components: List[str], description: str = "") -> None:
+        """Creates a stack."""
+
+    @classmethod
+    def from_env(cls, prefix: str = "ZENML") -> "HTTPClient":
+        """Reads the connection settings from the environment."""
+
+    @staticmethod
+    def ping(url: str) -> bool:
+        """Checks that the server is up."""
+
+
+def connect(url: str, *, timeout: float) -> HTTPClient:
+    """Opens a connection."""
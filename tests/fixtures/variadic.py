@@ -0,0 +1,19 @@
+from typing import Any
+
+
+def run_pipeline(name: str, *steps: str, cache: bool, **settings: Any) -> None:
+    """Runs a pipeline.
+
+    Args:
+        name: The pipeline name.
+        *steps: Names of the steps to run.
+        cache: Whether to reuse cached outputs.
+        **settings: Settings passed to each step.
+    """
+
+
+class Stack:
+    """A stack."""
+
+    def configure(self, name, *, debug: bool, timeout: int = 30, flag: bool = False) -> None:
+        """Configures the stack."""
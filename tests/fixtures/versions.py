@@ -0,0 +1,23 @@
+"""Symbols with version markers."""
+
+
+class Stack:
+    """A stack of components.
+
+    .. versionadded:: 0.40.0
+    """
+
+    def register(self, name: str) -> None:
+        """Register the stack.
+
+        .. versionchanged:: 0.45.0: renamed from `save`.
+        .. versionadded:: 0.40.0
+        """
+
+
+def create_stack(name: str) -> Stack:
+    """Create a stack.
+
+    Added in version: 0.9.0
+    Changed in version: 0.10.0: accepts a name.
+    """
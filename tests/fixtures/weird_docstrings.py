@@ -0,0 +1,32 @@
+'''Module docstring using single quotes.'''
+
+
+def no_docstring(value):
+    return value
+
+
+def one_liner() -> int:
+    """Return a number."""
+    return 1
+
+
+def quoted() -> None:
+    """ "Quoted" text with 'mixed' quotes """
+    pass
+
+
+def indented(a: int, b: int) -> int:
+    """
+        Docstring that starts on the second line.
+
+        It keeps its odd indentation.
+
+            Args:
+                a: First operand.
+                b: Second operand.
+    """
+    return a
+
+
+class Empty:
+    pass
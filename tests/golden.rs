@@ -0,0 +1,521 @@
+//! Golden-file tests: every fixture under `tests/fixtures/` is run through the
+//! full parse and render pipeline and compared with its expected page under
+//! `tests/snapshots/`.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite the expected pages after an
+//! intentional output change, then review the diff. New features should come
+//! with a fixture exercising them.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{
+    inline_private_reexports, is_notebook, merge_adjacent_stub, parse_module, render_module,
+    AliasStyle, Badge, Dunders, Format, MarkerAction, NestedFunctions, Notebook, Project,
+    RenderOptions, StubPreference,
+};
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Renders `input`. A notebook's Markdown cells are shown between the
+/// definitions.
+fn render(input: &Path, options: &RenderOptions) -> String {
+    let mut source = fs::read_to_string(input)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", input.display(), err));
+    let notebook = is_notebook(input).then(|| Notebook::parse(&source, input).unwrap());
+    if let Some(notebook) = &notebook {
+        source = notebook.source.clone();
+    }
+    let relative = input.strip_prefix(root()).unwrap_or(input);
+    let mut module = parse_module(&source, relative, options)
+        .unwrap_or_else(|err| panic!("failed to parse {}: {}", input.display(), err));
+    if let Some(notebook) = notebook {
+        module.prose = notebook.markdown;
+    }
+    merge_adjacent_stub(&mut module, options, StubPreference::Stub);
+    inline_private_reexports(&mut module, options);
+    let project = Project::new(vec![module]);
+    render_module(&project.modules()[0], &project, options)
+}
+
+fn assert_golden(input: &Path, expected: &Path, options: &RenderOptions) {
+    let actual = render(input, options);
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some_and(|value| value == "1") {
+        fs::create_dir_all(expected.parent().unwrap()).unwrap();
+        fs::write(expected, &actual).unwrap();
+        return;
+    }
+
+    let expected_content = fs::read_to_string(expected).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot {}; run with UPDATE_SNAPSHOTS=1 to create it",
+            expected.display()
+        )
+    });
+    if actual != expected_content {
+        let first_difference = actual
+            .lines()
+            .zip(expected_content.lines())
+            .position(|(a, e)| a != e)
+            .unwrap_or_else(|| actual.lines().count().min(expected_content.lines().count()));
+        panic!(
+            "{} does not match {} (first difference at line {}):\n  expected: {:?}\n  actual:   {:?}\n\
+             run with UPDATE_SNAPSHOTS=1 to accept the new output",
+            input.display(),
+            expected.display(),
+            first_difference + 1,
+            expected_content.lines().nth(first_difference),
+            actual.lines().nth(first_difference),
+        );
+    }
+}
+
+/// Checks `tests/fixtures/<fixture>.py` against `tests/snapshots/<snapshot>.mdx`.
+fn check(fixture: &str, snapshot: &str, options: &RenderOptions) {
+    let root = root();
+    assert_golden(
+        &root.join("tests/fixtures").join(format!("{}.py", fixture)),
+        &root
+            .join("tests/snapshots")
+            .join(format!("{}.mdx", snapshot)),
+        options,
+    );
+}
+
+#[test]
+fn classes() {
+    check("classes", "classes", &RenderOptions::default());
+}
+
+#[test]
+fn async_functions() {
+    check(
+        "async_functions",
+        "async_functions",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn dataclasses() {
+    check("dataclasses", "dataclasses", &RenderOptions::default());
+}
+
+#[test]
+fn attrs_classes() {
+    check("attrs_classes", "attrs_classes", &RenderOptions::default());
+}
+
+#[test]
+fn weird_docstrings() {
+    check(
+        "weird_docstrings",
+        "weird_docstrings",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn unicode() {
+    check("unicode", "unicode", &RenderOptions::default());
+}
+
+#[test]
+fn module_prefix() {
+    let options = RenderOptions::builder()
+        .module_prefix("acme")
+        .special_tag(true)
+        .build();
+    check("classes", "classes_module_prefix", &options);
+}
+
+#[test]
+fn include_private() {
+    let options = RenderOptions::builder().include_private(true).build();
+    check("classes", "classes_include_private", &options);
+}
+
+#[test]
+fn inferred_types() {
+    check(
+        "inferred_types",
+        "inferred_types",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn no_inferred_types() {
+    let options = RenderOptions::builder().infer_types(false).build();
+    check("inferred_types", "inferred_types_disabled", &options);
+}
+
+#[test]
+fn private_reexports() {
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    check("reexports/__init__", "reexports", &options);
+}
+
+#[test]
+fn stub_signatures() {
+    check("stubbed", "stubbed", &RenderOptions::default());
+}
+
+/// A stub given on its own is documented like a module.
+#[test]
+fn stub_input() {
+    let root = root();
+    assert_golden(
+        &root.join("tests/fixtures/stubbed.pyi"),
+        &root.join("tests/snapshots/stubbed_stub_input.mdx"),
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn property_assignments() {
+    check(
+        "property_assignments",
+        "property_assignments",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn decorator_calls() {
+    check(
+        "decorator_calls",
+        "decorator_calls",
+        &RenderOptions::builder().show_validators(true).build(),
+    );
+}
+
+#[test]
+fn pydantic_models() {
+    let options = RenderOptions::builder()
+        .pydantic_base("BaseSettings")
+        .build();
+    check("pydantic_models", "pydantic_models", &options);
+}
+
+#[test]
+fn variadic_parameters() {
+    check("variadic", "variadic", &RenderOptions::default());
+}
+
+#[test]
+fn positional_only_parameters() {
+    check(
+        "positional_only",
+        "positional_only",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn collapsed_annotated_types() {
+    let options = RenderOptions::builder().collapse_annotated(true).build();
+    check("annotated", "annotated_collapsed", &options);
+}
+
+#[test]
+fn default_values() {
+    check("defaults", "defaults", &RenderOptions::default());
+}
+
+#[test]
+fn method_kinds() {
+    check("method_kinds", "method_kinds", &RenderOptions::default());
+}
+
+#[test]
+fn property_decorators() {
+    check(
+        "property_decorators",
+        "property_decorators",
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn raises() {
+    check("raises", "raises", &RenderOptions::default());
+}
+
+#[test]
+fn inferred_raises() {
+    let options = RenderOptions::builder().infer_raises(true).build();
+    check("raises", "raises_inferred", &options);
+}
+
+#[test]
+fn markers() {
+    check("markers", "markers", &RenderOptions::default());
+}
+
+#[test]
+fn custom_markers() {
+    let options = RenderOptions::builder()
+        .include_private(true)
+        .marker(
+            "beta",
+            MarkerAction::Badge(Badge {
+                label: "beta".to_string(),
+                note: None,
+            }),
+        )
+        .marker("experimental", MarkerAction::Exclude)
+        .build();
+    check("markers", "markers_custom", &options);
+}
+
+#[test]
+fn skip_decorators() {
+    let options = RenderOptions::builder()
+        .skip_decorator("command")
+        .skip_decorator("helper")
+        .build();
+    check("skip_decorators", "skip_decorators", &options);
+}
+
+/// The code cells of a notebook, with its Markdown cells in between.
+#[test]
+fn notebook() {
+    let root = root();
+    assert_golden(
+        &root.join("tests/fixtures/notebook.ipynb"),
+        &root.join("tests/snapshots/notebook.mdx"),
+        &RenderOptions::default(),
+    );
+}
+
+#[test]
+fn only_matching_names() {
+    let options = RenderOptions::builder()
+        .only(Some("^Stack$".parse().unwrap()))
+        .build();
+    check("classes", "classes_only", &options);
+}
+
+#[test]
+fn version_markers() {
+    check("versions", "versions", &RenderOptions::default());
+}
+
+#[test]
+fn type_aliases() {
+    check("type_aliases", "type_aliases", &RenderOptions::default());
+}
+
+#[test]
+fn expanded_type_aliases() {
+    let options = RenderOptions::builder()
+        .alias_style(AliasStyle::Both)
+        .build();
+    check("type_aliases", "type_aliases_both", &options);
+}
+
+#[test]
+fn inheritance_diagrams() {
+    let options = RenderOptions::builder().inheritance_diagrams(true).build();
+    check("inheritance", "inheritance", &options);
+}
+
+#[test]
+fn referenced_by() {
+    let options = RenderOptions::builder().referenced_by(true).build();
+    check("references", "references", &options);
+}
+
+#[test]
+fn usage_snippets() {
+    check("usage", "usage", &RenderOptions::default());
+}
+
+#[test]
+fn no_usage_snippets() {
+    let options = RenderOptions::builder().usage_snippets(false).build();
+    check("usage", "usage_disabled", &options);
+}
+
+#[test]
+fn dunder_all() {
+    check("dunder_all", "dunder_all", &RenderOptions::default());
+}
+
+#[test]
+fn ignore_dunder_all() {
+    let options = RenderOptions::builder().respect_all(false).build();
+    check("dunder_all", "dunder_all_ignored", &options);
+}
+
+#[test]
+fn dunders() {
+    check("dunders", "dunders", &RenderOptions::default());
+}
+
+#[test]
+fn all_dunders() {
+    let options = RenderOptions::builder().dunders(Dunders::All).build();
+    check("dunders", "dunders_all", &options);
+}
+
+#[test]
+fn no_dunders() {
+    let options = RenderOptions::builder().dunders(Dunders::None).build();
+    check("dunders", "dunders_none", &options);
+}
+
+#[test]
+fn custom_frontmatter() {
+    let options = RenderOptions::builder()
+        .frontmatter("title", "Async: clients")
+        .frontmatter("description", "The \"async\" API")
+        .frontmatter("icon", "bolt")
+        .frontmatter("version", "1.10")
+        .frontmatter("draft", "no")
+        .frontmatter("sidebar_position", "3")
+        .module_frontmatter("async_functions", "icon", "cloud")
+        .module_frontmatter("other", "icon", "ignored")
+        .build();
+    check("async_functions", "async_functions_frontmatter", &options);
+}
+
+/// Markdown pages are checked against a `.md` snapshot.
+#[test]
+fn markdown_format() {
+    let root = root();
+    let options = RenderOptions::builder().format(Format::Markdown).build();
+    assert_golden(
+        &root.join("tests/fixtures/markers.py"),
+        &root.join("tests/snapshots/markers.md"),
+        &options,
+    );
+}
+
+#[test]
+fn type_parameters() {
+    check("generics", "generics", &RenderOptions::default());
+}
+
+#[test]
+fn class_attributes() {
+    check("attributes", "attributes", &RenderOptions::default());
+}
+
+#[test]
+fn enums() {
+    check("enums", "enums", &RenderOptions::default());
+}
+
+#[test]
+fn typed_dicts() {
+    check("typed_dicts", "typed_dicts", &RenderOptions::default());
+}
+
+#[test]
+fn named_tuples() {
+    check("named_tuples", "named_tuples", &RenderOptions::default());
+}
+
+#[test]
+fn protocols() {
+    check("protocols", "protocols", &RenderOptions::default());
+}
+
+#[test]
+fn module_attributes() {
+    check(
+        "module_attributes",
+        "module_attributes",
+        &RenderOptions::default(),
+    );
+    let options = RenderOptions::builder().all_module_attributes(true).build();
+    check("module_attributes", "module_attributes_all", &options);
+}
+
+#[test]
+fn nested_classes() {
+    check(
+        "nested_classes",
+        "nested_classes",
+        &RenderOptions::default(),
+    );
+    let options = RenderOptions::builder().skip_nested_config(true).build();
+    check("nested_classes", "nested_classes_no_config", &options);
+}
+
+#[test]
+fn nested_functions() {
+    check(
+        "nested_functions",
+        "nested_functions",
+        &RenderOptions::default(),
+    );
+    for (mode, snapshot) in [
+        (NestedFunctions::List, "nested_functions_list"),
+        (NestedFunctions::Document, "nested_functions_document"),
+        (NestedFunctions::Omit, "nested_functions_omit"),
+    ] {
+        let options = RenderOptions::builder().nested_functions(mode).build();
+        check("nested_functions", snapshot, &options);
+    }
+}
+
+#[test]
+fn overloads() {
+    check("overloads", "overloads", &RenderOptions::default());
+}
+
+#[test]
+fn merged_init() {
+    check("merge_init", "merge_init", &RenderOptions::default());
+    let options = RenderOptions::builder().merge_init(true).build();
+    check("merge_init", "merge_init_merged", &options);
+}
+
+#[test]
+fn slots() {
+    check("slots", "slots", &RenderOptions::default());
+}
+
+#[test]
+fn deprecations() {
+    let options = RenderOptions::builder()
+        .deprecated_decorator("legacy")
+        .build();
+    check("deprecations", "deprecations", &options);
+    let options = RenderOptions::builder()
+        .deprecated_decorator("legacy")
+        .deprecated_last(true)
+        .build();
+    check("deprecations", "deprecations_last", &options);
+    let options = RenderOptions::builder()
+        .deprecated_decorator("legacy")
+        .hide_deprecated(true)
+        .build();
+    check("deprecations", "deprecations_hidden", &options);
+}
+
+#[test]
+fn docstring_signatures() {
+    let options = RenderOptions::builder()
+        .signature_from_docstring(true)
+        .build();
+    check("docstring_signatures", "docstring_signatures", &options);
+}
+
+/// The example page in `docs/` must stay in sync with `test.py`.
+#[test]
+fn sample_page() {
+    let root = root();
+    assert_golden(
+        &root.join("test.py"),
+        &root.join("docs/test.mdx"),
+        &RenderOptions::default(),
+    );
+}
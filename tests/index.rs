@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use sdkdocgen::{
+    index_page, package_directories, package_listing, parse_module, render_package_index,
+    ModuleDoc, Project, RenderOptions,
+};
+
+fn module(source: &str, package: &str, file: &str, page: &str) -> ModuleDoc {
+    let options = RenderOptions::builder().module_prefix(package).build();
+    let mut module = parse_module(source, Path::new(file), &options).unwrap();
+    module.page = page.to_string();
+    module
+}
+
+fn project() -> Project {
+    Project::new(vec![
+        module(
+            "\"\"\"ZenML. The MLOps framework.\"\"\"\n",
+            "zenml",
+            "__init__.py",
+            "index",
+        ),
+        module(
+            "\"\"\"The client that talks\nto the server. More.\"\"\"\n",
+            "zenml",
+            "client.py",
+            "client",
+        ),
+        module("", "zenml.cli", "stack.py", "cli/stack"),
+        module(
+            "\"\"\"Base models.\"\"\"\n",
+            "zenml.models",
+            "base.py",
+            "models/base",
+        ),
+    ])
+}
+
+#[test]
+fn lists_modules_and_subpackages_of_each_directory() {
+    let project = project();
+    let options = RenderOptions::default();
+    assert_eq!(
+        package_directories(&project)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        ["", "cli", "models"]
+    );
+    assert_eq!(index_page("", "index"), "index");
+    assert_eq!(index_page("cli", "index"), "cli/index");
+
+    assert_eq!(
+        package_listing(&project, "", "index", &options),
+        "**Modules:**\n\n\
+         | Name | Description |\n| --- | --- |\n\
+         | [`client`](client) | The client that talks to the server. |\n\n\
+         **Sub-packages:**\n\n\
+         | Name | Description |\n| --- | --- |\n\
+         | [`cli`](cli/index) |  |\n\
+         | [`models`](models/index) |  |\n\n"
+    );
+}
+
+#[test]
+fn renders_a_page_for_a_directory_without_init() {
+    let project = project();
+    let page = render_package_index(&project, "models", "index", &RenderOptions::default());
+    assert_eq!(
+        page,
+        "---\ntitle: zenml.models\n---\n\n## `zenml.models`\n\n\
+         **Modules:**\n\n\
+         | Name | Description |\n| --- | --- |\n\
+         | [`base`](base) | Base models. |\n\n"
+    );
+}
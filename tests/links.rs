@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{check_links, parse_module, render_module, Category, Project, RenderOptions};
+
+fn page(path: &str, content: &str) -> (PathBuf, String) {
+    (PathBuf::from(path), content.to_string())
+}
+
+#[test]
+fn reports_missing_anchors_and_pages() {
+    let pages = [
+        page(
+            "docs/client.mdx",
+            "<a id=\"zenml.client.Client\"></a>\n\
+             See [`Client`](#zenml.client.Client) and [`Stack`](stack#zenml.stack.Stack).\n\
+             Also [`gone`](#zenml.client.gone) and [old](../old/page).\n",
+        ),
+        page("docs/stack.mdx", "<a id=\"zenml.stack.Stack\"></a>\n"),
+    ];
+
+    let problems: Vec<(String, usize, String)> = check_links(&pages)
+        .into_iter()
+        .map(|diagnostic| {
+            assert_eq!(diagnostic.category, Category::BrokenLink);
+            (
+                diagnostic.file.to_string_lossy().into_owned(),
+                diagnostic.line,
+                diagnostic.message,
+            )
+        })
+        .collect();
+    assert_eq!(
+        problems,
+        [
+            (
+                "docs/client.mdx".to_string(),
+                3,
+                "link [`gone`](#zenml.client.gone) is broken: anchor `#zenml.client.gone` does not exist"
+                    .to_string()
+            ),
+            (
+                "docs/client.mdx".to_string(),
+                3,
+                "link [old](../old/page) is broken: page `old/page.mdx` does not exist".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn skips_external_links_and_code_blocks() {
+    let pages = [page(
+        "client.mdx",
+        "[docs](https://docs.zenml.io/#nowhere) [mail](mailto:team@zenml.io)\n\
+         [Integration](/integrations-integration/#missing \"Integration\")\n\
+         ```py\n\
+         handlers[name](event)\n\
+         ```\n",
+    )];
+    assert_eq!(check_links(&pages), []);
+}
+
+#[test]
+fn generated_fixture_pages_have_no_broken_links() {
+    let options = RenderOptions::default();
+    for name in ["classes", "dataclasses", "property_assignments", "raises"] {
+        let path = Path::new("tests/fixtures").join(format!("{}.py", name));
+        let source = fs::read_to_string(&path).unwrap();
+        let project = Project::new(vec![parse_module(&source, &path, &options).unwrap()]);
+        let content = render_module(&project.modules()[0], &project, &options);
+        let pages = [(PathBuf::from(format!("{}.mdx", name)), content)];
+        assert_eq!(check_links(&pages), [], "{}", name);
+    }
+}
@@ -0,0 +1,240 @@
+use std::path::Path;
+
+use sdkdocgen::{lint, missing_docstrings, parse_module, Category, RenderOptions, Rule};
+
+fn findings(source: &str) -> Vec<(Rule, String, usize)> {
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let module = parse_module(source, Path::new("stacks.py"), &options).unwrap();
+    lint(&module, &options)
+        .into_iter()
+        .map(|finding| (finding.rule, finding.symbol, finding.line))
+        .collect()
+}
+
+#[test]
+fn reports_missing_docstrings_on_public_symbols() {
+    let source = r#"
+class Stack:
+    def describe(self):
+        pass
+
+    def _private(self):
+        pass
+
+
+def helper():
+    pass
+"#;
+    assert_eq!(
+        findings(source),
+        [
+            (Rule::MissingDocstring, "zenml.stacks".to_string(), 1),
+            (Rule::MissingDocstring, "Stack".to_string(), 2),
+            (Rule::MissingDocstring, "Stack.describe".to_string(), 3),
+            (Rule::MissingDocstring, "helper".to_string(), 10),
+        ]
+    );
+}
+
+#[test]
+fn required_docstrings_skip_the_module_and_hidden_symbols() {
+    let source = r#"
+__all__ = ["Stack"]
+
+
+class Stack:
+    def describe(self):
+        pass
+
+    def __repr__(self):
+        pass
+
+
+def helper():
+    pass
+"#;
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let module = parse_module(source, Path::new("stacks.py"), &options).unwrap();
+    let missing: Vec<String> = missing_docstrings(&module, &options)
+        .into_iter()
+        .map(|finding| finding.symbol)
+        .collect();
+    assert_eq!(missing, ["Stack", "Stack.describe"]);
+}
+
+#[test]
+fn checks_args_returns_and_raises_against_the_code() {
+    let source = r#"
+"""Stacks."""
+
+
+def register(name: str, size: int, *args) -> int:
+    """Register a stack.
+
+    Args:
+        name: The name.
+        colour: Not a parameter.
+        *args: Extra values.
+
+    Raises:
+        KeyError: If the name is taken.
+        ValueError: If the size is negative.
+    """
+    if size < 0:
+        raise ValueError("negative")
+    try:
+        lookup(name)
+    except LookupError as err:
+        raise err
+    return 1
+"#;
+    let rules: Vec<Rule> = findings(source)
+        .into_iter()
+        .map(|(rule, ..)| rule)
+        .collect();
+    assert_eq!(
+        rules,
+        [
+            Rule::UnknownParameter,
+            Rule::UndocumentedParameter,
+            Rule::MissingReturns,
+            Rule::UnraisedException,
+        ]
+    );
+}
+
+#[test]
+fn undocumented_parameters_need_an_args_section() {
+    let source = r#"
+"""Stacks."""
+
+
+class Stack:
+    """A stack."""
+
+    def describe(self, verbose: bool) -> None:
+        """Describe the stack."""
+"#;
+    assert_eq!(findings(source), []);
+}
+
+#[test]
+fn reports_malformed_section_headers() {
+    let source = r#"
+"""Stacks."""
+
+
+def register(name):
+    """Register a stack.
+
+    Args
+        name: The name.
+
+    returns:
+        Nothing.
+
+    Raises: ValueError if the name is taken.
+    """
+"#;
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let module = parse_module(source, Path::new("stacks.py"), &options).unwrap();
+    let messages: Vec<String> = lint(&module, &options)
+        .into_iter()
+        .filter(|finding| finding.rule == Rule::MalformedSection)
+        .map(|finding| finding.message)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "section header `Args` is missing a trailing colon",
+            "section header `returns:` should be written `Returns:`",
+            "text after `Raises:` is ignored; start the section on the next line",
+        ]
+    );
+}
+
+#[test]
+fn reports_args_out_of_signature_order() {
+    let source = r#"
+"""Stacks."""
+
+
+def register(name: str, size: int, flavor: str, *args) -> None:
+    """Register a stack.
+
+    Args:
+        *args: Extra values.
+        name: The name.
+        flavor: The flavor.
+        colour: Not a parameter.
+        size: The size.
+    """
+
+
+def rename(old: str, new: str) -> None:
+    """Rename a stack.
+
+    Args:
+        old: The current name.
+        new: The new name.
+    """
+"#;
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let module = parse_module(source, Path::new("stacks.py"), &options).unwrap();
+    let findings: Vec<(String, String)> = lint(&module, &options)
+        .into_iter()
+        .filter(|finding| finding.rule == Rule::ParameterOrder)
+        .map(|finding| (finding.symbol, finding.message))
+        .collect();
+    assert_eq!(
+        findings,
+        [(
+            "register".to_string(),
+            "`Args:` lists `flavor` where the signature has `size`".to_string()
+        )]
+    );
+
+    let mismatches: Vec<&str> = module
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.category == Category::DocstringMismatch)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        mismatches,
+        ["`Args:` of `register` lists `flavor` where the signature has `size`"]
+    );
+}
+
+#[test]
+fn reports_docstring_signatures_that_do_not_parse() {
+    let source = r#"
+def run(*args, **kwargs):
+    """run(name: str -> Run"""
+
+
+def stop(*args, **kwargs):
+    """stop(run_id: str) -> None"""
+"#;
+    let options = RenderOptions::builder()
+        .signature_from_docstring(true)
+        .build();
+    let module = parse_module(source, Path::new("runs.py"), &options).unwrap();
+    let mismatches: Vec<&str> = module
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.category == Category::DocstringMismatch)
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(
+        mismatches,
+        ["the first line of the docstring of `run` is not a valid signature: `run(name: str -> Run`"]
+    );
+}
+
+#[test]
+fn rules_parse_from_their_codes() {
+    assert_eq!("DOC004".parse::<Rule>(), Ok(Rule::MissingReturns));
+    assert_eq!("doc001".parse::<Rule>(), Ok(Rule::MissingDocstring));
+    assert!("DOC999".parse::<Rule>().is_err());
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::Path;
+
+use sdkdocgen::{parse_module, render_module, Project, RenderOptions, Strings};
+
+fn render(source: &str, strings: Strings) -> String {
+    let options = RenderOptions::builder()
+        .module_prefix("zenml")
+        .special_tag(true)
+        .strings(strings)
+        .build();
+    let module = parse_module(source, Path::new("stacks.py"), &options).unwrap();
+    let project = Project::new(vec![module]);
+    render_module(&project.modules()[0], &project, &options)
+}
+
+const SOURCE: &str = r#"
+class Stack:
+    """A stack."""
+
+    def describe(self, verbose: bool) -> str:
+        """Describe the stack."""
+
+
+def delete(name: str) -> None:
+    """Delete a stack."""
+"#;
+
+#[test]
+fn translates_labels_and_keeps_english_for_missing_keys() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/locale/ja.toml");
+    let strings = Strings::from_toml(&fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(strings.parameters, "引数");
+    assert_eq!(strings.example, Strings::default().example);
+
+    let page = render(SOURCE, strings);
+    assert!(page.contains("## `zenml.stacks` `特殊`"), "{}", page);
+    assert!(page.contains("| 名前 | 種類 | 説明 |"), "{}", page);
+    assert!(page.contains("**`Stack` のメンバー:**"), "{}", page);
+    assert!(page.contains("**引数:**"), "{}", page);
+    assert!(page.contains("| `name` | `str` |  | _必須_ |"), "{}", page);
+    assert!(page.contains("`stacks.py` のソースコード"), "{}", page);
+    assert!(page.contains("**説明:**"), "{}", page);
+    assert!(!page.contains("Parameters"), "{}", page);
+}
+
+#[test]
+fn default_strings_reproduce_the_english_page() {
+    let page = render(SOURCE, Strings::from_toml("").unwrap());
+    assert_eq!(page, render(SOURCE, Strings::default()));
+    assert!(page.contains("**Parameters:**"));
+    assert!(page.contains("Source code in `stacks.py`"));
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    let err = Strings::from_toml("paramters = \"引数\"\n").unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `paramters`"),
+        "{}",
+        err
+    );
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use sdkdocgen::{
+    parse_module, render_module, render_nav, NavFormat, NavPage, Project, RenderOptions,
+};
+use serde_json::json;
+
+fn page(page: &str, title: &str) -> NavPage {
+    NavPage {
+        page: page.to_string(),
+        title: title.to_string(),
+    }
+}
+
+#[test]
+fn nests_mkdocs_pages_by_directory() {
+    let pages = [
+        page("stack", "stack"),
+        page("client", "client"),
+        page("index", "zenml"),
+        page("cli/stack", "stack"),
+        page("cli/index", "cli"),
+        page("models/_base", "_base"),
+        page("Zeta: notes", "Zeta: notes"),
+    ];
+    assert_eq!(
+        render_nav(NavFormat::Mkdocs, &pages, "md", "index"),
+        "nav:\n\
+         \x20 - zenml: index.md\n\
+         \x20 - cli:\n\
+         \x20   - cli: cli/index.md\n\
+         \x20   - stack: cli/stack.md\n\
+         \x20 - client: client.md\n\
+         \x20 - models:\n\
+         \x20   - _base: models/_base.md\n\
+         \x20 - stack: stack.md\n\
+         \x20 - \"Zeta: notes\": \"Zeta: notes.md\"\n"
+    );
+}
+
+#[test]
+fn nests_docusaurus_categories_by_directory() {
+    let pages = [
+        page("index", "zenml"),
+        page("client", "client"),
+        page("cli/index", "cli"),
+        page("cli/stack", "stack"),
+        page("cli/stack/components", "components"),
+        page("models/_base", "_base"),
+    ];
+    let sidebar = render_nav(NavFormat::Docusaurus, &pages, "mdx", "index");
+    assert!(sidebar.ends_with("]\n"));
+    let sidebar: serde_json::Value = serde_json::from_str(&sidebar).unwrap();
+    assert_eq!(
+        sidebar,
+        json!([
+            { "type": "doc", "id": "index", "label": "zenml" },
+            {
+                "type": "category",
+                "label": "cli",
+                "collapsed": false,
+                "link": { "type": "doc", "id": "cli/index" },
+                "items": [
+                    { "type": "doc", "id": "cli/stack", "label": "stack" },
+                    {
+                        "type": "category",
+                        "label": "stack",
+                        "collapsed": true,
+                        "items": [
+                            {
+                                "type": "doc",
+                                "id": "cli/stack/components",
+                                "label": "components",
+                            },
+                        ],
+                    },
+                ],
+            },
+            { "type": "doc", "id": "client", "label": "client" },
+            {
+                "type": "category",
+                "label": "models",
+                "collapsed": false,
+                "items": [{ "type": "doc", "id": "models/_base", "label": "_base" }],
+            },
+        ])
+    );
+}
+
+#[test]
+fn writes_the_page_id_to_the_frontmatter() {
+    let options = RenderOptions::builder().page_id(true).build();
+    let mut module = parse_module("", Path::new("stack.py"), &options).unwrap();
+    module.page = "cli/stack".to_string();
+    let project = Project::new(vec![module]);
+    let page = render_module(&project.modules()[0], &project, &options);
+    assert!(
+        page.starts_with("---\ntitle: stack\nid: stack\n---\n"),
+        "{}",
+        page
+    );
+}
+
+#[test]
+fn nests_mdbook_chapters_by_directory() {
+    let pages = [
+        page("index", "zenml"),
+        page("client", "client"),
+        page("cli/index", "cli"),
+        page("cli/stack", "stack"),
+        page("models/_base", "_base"),
+        page("notes [draft]", "notes [draft]"),
+    ];
+    assert_eq!(
+        render_nav(NavFormat::Mdbook, &pages, "md", "index"),
+        "# Summary\n\n\
+         - [zenml](index.md)\n\
+         - [cli](cli/index.md)\n\
+         \x20 - [stack](cli/stack.md)\n\
+         - [client](client.md)\n\
+         - [models]()\n\
+         \x20 - [_base](models/_base.md)\n\
+         - [notes \\[draft\\]](<notes [draft].md>)\n"
+    );
+}
@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use sdkdocgen::{is_notebook, Notebook, Prose};
+
+#[test]
+fn joins_the_code_cells_that_parse() {
+    let json = r##"{
+      "cells": [
+        {"cell_type": "markdown", "source": "# Setup"},
+        {"cell_type": "code", "source": ["!pip install zenml\n", "  %time x = 1\n", "import zenml"]},
+        {"cell_type": "code", "source": "def broken(:"},
+        {"cell_type": "raw", "source": "ignored"},
+        {"cell_type": "markdown", "source": ["  \n"]},
+        {"cell_type": "markdown", "source": ["Then a client."]},
+        {"cell_type": "code", "source": ["%%time\n", "client = 1"]},
+        {"cell_type": "code", "source": "client = zenml.Client()\n\n"}
+      ],
+      "metadata": {},
+      "nbformat": 4,
+      "nbformat_minor": 5
+    }"##;
+    let notebook = Notebook::parse(json, Path::new("setup.ipynb")).unwrap();
+    assert_eq!(notebook.source, "import zenml\n\nclient = zenml.Client()\n");
+    assert_eq!(
+        notebook.markdown,
+        [
+            Prose {
+                line: 1,
+                text: "# Setup".to_string()
+            },
+            Prose {
+                line: 2,
+                text: "Then a client.".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn rejects_invalid_json() {
+    let err = Notebook::parse("{\"cells\": [", Path::new("broken.ipynb")).unwrap_err();
+    assert_eq!(err.line(), 1);
+}
+
+#[test]
+fn recognizes_notebooks_by_extension() {
+    assert!(is_notebook(Path::new("examples/train.ipynb")));
+    assert!(!is_notebook(Path::new("examples/train.py")));
+}
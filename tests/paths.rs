@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{
+    discover_python_files, expand_glob, glob_base, glob_matches, is_glob, normalize_path,
+};
+
+fn touch(root: &Path, relative: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "").unwrap();
+}
+
+#[test]
+fn discovers_python_files_of_a_package() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("discover");
+    let _ = fs::remove_dir_all(&root);
+    for file in [
+        "pkg/__init__.py",
+        "pkg/sub/module.py",
+        "pkg/sub/__pycache__/module.cpython-312.py",
+        "pkg/.hidden/secret.py",
+        "pkg/.venv.py",
+        "pkg/README.md",
+        "pkg/client.py",
+        "pkg/client.pyi",
+        "pkg/_native.pyi",
+        "setup.py",
+    ] {
+        touch(&root, file);
+    }
+
+    let files: Vec<PathBuf> = discover_python_files(&root, false)
+        .unwrap()
+        .into_iter()
+        .map(|file| file.strip_prefix(&root).unwrap().to_path_buf())
+        .collect();
+    assert_eq!(
+        files,
+        [
+            "pkg/__init__.py",
+            "pkg/_native.pyi",
+            "pkg/client.py",
+            "pkg/sub/module.py",
+            "setup.py"
+        ]
+        .map(PathBuf::from)
+    );
+}
+
+#[test]
+fn expands_glob_patterns_in_sorted_order() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("glob");
+    let _ = fs::remove_dir_all(&root);
+    for file in [
+        "src/zenml/client.py",
+        "src/zenml/steps/step.py",
+        "src/zenml/steps/step_2.py",
+        "src/zenml/steps/base_step.pyi",
+        "src/zenml/.hidden.py",
+        "src/zenml/__pycache__/client.py",
+    ] {
+        touch(&root, file);
+    }
+    let matches = |pattern: &str| -> Vec<String> {
+        let pattern = format!("{}/{}", root.display(), pattern);
+        expand_glob(&pattern, false)
+            .unwrap()
+            .iter()
+            .map(|file| normalize_path(file.strip_prefix(&root).unwrap()))
+            .collect()
+    };
+
+    assert_eq!(
+        matches("src/zenml/**/*.py"),
+        [
+            "src/zenml/client.py",
+            "src/zenml/steps/step.py",
+            "src/zenml/steps/step_2.py"
+        ]
+    );
+    assert_eq!(matches("src/*/*.py"), ["src/zenml/client.py"]);
+    assert_eq!(matches("src/zenml/steps/step?.py"), [] as [&str; 0]);
+    assert_eq!(
+        matches("src/zenml/steps/step_?.py"),
+        ["src/zenml/steps/step_2.py"]
+    );
+    assert_eq!(matches("src/**/base_*"), ["src/zenml/steps/base_step.pyi"]);
+    assert!(matches("docs/**/*.py").is_empty());
+}
+
+#[test]
+fn glob_base_is_the_literal_prefix() {
+    assert!(is_glob("src/**/*.py"));
+    assert!(!is_glob("src/zenml/client.py"));
+    assert_eq!(glob_base("src/zenml/**/*.py"), Path::new("src/zenml"));
+    assert_eq!(glob_base("*.py"), Path::new("."));
+    assert_eq!(glob_base(r"src\zenml\*.py"), Path::new("src/zenml"));
+}
+
+#[test]
+fn matches_exclude_patterns_against_relative_paths() {
+    let excluded = |pattern: &str, path: &str| glob_matches(pattern, Path::new(path));
+    assert!(excluded("**/tests/**", "pkg/tests/test_client.py"));
+    assert!(excluded("**/tests/**", "tests/conftest.py"));
+    assert!(excluded("**/_vendor/**", "pkg/_vendor/six/moves.py"));
+    assert!(excluded("migrations/*.py", "migrations/0001_initial.py"));
+    assert!(excluded("**/test_*.py", "pkg/test_client.py"));
+    assert!(!excluded("**/tests/**", "pkg/client.py"));
+    assert!(!excluded(
+        "migrations/*.py",
+        "pkg/migrations/0001_initial.py"
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn follows_symlinks_once_through_loops() {
+    use std::os::unix::fs::symlink;
+
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("symlinks");
+    let _ = fs::remove_dir_all(&root);
+    touch(&root, "pkg/client.py");
+    touch(&root, "vendor/six.py");
+    symlink(root.join("vendor"), root.join("pkg/vendored")).unwrap();
+    symlink(root.join("pkg"), root.join("pkg/loop")).unwrap();
+    symlink(root.join("pkg/client.py"), root.join("pkg/alias.py")).unwrap();
+    symlink(root.join("pkg/missing.py"), root.join("pkg/broken.py")).unwrap();
+    let discover = |follow_symlinks| -> Vec<String> {
+        discover_python_files(&root.join("pkg"), follow_symlinks)
+            .unwrap()
+            .iter()
+            .map(|file| normalize_path(file.strip_prefix(&root).unwrap()))
+            .collect()
+    };
+
+    assert_eq!(discover(false), ["pkg/client.py"]);
+    assert_eq!(discover(true), ["pkg/client.py", "pkg/vendored/six.py"]);
+}
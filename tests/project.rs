@@ -0,0 +1,273 @@
+use std::path::Path;
+
+use sdkdocgen::{parse_module, Category, DefinitionKind, ModuleDoc, Project, RenderOptions};
+
+fn module(path: &str, source: &str) -> ModuleDoc {
+    parse_module(
+        source,
+        Path::new(path),
+        &RenderOptions::builder().module_prefix("zenml").build(),
+    )
+    .unwrap()
+}
+
+fn project() -> Project {
+    Project::new(vec![
+        module(
+            "models.py",
+            r#"
+class Stack:
+    def describe(self):
+        pass
+
+
+def helper():
+    pass
+"#,
+        ),
+        module(
+            "__init__.py",
+            r#"
+from .models import Stack as PublicStack
+"#,
+        ),
+        module(
+            "client.py",
+            r#"
+import zenml.models as m
+from . import models
+from .models import Stack, helper
+from zenml import PublicStack
+
+
+def helper():
+    pass
+
+
+from .models import Stack
+"#,
+        ),
+    ])
+}
+
+fn resolved(project: &Project, module: &str, name: &str) -> Option<String> {
+    project
+        .resolve(module, name)
+        .map(|symbol| symbol.path.clone())
+}
+
+#[test]
+fn indexes_every_definition() {
+    let project = project();
+    let paths: Vec<(&str, DefinitionKind)> = project
+        .symbols()
+        .map(|symbol| (symbol.path.as_str(), symbol.kind))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("zenml", DefinitionKind::Module),
+            ("zenml.client", DefinitionKind::Module),
+            ("zenml.client.helper", DefinitionKind::Function),
+            ("zenml.models", DefinitionKind::Module),
+            ("zenml.models.Stack", DefinitionKind::Class),
+            ("zenml.models.Stack.describe", DefinitionKind::Method),
+            ("zenml.models.helper", DefinitionKind::Function),
+        ]
+    );
+}
+
+#[test]
+fn resolves_relative_imports_and_aliases() {
+    let project = project();
+    let resolve = |name| resolved(&project, "zenml.client", name);
+    assert_eq!(resolve("Stack").as_deref(), Some("zenml.models.Stack"));
+    assert_eq!(
+        resolve("models.Stack").as_deref(),
+        Some("zenml.models.Stack")
+    );
+    assert_eq!(
+        resolve("m.Stack.describe").as_deref(),
+        Some("zenml.models.Stack.describe")
+    );
+    assert_eq!(
+        resolve("zenml.models.helper").as_deref(),
+        Some("zenml.models.helper")
+    );
+    assert_eq!(resolve("Missing"), None);
+}
+
+#[test]
+fn follows_reexports() {
+    let project = project();
+    assert_eq!(
+        resolved(&project, "zenml.client", "PublicStack").as_deref(),
+        Some("zenml.models.Stack")
+    );
+    assert_eq!(
+        resolved(&project, "zenml.models", "zenml.PublicStack.describe").as_deref(),
+        Some("zenml.models.Stack.describe")
+    );
+}
+
+#[test]
+fn later_bindings_shadow_earlier_ones() {
+    let project = project();
+    // `def helper` comes after `from .models import helper`.
+    assert_eq!(
+        resolved(&project, "zenml.client", "helper").as_deref(),
+        Some("zenml.client.helper")
+    );
+}
+
+#[test]
+fn local_definitions_win_over_earlier_imports() {
+    let project = Project::new(vec![
+        module("models.py", "class Stack:\n    pass\n"),
+        module(
+            "client.py",
+            "from .models import Stack\n\n\nclass Stack:\n    pass\n",
+        ),
+    ]);
+    assert_eq!(
+        resolved(&project, "zenml.client", "Stack").as_deref(),
+        Some("zenml.client.Stack")
+    );
+}
+
+#[test]
+fn links_within_and_across_pages() {
+    let project = project();
+    let stack = project.symbol("zenml.models.Stack").unwrap();
+    assert_eq!(project.link("zenml.models", stack), "#zenml.models.Stack");
+    assert_eq!(
+        project.link("zenml.client", stack),
+        "models#zenml.models.Stack"
+    );
+}
+
+#[test]
+fn reports_module_collisions() {
+    let project = Project::new(vec![
+        module("models.py", "class Stack:\n    pass\n"),
+        module("models.py", "class Other:\n    pass\n"),
+    ]);
+    assert_eq!(project.modules().len(), 1);
+    assert!(project.symbol("zenml.models.Stack").is_some());
+    assert!(project.symbol("zenml.models.Other").is_none());
+
+    let categories: Vec<Category> = project
+        .diagnostics()
+        .iter()
+        .map(|diagnostic| diagnostic.category)
+        .collect();
+    assert_eq!(categories, [Category::Collision]);
+}
+
+#[test]
+fn finds_subclasses_across_modules() {
+    let project = Project::new(vec![
+        module(
+            "base.py",
+            r#"
+class StackComponent:
+    pass
+
+
+class BaseOrchestrator(StackComponent):
+    pass
+"#,
+        ),
+        module(
+            "local.py",
+            r#"
+from zenml import base
+from zenml.base import BaseOrchestrator
+from pydantic import BaseModel
+
+
+class LocalOrchestrator(BaseOrchestrator, BaseModel):
+    pass
+
+
+class LocalStepOperator(base.StackComponent):
+    pass
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        project.bases("zenml.local.LocalOrchestrator"),
+        ["zenml.base.BaseOrchestrator"]
+    );
+    assert_eq!(
+        project.subclasses("zenml.base.StackComponent"),
+        [
+            "zenml.base.BaseOrchestrator",
+            "zenml.local.LocalStepOperator"
+        ]
+    );
+    assert_eq!(
+        project.subclasses("zenml.base.BaseOrchestrator"),
+        ["zenml.local.LocalOrchestrator"]
+    );
+    assert!(project
+        .subclasses("zenml.local.LocalOrchestrator")
+        .is_empty());
+}
+
+#[test]
+fn indexes_references_in_annotations_bases_and_decorators() {
+    let project = Project::new(vec![
+        module(
+            "models.py",
+            r#"
+def flavor(cls):
+    return cls
+
+
+class Stack:
+    def copy(self) -> Stack:
+        pass
+"#,
+        ),
+        module(
+            "client.py",
+            r#"
+from typing import Optional
+
+from zenml import models
+from zenml.models import Stack, flavor
+
+
+@flavor
+class LocalStack(models.Stack):
+    pass
+
+
+def get_stack(name: str = "default") -> Optional[Stack]:
+    return Stack()
+
+
+class Client:
+    @property
+    def active_stack(self) -> Stack:
+        pass
+"#,
+        ),
+    ]);
+
+    assert_eq!(
+        project.referenced_by("zenml.models.Stack"),
+        [
+            "zenml.client.Client.active_stack",
+            "zenml.client.LocalStack",
+            "zenml.client.get_stack"
+        ]
+    );
+    assert_eq!(
+        project.referenced_by("zenml.models.flavor"),
+        ["zenml.client.LocalStack"]
+    );
+    assert!(project.referenced_by("zenml.client.Client").is_empty());
+}
@@ -0,0 +1,89 @@
+use std::fs;
+use std::path::Path;
+
+use sdkdocgen::{
+    inline_private_reexports, parse_module, render_module, Category, Project, RenderOptions,
+};
+
+#[test]
+fn documents_private_definitions_on_the_public_module() {
+    let path = Path::new("tests/fixtures/reexports/__init__.py");
+    let options = RenderOptions::builder().module_prefix("zenml").build();
+    let mut module = parse_module(&fs::read_to_string(path).unwrap(), path, &options).unwrap();
+    inline_private_reexports(&mut module, &options);
+
+    let reexports: Vec<(&str, &str, &str)> = module
+        .reexports
+        .iter()
+        .map(|reexport| {
+            (
+                reexport.item.name(),
+                reexport.original_name.as_str(),
+                reexport.module.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        reexports,
+        [
+            ("Component", "Component", "zenml._internal.components"),
+            ("Stack", "Stack", "zenml._internal.models"),
+            ("make_stack", "build_stack", "zenml._internal.models"),
+        ]
+    );
+
+    // `missing` is imported from a private module that does not define it.
+    let unresolved: Vec<(usize, &str)> = module
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.category == Category::UnresolvedReference)
+        .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(
+        unresolved,
+        [(6, "`missing` is not defined in `zenml._internal.models`")]
+    );
+
+    // Links to the re-exported names point at the inlined entries.
+    let project = Project::new(vec![module]);
+    let resolved = |name| {
+        project
+            .resolve("zenml", name)
+            .map(|symbol| symbol.path.clone())
+    };
+    assert_eq!(resolved("Stack").as_deref(), Some("zenml.Stack"));
+    assert_eq!(
+        resolved("Stack.describe").as_deref(),
+        Some("zenml.Stack.describe")
+    );
+    assert_eq!(resolved("make_stack").as_deref(), Some("zenml.make_stack"));
+}
+
+#[test]
+fn links_names_a_package_imports_to_their_pages() {
+    let options = RenderOptions::builder().module_prefix("pkg").build();
+    let package_source = "\
+from .client import Client as Api
+from .client import helper
+from pydantic import BaseModel
+";
+    let client_source = "class Client:\n    \"\"\"A client.\"\"\"\n\n\ndef helper():\n    pass\n";
+    let mut package = parse_module(package_source, Path::new("pkg/__init__.py"), &options).unwrap();
+    package.page = "pkg/index".to_string();
+    let mut client = parse_module(client_source, Path::new("pkg/client.py"), &options).unwrap();
+    client.page = "pkg/client".to_string();
+    let project = Project::new(vec![package, client]);
+
+    let package = project.module("pkg").unwrap();
+    let page = render_module(package, &project, &options);
+    assert!(
+        page.contains(
+            "| Name | Imported from |\n| --- | --- |\n\
+             | [`Api`](client#pkg.client.Client) | `pkg.client.Client` |\n\
+             | [`helper`](client#pkg.client.helper) | `pkg.client.helper` |\n\
+             | `BaseModel` | `pydantic.BaseModel` |\n"
+        ),
+        "{}",
+        page
+    );
+}
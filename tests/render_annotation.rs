@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use sdkdocgen::{render_annotation, OptionalStyle, TypeRenderOptions};
+
+fn render(source: &str) -> String {
+    render_annotation(source, &TypeRenderOptions::default()).unwrap()
+}
+
+fn render_with(source: &str, options: &TypeRenderOptions) -> String {
+    render_annotation(source, options).unwrap()
+}
+
+#[test]
+fn renders_names_attributes_and_subscripts() {
+    assert_eq!(render("int"), "int");
+    assert_eq!(render("pathlib.Path"), "pathlib.Path");
+    assert_eq!(render("Dict[str, List[int]]"), "Dict[str, List[int]]");
+    assert_eq!(render("int | str"), "int | str");
+    assert_eq!(render("int | None"), "int | None");
+}
+
+#[test]
+fn surrounding_whitespace_is_ignored() {
+    assert_eq!(render("  Optional[str]\n"), "Optional[str]");
+}
+
+#[test]
+fn invalid_annotations_are_errors() {
+    assert!(render_annotation("List[", &TypeRenderOptions::default()).is_err());
+    assert!(render_annotation("", &TypeRenderOptions::default()).is_err());
+}
+
+#[test]
+fn renders_forward_references_as_their_annotation() {
+    assert_eq!(render("\"StackComponent\""), "StackComponent");
+    assert_eq!(render("List[\"Foo\"]"), "List[Foo]");
+    assert_eq!(
+        render("'Optional[typing.List[str]]'"),
+        "Optional[typing.List[str]]"
+    );
+    assert_eq!(render("\"not an [annotation\""), "not an [annotation");
+}
+
+#[test]
+fn optional_pipe_style() {
+    let options = TypeRenderOptions {
+        optional_style: OptionalStyle::Pipe,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(render_with("Optional[int]", &options), "int | None");
+    assert_eq!(
+        render_with("typing.Optional[List[str]]", &options),
+        "List[str] | None"
+    );
+    assert_eq!(
+        render_with("Dict[str, Optional[int]]", &options),
+        "Dict[str, int | None]"
+    );
+    assert_eq!(render("Optional[int]"), "Optional[int]");
+}
+
+#[test]
+fn renders_literal_values_and_annotated_metadata() {
+    assert_eq!(
+        render("Literal[\"json\", 'yaml', 1, True, None]"),
+        "Literal[\"json\", \"yaml\", 1, True, None]"
+    );
+    assert_eq!(
+        render("Annotated[int, Field(gt=0), \"port\"]"),
+        "Annotated[int, Field(gt=0), \"port\"]"
+    );
+    assert_eq!(render("Tuple[int, ...]"), "Tuple[int, ...]");
+
+    let collapsed = TypeRenderOptions {
+        collapse_annotated: true,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(
+        render_with("Optional[Annotated[\"Stack\", Field(gt=0)]]", &collapsed),
+        "Optional[Stack]"
+    );
+}
+
+#[test]
+fn normalizes_unions() {
+    let options = TypeRenderOptions {
+        optional_style: OptionalStyle::PipeUnions,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(render_with("Optional[int]", &options), "int | None");
+    assert_eq!(
+        render_with("typing.Union[int, str, None]", &options),
+        "int | str | None"
+    );
+    assert_eq!(
+        render_with("Union[int, Optional[\"Stack\"]]", &options),
+        "int | Stack | None"
+    );
+    assert_eq!(render_with("Union[int]", &options), "int");
+    assert_eq!(render("Union[int, str]"), "Union[int, str]");
+    assert_eq!(render("None"), "None");
+}
+
+#[test]
+fn strips_typing_prefixes() {
+    let options = TypeRenderOptions {
+        strip_typing_prefix: true,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(
+        render_with("typing.List[typing.Any]", &options),
+        "List[Any]"
+    );
+    assert_eq!(render_with("typing_extensions.Self", &options), "Self");
+    assert_eq!(
+        render_with("collections.abc.Mapping", &options),
+        "collections.abc.Mapping"
+    );
+    assert_eq!(render("typing.List[int]"), "typing.List[int]");
+}
+
+#[test]
+fn resolves_aliases_from_the_import_map() {
+    let aliases = BTreeMap::from([
+        ("np".to_string(), "numpy".to_string()),
+        ("Seq".to_string(), "typing.Sequence".to_string()),
+        ("t".to_string(), "typing".to_string()),
+    ]);
+    let options = TypeRenderOptions {
+        aliases: aliases.clone(),
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(render_with("np.ndarray", &options), "numpy.ndarray");
+    assert_eq!(
+        render_with("Seq[np.float64]", &options),
+        "typing.Sequence[numpy.float64]"
+    );
+
+    let options = TypeRenderOptions {
+        aliases,
+        strip_typing_prefix: true,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(render_with("Seq[int]", &options), "Sequence[int]");
+    assert_eq!(render_with("t.Optional[int]", &options), "Optional[int]");
+}
+
+#[test]
+fn renders_callable_signatures() {
+    assert_eq!(
+        render("Callable[[int, str], bool]"),
+        "Callable[[int, str], bool]"
+    );
+    assert_eq!(
+        render("Callable[..., Awaitable[None]]"),
+        "Callable[..., Awaitable[None]]"
+    );
+    assert_eq!(
+        render("Callable[Concatenate[int, P], R]"),
+        "Callable[Concatenate[int, P], R]"
+    );
+    assert_eq!(
+        render("Callable[[Callable[P, R]], Callable[P, Optional[R]]]"),
+        "Callable[[Callable[P, R]], Callable[P, Optional[R]]]"
+    );
+    assert_eq!(render("Tuple[int, ...]"), "Tuple[int, ...]");
+
+    let options = TypeRenderOptions {
+        strip_typing_prefix: true,
+        ..TypeRenderOptions::default()
+    };
+    assert_eq!(
+        render_with("typing.Callable[..., typing.Awaitable[None]]", &options),
+        "Callable[..., Awaitable[None]]"
+    );
+}
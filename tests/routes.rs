@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::Path;
+
+use sdkdocgen::{
+    extract_routes, render_routes_page, Endpoint, EndpointParameter, ParameterLocation,
+};
+
+fn extract(fixture: &str) -> Vec<Endpoint> {
+    let path = Path::new("tests/fixtures/routes").join(fixture);
+    extract_routes(&fs::read_to_string(&path).unwrap(), &path).unwrap()
+}
+
+fn parameter(
+    name: &str,
+    location: ParameterLocation,
+    type_name: &str,
+    default: Option<&str>,
+    required: bool,
+    description: Option<&str>,
+) -> EndpointParameter {
+    EndpointParameter {
+        name: name.to_string(),
+        location,
+        type_name: type_name.to_string(),
+        default: default.map(str::to_string),
+        required,
+        description: description.map(str::to_string),
+    }
+}
+
+#[test]
+fn extracts_fastapi_endpoints() {
+    let endpoints = extract("fastapi_app.py");
+    let routes: Vec<(String, &str)> = endpoints
+        .iter()
+        .map(|endpoint| (endpoint.methods.join(","), endpoint.path.as_str()))
+        .collect();
+    assert_eq!(
+        routes,
+        [
+            ("GET".to_string(), "/api/v1/items/{item_id}"),
+            ("POST".to_string(), "/api/v1/items/"),
+            ("GET,HEAD".to_string(), "/health"),
+        ]
+    );
+
+    let read = &endpoints[0];
+    assert_eq!(
+        read.help.as_deref(),
+        Some("Read an item.\n\nLooks the item up by its id.")
+    );
+    assert_eq!(read.response.as_deref(), Some("Item"));
+    // The dependency and the injected request are not request parameters.
+    assert_eq!(
+        read.parameters,
+        [
+            parameter(
+                "item_id",
+                ParameterLocation::Path,
+                "int",
+                None,
+                true,
+                Some("Id of the item.")
+            ),
+            parameter(
+                "q",
+                ParameterLocation::Query,
+                "Optional[str]",
+                Some("None"),
+                false,
+                Some("Free-text filter.")
+            ),
+        ]
+    );
+
+    let create = &endpoints[1];
+    assert_eq!(create.status_code.as_deref(), Some("201"));
+    assert_eq!(create.response.as_deref(), Some("Item"));
+    assert_eq!(
+        create.parameters,
+        [
+            parameter("item", ParameterLocation::Body, "Item", None, true, None),
+            parameter(
+                "x_token",
+                ParameterLocation::Header,
+                "str",
+                None,
+                true,
+                Some("API token.")
+            ),
+        ]
+    );
+
+    let health = &endpoints[2];
+    assert_eq!(health.help.as_deref(), Some("Check the service."));
+    assert_eq!(
+        health.parameters,
+        [
+            parameter(
+                "verbose",
+                ParameterLocation::Query,
+                "bool",
+                Some("False"),
+                false,
+                Some("Include details.")
+            ),
+            parameter(
+                "tags",
+                ParameterLocation::Query,
+                "List[str]",
+                None,
+                true,
+                None
+            ),
+        ]
+    );
+}
+
+#[test]
+fn extracts_flask_endpoints() {
+    let endpoints = extract("flask_app.py");
+    let routes: Vec<(String, &str, &str)> = endpoints
+        .iter()
+        .map(|endpoint| {
+            (
+                endpoint.methods.join(","),
+                endpoint.path.as_str(),
+                endpoint.function.as_str(),
+            )
+        })
+        .collect();
+    // The prefix given to `register_blueprint` replaces the blueprint's own.
+    assert_eq!(
+        routes,
+        [
+            ("GET,DELETE".to_string(), "/api/users/<int:user_id>", "user"),
+            ("POST".to_string(), "/api/users/", "create_user"),
+            ("GET".to_string(), "/", "index"),
+        ]
+    );
+    assert_eq!(
+        endpoints[0].parameters,
+        [parameter(
+            "user_id",
+            ParameterLocation::Path,
+            "int",
+            None,
+            true,
+            Some("Id of the user.")
+        )]
+    );
+    assert_eq!(endpoints[2].help, None);
+}
+
+#[test]
+fn renders_an_endpoint_table_and_sections() {
+    let page = render_routes_page("fastapi_app", &extract("fastapi_app.py"));
+    assert!(page.starts_with("---\ntitle: fastapi_app\n---\n\n| Method | Path | Description |\n"));
+    assert!(
+        page.contains("| GET | [`/api/v1/items/{item_id}`](#routes.read_item) | Read an item. |\n")
+    );
+    assert!(page.contains(
+        "<a id=\"routes.create_item\"></a>\n### `POST` `/api/v1/items/`\n\nCreate an item.\n\n"
+    ));
+    assert!(page.contains("| `item` | body | `Item` | - | yes |  |\n"));
+    assert!(page.contains("**Response:** `201` `Item`\n"));
+}
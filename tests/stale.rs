@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sdkdocgen::{stale_pages, unified_diff, Staleness};
+
+#[test]
+fn reports_changed_missing_and_extra_files() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("stale");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("cli")).unwrap();
+    fs::write(root.join("client.mdx"), "# client\n").unwrap();
+    fs::write(root.join("stack.mdx"), "# stack\nold\n").unwrap();
+    fs::write(root.join("cli/removed.mdx"), "# removed\n").unwrap();
+    fs::write(root.join("notes.txt"), "kept\n").unwrap();
+
+    let pages = [
+        (root.join("client.mdx"), "# client\n".to_string()),
+        (root.join("stack.mdx"), "# stack\nnew\n".to_string()),
+        (root.join("pipeline.mdx"), "# pipeline\n".to_string()),
+    ];
+    let stale: Vec<(PathBuf, Staleness)> = stale_pages(&pages, &root, "mdx", 20)
+        .into_iter()
+        .map(|page| {
+            (
+                page.path.strip_prefix(&root).unwrap().to_path_buf(),
+                page.staleness,
+            )
+        })
+        .collect();
+    assert_eq!(
+        stale,
+        [
+            (
+                PathBuf::from("stack.mdx"),
+                Staleness::Changed {
+                    diff: "@@ -1,2 +1,2 @@\n # stack\n-old\n+new\n".to_string()
+                }
+            ),
+            (PathBuf::from("pipeline.mdx"), Staleness::Missing),
+            (PathBuf::from("cli/removed.mdx"), Staleness::Extra),
+        ]
+    );
+}
+
+#[test]
+fn diffs_only_the_first_changed_lines() {
+    let old: String = (1..=30).map(|line| format!("line {}\n", line)).collect();
+    let new = old
+        .replace("line 2\n", "line two\n")
+        .replace("line 29\n", "");
+
+    assert_eq!(
+        unified_diff(&old, &new, 20),
+        "@@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+         @@ -26,5 +26,4 @@\n line 26\n line 27\n line 28\n-line 29\n line 30\n"
+    );
+    assert_eq!(
+        unified_diff(&old, &new, 1),
+        "@@ -1,5 +1,5 @@\n line 1\n-line 2\n... 2 more changed line(s)\n"
+    );
+}
@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{
+    merge_stub, merge_stub_signatures, parse_module, stub_in_dir, Category, Item, ModuleDoc,
+    RenderOptions, StubPreference,
+};
+
+const IMPLEMENTATION: &str = r#"
+def get(key, default=None, *, strict: bool = False):
+    """Fetch a value.
+
+    Args:
+        key: The key to look up.
+    """
+
+
+def put(key, value):
+    """Store a value."""
+"#;
+
+const STUB: &str = r#"
+def get(key: str, default: int = ..., *, strict: str = ...) -> int: ...
+def put(key: str) -> None:
+    """Stub docstring."""
+"#;
+
+fn merged(prefer: StubPreference) -> ModuleDoc {
+    let options = RenderOptions::default();
+    let mut module = parse_module(IMPLEMENTATION, Path::new("store.py"), &options).unwrap();
+    let stub = parse_module(STUB, Path::new("store.pyi"), &options).unwrap();
+    merge_stub(&mut module, &stub, prefer);
+    module
+}
+
+fn signature(module: &ModuleDoc, name: &str) -> Vec<(String, Option<String>)> {
+    let function = module
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Function(function) if function.name == name => Some(function),
+            _ => None,
+        })
+        .unwrap();
+    function
+        .parameters
+        .iter()
+        .map(|p| (p.name.clone(), p.annotation.clone()))
+        .collect()
+}
+
+fn annotated(name: &str, annotation: Option<&str>) -> (String, Option<String>) {
+    (name.to_string(), annotation.map(str::to_string))
+}
+
+#[test]
+fn prefers_stub_signatures_with_implementation_docs() {
+    let module = merged(StubPreference::Stub);
+    assert_eq!(
+        signature(&module, "get"),
+        [
+            annotated("key", Some("str")),
+            annotated("default", Some("int")),
+            annotated("strict", Some("str")),
+        ]
+    );
+    assert_eq!(signature(&module, "put"), [annotated("key", Some("str"))]);
+
+    let Item::Function(get) = &module.items[0] else {
+        panic!("expected a function");
+    };
+    assert_eq!(get.returns.as_deref(), Some("int"));
+    // The real default replaces the stub's `...`.
+    assert!(get.parameters[1]
+        .default
+        .as_deref()
+        .unwrap()
+        .contains("None"));
+    assert_eq!(
+        get.parameters[0].description.as_deref(),
+        Some("The key to look up.")
+    );
+    assert!(get.docstring.as_deref().unwrap().contains("Fetch a value."));
+}
+
+#[test]
+fn implementation_preference_only_fills_gaps() {
+    let module = merged(StubPreference::Impl);
+    assert_eq!(
+        signature(&module, "get"),
+        [
+            annotated("key", Some("str")),
+            annotated("default", Some("int")),
+            annotated("strict", Some("bool")),
+        ]
+    );
+    assert_eq!(
+        signature(&module, "put"),
+        [annotated("key", Some("str")), annotated("value", None)]
+    );
+}
+
+#[test]
+fn flags_mismatched_parameter_lists() {
+    let module = merged(StubPreference::Stub);
+    let mismatches: Vec<(usize, &str)> = module
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.category == Category::StubMismatch)
+        .map(|diagnostic| (diagnostic.line, diagnostic.message.as_str()))
+        .collect();
+    assert_eq!(
+        mismatches,
+        [(10, "`put` takes (key, value) but its stub declares (key)")]
+    );
+}
+
+#[test]
+fn merges_a_stub_from_a_stubs_directory() {
+    let stubs_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("stubs");
+    let _ = fs::remove_dir_all(&stubs_dir);
+    let stub_path = stub_in_dir(&stubs_dir, Path::new("pkg/store.py"));
+    assert_eq!(stub_path, stubs_dir.join("pkg/store.pyi"));
+    fs::create_dir_all(stub_path.parent().unwrap()).unwrap();
+    fs::write(&stub_path, format!("{}\ndef native() -> int: ...\n", STUB)).unwrap();
+
+    let options = RenderOptions::default();
+    let mut module = parse_module(IMPLEMENTATION, Path::new("store.py"), &options).unwrap();
+    assert!(merge_stub_signatures(
+        &mut module,
+        &stub_path,
+        &options,
+        StubPreference::Impl
+    ));
+
+    let names: Vec<&str> = module.items.iter().map(|item| item.name()).collect();
+    assert_eq!(names, ["get", "put"]);
+    assert_eq!(
+        signature(&module, "put"),
+        [annotated("key", Some("str")), annotated("value", None)]
+    );
+    assert!(module
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.message.starts_with("`put` takes (key, value)")));
+    assert!(!merge_stub_signatures(
+        &mut module,
+        &stubs_dir.join("missing.pyi"),
+        &options,
+        StubPreference::Impl
+    ));
+}
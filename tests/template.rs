@@ -0,0 +1,47 @@
+use std::path::Path;
+
+use sdkdocgen::{parse_module, OutputTemplate, RenderOptions};
+
+#[test]
+fn names_pages_from_the_placeholders() {
+    let options = RenderOptions::builder()
+        .module_prefix("zenml.client")
+        .build();
+    let mut module = parse_module("", Path::new("src/_base_client.py"), &options).unwrap();
+    module.page = "client/_base_client".to_string();
+
+    let page = |template: &str| {
+        let template: OutputTemplate = template.parse().unwrap();
+        assert!(template.ends_with_extension("mdx"));
+        template.page(&module, "mdx")
+    };
+    assert_eq!(page("{page}.{ext}"), "client/_base_client");
+    assert_eq!(
+        page("{module_path}.api.mdx"),
+        "zenml.client._base_client.api"
+    );
+    assert_eq!(page("api/{stem}.{ext}"), "api/_base_client");
+    assert_eq!(page("{kebab}.mdx"), "client/base-client");
+}
+
+#[test]
+fn rejects_invalid_templates() {
+    let error = |template: &str| template.parse::<OutputTemplate>().unwrap_err();
+    assert_eq!(
+        error("{name}.mdx"),
+        "unknown placeholder `{name}` in output template `{name}.mdx`; \
+         expected one of {page}, {stem}, {kebab}, {module_path}, {ext}"
+    );
+    assert_eq!(
+        error("{page.mdx"),
+        "unmatched `{` in output template `{page.mdx`"
+    );
+    assert_eq!(
+        error("../{page}.mdx"),
+        "output template `../{page}.mdx` must stay inside the output directory"
+    );
+
+    let markdown: OutputTemplate = "{page}.md".parse().unwrap();
+    assert!(!markdown.ends_with_extension("mdx"));
+    assert!(markdown.ends_with_extension("md"));
+}
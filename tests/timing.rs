@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use sdkdocgen::{parse_module, render_module, Project, RenderOptions, TimingLayer};
+use tracing_subscriber::prelude::*;
+
+#[test]
+fn records_parse_and_render_per_file() {
+    let (layer, timings) = TimingLayer::new();
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        let options = RenderOptions::default();
+        let module = parse_module(
+            "def f():\n    \"\"\"Doc.\"\"\"\n",
+            Path::new("pkg/f.py"),
+            &options,
+        )
+        .unwrap();
+        let project = Project::new(vec![module]);
+        render_module(&project.modules()[0], &project, &options);
+    });
+
+    let timings = timings.lock().unwrap();
+    assert_eq!(timings.phases["parse"].count, 1);
+    assert_eq!(timings.phases["render"].count, 1);
+    assert!(timings.phases.contains_key("docstring"));
+    assert_eq!(timings.files.keys().collect::<Vec<_>>(), ["pkg/f.py"]);
+    assert!(timings.report().contains("* pkg/f.py"));
+}
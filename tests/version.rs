@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sdkdocgen::{parse_module, pyproject_version, render_module, Project, RenderOptions};
+
+#[test]
+fn reads_the_nearest_pyproject() {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("version");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src/pkg")).unwrap();
+    fs::create_dir_all(root.join("poetry/pkg")).unwrap();
+    fs::write(root.join("src/pkg/client.py"), "").unwrap();
+    fs::write(root.join("poetry/pkg/client.py"), "").unwrap();
+    fs::write(
+        root.join("pyproject.toml"),
+        "[project]\nname = \"pkg\"\nversion = \"0.55.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("poetry/pyproject.toml"),
+        "[tool.poetry]\nname = \"pkg\"\nversion = \"1.2.0\"\n",
+    )
+    .unwrap();
+
+    let (file, version) = pyproject_version(&root.join("src/pkg/client.py")).unwrap();
+    assert_eq!(version, "0.55.0");
+    assert_eq!(file, fs::canonicalize(root.join("pyproject.toml")).unwrap());
+    let (_, version) = pyproject_version(&root.join("poetry/pkg/client.py")).unwrap();
+    assert_eq!(version, "1.2.0");
+}
+
+#[test]
+fn writes_the_version_to_the_frontmatter() {
+    let source = "__version__ = \"0.55.0\"\n";
+    let options = RenderOptions::default();
+    let module = parse_module(source, Path::new("zenml.py"), &options).unwrap();
+    assert_eq!(module.version.as_deref(), Some("0.55.0"));
+
+    let options = RenderOptions::builder()
+        .doc_version(Some("0.56.0".to_string()))
+        .build();
+    let project = Project::new(vec![module]);
+    let page = render_module(&project.modules()[0], &project, &options);
+    assert!(
+        page.starts_with("---\ntitle: zenml\nversion: \"0.56.0\"\n---\n"),
+        "{}",
+        page
+    );
+}